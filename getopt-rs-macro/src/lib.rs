@@ -11,6 +11,7 @@ struct GetoptArgs {
     iterator: Option<Expr>,
     parsers: Punctuated<Expr, Comma>,
     sets: Punctuated<Expr, Comma>,
+    callback: Option<Expr>,
 }
 
 enum ParseState {
@@ -20,51 +21,44 @@ enum ParseState {
 
 impl Parse for GetoptArgs {
     fn parse(input: ParseStream) -> Result<Self> {
+        let mut args: Punctuated<Expr, Comma> = Punctuated::parse_terminated(input)?;
         let mut parsers = Punctuated::new();
         let mut sets = Punctuated::new();
         let mut state = ParseState::PSParser;
-        let iterator: Expr = input.parse()?;
-        let comma: Comma = input.parse()?;
+        let mut callback = None;
 
-        while ! input.is_empty() {
+        // the last argument may be a closure called with matched parser
+        if let Some(Expr::Closure(_)) = args.last() {
+            callback = args.pop().map(|v| v.into_value());
+        }
+
+        let mut args = args.into_iter();
+        let iterator = if args.len() % 2 == 1 {
+            args.next()
+        }
+        else {
+            None
+        };
+
+        for arg in args {
             match state {
                 ParseState::PSParser => {
-                    let parse: Expr = input.parse()?;
-                    parsers.push(parse);
+                    parsers.push(arg);
                     state = ParseState::PSSet;
-                    if input.is_empty() {
-                        break;
-                    }
-                    parsers.push_punct(input.parse()?);
                 }
                 ParseState::PSSet => {
-                    let set: Expr = input.parse()?;
-                    sets.push(set);
+                    sets.push(arg);
                     state = ParseState::PSParser;
-                    if input.is_empty() {
-                        break;
-                    }
-                    sets.push_punct(input.parse()?);
                 }
             }
         }
 
-        if parsers.len() > sets.len() {
-            sets.push_value(iterator);
-            sets.push_punct(comma);
-            Ok(GetoptArgs {
-                iterator: None,
-                parsers: sets,
-                sets: parsers,
-            })
-        }
-        else {
-            Ok(GetoptArgs {
-                iterator: Some(iterator),
-                parsers,
-                sets,
-            })
-        }
+        Ok(GetoptArgs {
+            iterator,
+            parsers,
+            sets,
+            callback,
+        })
     }
 }
 
@@ -114,7 +108,7 @@ pub fn getopt(input: TokenStream) -> TokenStream {
                 let mut parsers: Vec<Box<dyn Parser<DefaultSet, DefaultIdGen>>> = vec![];
                 let mut ai = ArgIterator::new();
                 
-                ai.set_args(&mut std::env::args().skip(1));
+                ai.set_args_with_prog(&mut std::env::args());
             }
         }
     };
@@ -130,10 +124,20 @@ pub fn getopt(input: TokenStream) -> TokenStream {
             }
     ));
 
-    let ret = quote! {{
-        #getopt_init
-        getopt_impl(#iterator, parsers)
-    }};
+    let ret = match getopt_args.callback.as_ref() {
+        Some(callback) => {
+            quote! {{
+                #getopt_init
+                getopt_impl(#iterator, parsers).map(|ret| ret.map(#callback))
+            }}
+        }
+        None => {
+            quote! {{
+                #getopt_init
+                getopt_impl(#iterator, parsers)
+            }}
+        }
+    };
     ret.into()
 }
 
@@ -184,7 +188,7 @@ pub fn getopt(input: TokenStream) -> TokenStream {
                 let mut parsers: Vec<Box<dyn Parser<DefaultSet, DefaultIdGen>>> = vec![];
                 let mut ai = ArgIterator::new();
                 
-                ai.set_args(&mut std::env::args().skip(1));
+                ai.set_args_with_prog(&mut std::env::args());
             }
         }
     };
@@ -200,9 +204,19 @@ pub fn getopt(input: TokenStream) -> TokenStream {
             }
     ));
 
-    let ret = quote! {async {
-        #getopt_init
-        getopt_impl(#iterator, parsers).await
-    }};
+    let ret = match getopt_args.callback.as_ref() {
+        Some(callback) => {
+            quote! {async {
+                #getopt_init
+                getopt_impl(#iterator, parsers).await.map(|ret| ret.map(#callback))
+            }}
+        }
+        None => {
+            quote! {async {
+                #getopt_init
+                getopt_impl(#iterator, parsers).await
+            }}
+        }
+    };
     ret.into()
 }
\ No newline at end of file
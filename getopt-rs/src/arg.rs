@@ -43,6 +43,11 @@ use crate::error::{Result, Error};
 /// ai.skip();
 /// assert!(ai.reach_end());
 /// ``` 
+/// 
+/// The parser only access the arguments through this trait, so you can implement it
+/// for your own argument source, such as a channel or stream.
+/// The parser call [`reset`](IndexIterator::reset) first, then call [`fill_current_and_next`](IndexIterator::fill_current_and_next)
+/// and [`skip`](IndexIterator::skip) until [`reach_end`](IndexIterator::reach_end) return true.
 #[async_trait]
 pub trait IndexIterator : Debug {
     /// Set [`std::iter::Iterator`] of arguments.
@@ -66,6 +71,17 @@ pub trait IndexIterator : Debug {
     /// Get next argument
     fn next(&self) -> &Option<String>;
 
+    /// Get the argument at offset `n` from current index, `peek_nth(0)` is current argument.
+    /// Return None if it is past the end.
+    /// The default implementation can only peek the current and next argument.
+    fn peek_nth(&self, n: usize) -> Option<&String> {
+        match n {
+            0 => self.current().as_ref(),
+            1 => self.next().as_ref(),
+            _ => None,
+        }
+    }
+
     /// Increment the index to next argument 
     fn skip(&mut self);
 
@@ -77,7 +93,26 @@ pub trait IndexIterator : Debug {
     #[cfg(feature="async")]
     async fn parse(&self, prefixs: &Vec<String>) -> Result<Argument>;
 
+    /// Reset the index to the first argument
     fn reset(&mut self);
+
+    /// Save current position, it can be restored by [`restore`](IndexIterator::restore).
+    fn checkpoint(&self) -> usize {
+        self.current_index()
+    }
+
+    /// Restore the position saved by [`checkpoint`](IndexIterator::checkpoint),
+    /// the current and next argument are filled if they are available.
+    /// The default implementation [`reset`](IndexIterator::reset) and skip to the position.
+    fn restore(&mut self, cp: usize) {
+        self.reset();
+        while self.current_index() < cp && ! self.reach_end() {
+            self.skip();
+        }
+        if ! self.reach_end() {
+            self.fill_current_and_next();
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -109,6 +144,25 @@ impl Argument {
     pub fn get_value(&self) -> Option<&String> {
         self.value.as_ref()
     }
+
+    pub fn set_name(&mut self, name: Option<String>) {
+        self.name = name;
+    }
+
+    pub fn set_value(&mut self, value: Option<String>) {
+        self.value = value;
+    }
+}
+
+/// How [`ArgIterator::set_args_os`] handle the argument which is not valid unicode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OsArgPolicy {
+    /// Replace the invalid sequence with `U+FFFD`.
+    #[default]
+    Lossy,
+
+    /// Return [`Error::InvalidCommandLine`].
+    Strict,
 }
 
 #[derive(Debug, Default)]
@@ -124,6 +178,8 @@ pub struct ArgIterator {
     arg: Option<String>,
 
     next_arg: Option<String>,
+
+    prog_name: Option<String>,
 }
 
 impl ArgIterator {
@@ -135,6 +191,7 @@ impl ArgIterator {
             args: vec![],
             arg: None,
             next_arg: None,
+            prog_name: None,
         }
     }
 
@@ -146,10 +203,91 @@ impl ArgIterator {
             args: vec![],
             arg: None,
             next_arg: None,
+            prog_name: None,
         };
         ret.set_args(args);
         ret
     }
+
+    /// Create the iterator from arguments, such as `ArgIterator::from_iter(rx.iter())`.
+    /// The arguments are collected eagerly.
+    pub fn from_iter<I: Iterator<Item = String>>(iter: I) -> Self {
+        iter.collect()
+    }
+
+    /// Set the first argument as program name, and the rest as arguments.
+    /// It is useful for the full argv such as [`std::env::args`].
+    pub fn set_args_with_prog(&mut self, args: &mut dyn std::iter::Iterator<Item = String>) {
+        self.prog_name = args.next();
+        self.set_args(args);
+    }
+
+    /// Set the arguments from [`OsString`](std::ffi::OsString), such as [`std::env::args_os`].
+    /// The argument not valid unicode is converted according to the `policy`,
+    /// the arguments not changed if any error occurred.
+    pub fn set_args_os(&mut self, args: &mut dyn std::iter::Iterator<Item = std::ffi::OsString>, policy: OsArgPolicy) -> Result<bool> {
+        let mut ret = vec![];
+
+        for arg in args {
+            match arg.into_string() {
+                Ok(arg) => ret.push(arg),
+                Err(arg) => {
+                    if policy == OsArgPolicy::Strict {
+                        return Err(Error::InvalidCommandLine(arg.to_string_lossy().into_owned()));
+                    }
+                    ret.push(arg.to_string_lossy().into_owned());
+                }
+            }
+        }
+        self.set_args(&mut ret.into_iter());
+        Ok(true)
+    }
+
+    /// Return the program name set by [`set_args_with_prog`](ArgIterator::set_args_with_prog).
+    pub fn prog_name(&self) -> Option<&str> {
+        self.prog_name.as_deref()
+    }
+
+    /// Create an [`ArgIterator`] from a single command line string, see [`split_line`].
+    pub fn from_line(line: &str) -> Result<Self> {
+        let mut ret = Self::new();
+
+        ret.set_line(line)?;
+        Ok(ret)
+    }
+
+    /// Split the command line string and set it as arguments, see [`split_line`].
+    pub fn set_line(&mut self, line: &str) -> Result<bool> {
+        let args = split_line(line)?;
+
+        self.set_args(&mut args.into_iter());
+        Ok(true)
+    }
+
+    /// Create an [`ArgIterator`] from a response file, see [`read_response_file`].
+    pub fn from_file<P: AsRef<std::path::Path>>(path: P) -> Result<Self> {
+        let mut ret = Self::new();
+
+        ret.set_file(path)?;
+        Ok(ret)
+    }
+
+    /// Read the response file and set it as arguments, see [`read_response_file`].
+    pub fn set_file<P: AsRef<std::path::Path>>(&mut self, path: P) -> Result<bool> {
+        let args = read_response_file(path)?;
+
+        self.set_args(&mut args.into_iter());
+        Ok(true)
+    }
+
+    /// Replace the `@file` arguments with the content of file, see [`expand_response_files`].
+    /// The arguments not changed if any error occurred.
+    pub fn expand_response_files(&mut self) -> Result<bool> {
+        let args = expand_response_files(self.args.clone())?;
+
+        self.set_args(&mut args.into_iter());
+        Ok(true)
+    }
 }
 
 #[async_trait]
@@ -189,6 +327,10 @@ impl IndexIterator for ArgIterator {
         &self.next_arg
     }
 
+    fn peek_nth(&self, n: usize) -> Option<&String> {
+        self.args.get(self.index.checked_add(n)?)
+    }
+
     fn skip(&mut self) {
         self.index += 1;
     }
@@ -208,23 +350,197 @@ impl IndexIterator for ArgIterator {
         self.arg = None;
         self.next_arg = None;
     }
+
+    fn checkpoint(&self) -> usize {
+        self.index
+    }
+
+    fn restore(&mut self, cp: usize) {
+        self.index = cp.min(self.total);
+        if self.reach_end() {
+            self.arg = None;
+            self.next_arg = None;
+        }
+        else {
+            self.fill_current_and_next();
+        }
+    }
+}
+
+impl std::iter::FromIterator<String> for ArgIterator {
+    fn from_iter<I: IntoIterator<Item = String>>(iter: I) -> Self {
+        Self::from_args(&mut iter.into_iter())
+    }
+}
+
+/// Split the command line string to arguments like the shell word splitting.
+/// 
+/// * The whitespace outside the quotes separate the arguments.
+/// * The characters inside single quotes are keep literally.
+/// * Inside double quotes, the backslash only escape `"`, `\`, `$` and `` ` ``.
+/// * Outside the quotes, the backslash escape any character.
+/// * The `""` or `''` will generate an empty argument.
+/// 
+/// Return Err if the quote not terminated or the line end with a backslash.
+pub fn split_line(line: &str) -> Result<Vec<String>> {
+    let mut ret = vec![];
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut chars = line.chars();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => { break; }
+                        Some(ch) => { current.push(ch); }
+                        None => {
+                            return Err(Error::InvalidCommandLine(format!("unterminated single quote in {}", line)));
+                        }
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => { break; }
+                        Some('\\') => {
+                            match chars.next() {
+                                Some(ch) if ['"', '\\', '$', '`'].contains(&ch) => {
+                                    current.push(ch);
+                                }
+                                Some(ch) => {
+                                    current.push('\\');
+                                    current.push(ch);
+                                }
+                                None => {
+                                    return Err(Error::InvalidCommandLine(format!("unterminated double quote in {}", line)));
+                                }
+                            }
+                        }
+                        Some(ch) => { current.push(ch); }
+                        None => {
+                            return Err(Error::InvalidCommandLine(format!("unterminated double quote in {}", line)));
+                        }
+                    }
+                }
+            }
+            '\\' => {
+                in_word = true;
+                match chars.next() {
+                    Some(ch) => { current.push(ch); }
+                    None => {
+                        return Err(Error::InvalidCommandLine(format!("unterminated escape in {}", line)));
+                    }
+                }
+            }
+            ch if ch.is_whitespace() => {
+                if in_word {
+                    ret.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            ch => {
+                in_word = true;
+                current.push(ch);
+            }
+        }
+    }
+    if in_word {
+        ret.push(current);
+    }
+    Ok(ret)
+}
+
+/// Read the response file and split the content to arguments using [`split_line`],
+/// the line break is treated as whitespace, and quoted value keep the whitespace in it.
+pub fn read_response_file<P: AsRef<std::path::Path>>(path: P) -> Result<Vec<String>> {
+    let content = std::fs::read_to_string(path)?;
+
+    split_line(&content)
+}
+
+/// The prefix of response file argument, such as `@args.txt`.
+pub const RESPONSE_FILE_PREFIX: &str = "@";
+
+/// The maximum depth of nested response file.
+pub const RESPONSE_FILE_MAX_DEPTH: usize = 16;
+
+/// Replace the argument start with [`RESPONSE_FILE_PREFIX`] with the arguments read from file,
+/// the nested `@file` will be expanded recursively.
+/// Return [`Error::ResponseFileTooDeep`] if the depth exceed [`RESPONSE_FILE_MAX_DEPTH`].
+pub fn expand_response_files(args: Vec<String>) -> Result<Vec<String>> {
+    expand_response_files_impl(args, 0)
+}
+
+fn expand_response_files_impl(args: Vec<String>, depth: usize) -> Result<Vec<String>> {
+    let mut ret = vec![];
+
+    for arg in args {
+        match arg.strip_prefix(RESPONSE_FILE_PREFIX) {
+            Some(path) if ! path.is_empty() => {
+                if depth >= RESPONSE_FILE_MAX_DEPTH {
+                    return Err(Error::ResponseFileTooDeep(path.to_owned()));
+                }
+                ret.extend(expand_response_files_impl(read_response_file(path)?, depth + 1)?);
+            }
+            _ => {
+                ret.push(arg);
+            }
+        }
+    }
+    Ok(ret)
+}
+
+/// The standalone argument terminate the option processing,
+/// all the arguments after it are non-option arguments.
+pub const TERMINATOR: &str = "--";
+
+/// Return true if the argument is exactly the [`TERMINATOR`], `--foo` is not a terminator.
+pub fn is_terminator(s: &Option<String>) -> bool {
+    s.as_deref() == Some(TERMINATOR)
+}
+
+/// Skip current argument and return all the arguments after it.
+pub fn take_left_arguments(iter: &mut dyn IndexIterator) -> Vec<String> {
+    let mut args = vec![];
+
+    iter.skip();
+    while ! iter.reach_end() {
+        iter.fill_current_and_next();
+        if let Some(arg) = iter.current() {
+            args.push(arg.clone());
+        }
+        iter.skip();
+    }
+    args
+}
+
+/// Return true if the argument has a valid option name.
+/// The name contains `/` is not an option name, so `/etc/passwd` is a non-option argument
+/// even if `/` is one of the prefixs.
+pub fn is_option_name(arg: &Argument) -> bool {
+    arg.get_name().is_some_and(|name| ! name.contains('/'))
 }
 
 /// Parsing the string to [`Argument`].
-/// The given `prefixs` need be sorted by length in descending order.
+/// The longest prefix in `prefixs` matched will be used.
 #[cfg(feature="async")]
 pub async fn parse_argument(s: &Option<String>, prefixs: &Vec<String>) -> Result<Argument> {
     parse_argument_impl(s, prefixs)
 }
 
 /// Parsing the string to [`Argument`].
-/// The given `prefixs` need be sorted by length in descending order.
+/// The longest prefix in `prefixs` matched will be used.
 #[cfg(not(feature="async"))]
 pub fn parse_argument(s: &Option<String>, prefixs: &Vec<String>) -> Result<Argument> {
     parse_argument_impl(s, prefixs)
 }
 
-fn parse_argument_impl(s: &Option<String>, prefixs: &Vec<String>) -> Result<Argument> {
+pub(crate) fn parse_argument_impl(s: &Option<String>, prefixs: &[String]) -> Result<Argument> {
     match s {
         Some(s) => {
             const SPLIT: &'static str = "=";
@@ -233,25 +549,31 @@ fn parse_argument_impl(s: &Option<String>, prefixs: &Vec<String>) -> Result<Argu
             let p_name: Option<String>;
             let mut p_value: Option<String> = None;
 
-            for prefix in prefixs.iter() {
-                if s.starts_with(prefix) {
-                    let (_, left_str) = s.split_at(prefix.len());
+            let mut matched_prefix: Option<&String> = None;
 
-                    if left_str.len() > 0 {
-                        p_prefix = Some(prefix.to_owned());
-                        let name_or_value: Vec<_> = left_str.splitn(2, SPLIT).collect();
+            // prefer the longest prefix, such as `--` rather than `-`
+            for prefix in prefixs.iter() {
+                if s.starts_with(prefix) && s.len() > prefix.len() {
+                    if matched_prefix.is_none_or(|v| v.len() < prefix.len()) {
+                        matched_prefix = Some(prefix);
+                    }
+                }
+            }
+            if let Some(prefix) = matched_prefix {
+                let (_, left_str) = s.split_at(prefix.len());
 
-                        if name_or_value.len() > 1 {
-                            p_name = Some(name_or_value[0].to_owned());
-                            p_value = Some(name_or_value[1].to_owned());
-                        }
-                        else {
-                            p_name = Some(left_str.to_owned());
-                        }
+                p_prefix = Some(prefix.to_owned());
+                let name_or_value: Vec<_> = left_str.splitn(2, SPLIT).collect();
 
-                        return Ok(Argument::new(p_prefix, p_name, p_value));
-                    }
+                if name_or_value.len() > 1 {
+                    p_name = Some(name_or_value[0].to_owned());
+                    p_value = Some(name_or_value[1].to_owned());
                 }
+                else {
+                    p_name = Some(left_str.to_owned());
+                }
+
+                return Ok(Argument::new(p_prefix, p_name, p_value));
             }
             Err(Error::InvalidOptionStr(s.clone()))
         }
@@ -370,4 +692,215 @@ mod tests {
             }
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn make_sure_terminator_work() {
+        assert!(is_terminator(&Some(String::from("--"))));
+        assert!(! is_terminator(&Some(String::from("--foo"))));
+        assert!(! is_terminator(&Some(String::from("--="))));
+        assert!(! is_terminator(&Some(String::from("-"))));
+        assert!(! is_terminator(&None));
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_option_name_work() {
+        let prefixs: Vec<String> = ["-", "/", "--"].iter().map(|&v|String::from(v)).collect();
+
+        assert!(is_option_name(&parse_argument(&Some(String::from("--debug")), &prefixs).unwrap()));
+        assert!(is_option_name(&parse_argument(&Some(String::from("/?")), &prefixs).unwrap()));
+        assert!(! is_option_name(&parse_argument(&Some(String::from("/etc/passwd")), &prefixs).unwrap()));
+        assert!(is_option_name(&parse_argument(&Some(String::from("--path=a/b")), &prefixs).unwrap()));
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_longest_prefix_matched() {
+        let prefixs: Vec<String> = ["-", "/", "--"].iter().map(|&v|String::from(v)).collect();
+
+        let arg = parse_argument(&Some(String::from("--debug")), &prefixs).unwrap();
+
+        assert_eq!(arg.get_prefix(), Some(&String::from("--")));
+        assert_eq!(arg.get_name(), Some(&String::from("debug")));
+
+        let arg = parse_argument(&Some(String::from("-d")), &prefixs).unwrap();
+
+        assert_eq!(arg.get_prefix(), Some(&String::from("-")));
+        assert_eq!(arg.get_name(), Some(&String::from("d")));
+
+        let arg = parse_argument(&Some(String::from("/d=1")), &prefixs).unwrap();
+
+        assert_eq!(arg.get_prefix(), Some(&String::from("/")));
+        assert_eq!(arg.get_value(), Some(&String::from("1")));
+    }
+
+    #[test]
+    fn make_sure_split_line_work() {
+        let test_cases = [
+            ("set -f --name \"a b c\"", Some(vec!["set", "-f", "--name", "a b c"])),
+            ("  set   -f  ", Some(vec!["set", "-f"])),
+            ("--name 'a \"b\" c'", Some(vec!["--name", "a \"b\" c"])),
+            ("--name \"a \\\"b\\\" c\"", Some(vec!["--name", "a \"b\" c"])),
+            ("--name a\\ b", Some(vec!["--name", "a b"])),
+            ("--name=\"a b\"c", Some(vec!["--name=a bc"])),
+            ("--name \"\" ''", Some(vec!["--name", "", ""])),
+            ("", Some(vec![])),
+            ("--name \"a b", None),
+            ("--name 'a b", None),
+            ("--name a\\", None),
+        ];
+
+        for (line, args) in test_cases.iter() {
+            match split_line(line) {
+                Ok(ret) => {
+                    assert_eq!(&ret, args.as_ref().unwrap());
+                }
+                Err(_) => {
+                    assert!(args.is_none());
+                }
+            }
+        }
+
+        let mut ai = ArgIterator::from_line("set -f --name \"a b c\"").unwrap();
+
+        assert_eq!(ai.count(), 4);
+        ai.skip();
+        ai.skip();
+        ai.skip();
+        ai.fill_current_and_next();
+        assert_eq!(ai.current(), &Some(String::from("a b c")));
+        assert!(ArgIterator::from_line("--name \"a b c").is_err());
+    }
+
+    #[test]
+    fn make_sure_expand_response_files_work() {
+        let dir = std::env::temp_dir();
+        let outer = dir.join(format!("getopt-rs-expand-outer-{}.txt", std::process::id()));
+        let inner = dir.join(format!("getopt-rs-expand-inner-{}.txt", std::process::id()));
+        let cycle = dir.join(format!("getopt-rs-expand-cycle-{}.txt", std::process::id()));
+
+        std::fs::write(&inner, "-c\nc").unwrap();
+        std::fs::write(&outer, format!("-d\n@{}\n", inner.display())).unwrap();
+        std::fs::write(&cycle, format!("-d @{}", cycle.display())).unwrap();
+
+        let mut ai = ArgIterator::new();
+
+        ai.set_args(&mut ["foo", &format!("@{}", outer.display()), "@", "bar"].iter().map(|&v|String::from(v)));
+        assert!(ai.expand_response_files().unwrap());
+        assert_eq!(ai.count(), 6);
+        assert_eq!(ai.args, vec!["foo", "-d", "-c", "c", "@", "bar"]);
+
+        let ret = expand_response_files(vec![format!("@{}", cycle.display())]);
+
+        assert!(matches!(ret, Err(Error::ResponseFileTooDeep(_))));
+
+        std::fs::remove_file(&inner).unwrap();
+        assert!(expand_response_files(vec![format!("@{}", outer.display())]).is_err());
+
+        let args = vec![String::from("foo"), format!("@{}", outer.display())];
+
+        ai.set_args(&mut args.clone().into_iter());
+        assert!(ai.expand_response_files().is_err());
+        assert_eq!(ai.count(), 2);
+        assert_eq!(ai.args, args);
+
+        std::fs::remove_file(&outer).unwrap();
+        std::fs::remove_file(&cycle).unwrap();
+    }
+
+    #[test]
+    fn make_sure_prog_name_work() {
+        let mut ai = ArgIterator::new();
+
+        assert_eq!(ai.prog_name(), None);
+        ai.set_args_with_prog(&mut ["/usr/bin/app", "-c", "foo"].iter().map(|&v|String::from(v)));
+        assert_eq!(ai.prog_name(), Some("/usr/bin/app"));
+        assert_eq!(ai.count(), 2);
+        ai.fill_current_and_next();
+        assert_eq!(ai.current(), &Some(String::from("-c")));
+        assert_eq!(ai.next(), &Some(String::from("foo")));
+
+        ai.set_args_with_prog(&mut std::iter::empty());
+        assert_eq!(ai.prog_name(), None);
+        assert_eq!(ai.count(), 0);
+    }
+
+    #[test]
+    fn make_sure_peek_nth_work() {
+        let mut ai = ArgIterator::new();
+
+        ai.set_args(&mut ["-a", "b", "c", "-d", "e"].iter().map(|&v|String::from(v)));
+        assert_eq!(ai.peek_nth(0), Some(&String::from("-a")));
+        assert_eq!(ai.peek_nth(2), Some(&String::from("c")));
+        assert_eq!(ai.peek_nth(4), Some(&String::from("e")));
+        assert_eq!(ai.peek_nth(5), None);
+        assert_eq!(ai.peek_nth(usize::MAX), None);
+
+        ai.skip();
+        ai.skip();
+        ai.fill_current_and_next();
+        assert_eq!(ai.peek_nth(0), ai.current().as_ref());
+        assert_eq!(ai.peek_nth(1), ai.next().as_ref());
+        assert_eq!(ai.peek_nth(2), Some(&String::from("e")));
+        assert_eq!(ai.peek_nth(3), None);
+        assert_eq!(ai.current_index(), 2);
+    }
+
+    #[test]
+    fn make_sure_checkpoint_work() {
+        let mut ai = ArgIterator::new();
+
+        ai.set_args(&mut ["-a", "b", "c", "-d", "e"].iter().map(|&v|String::from(v)));
+        ai.skip();
+        ai.fill_current_and_next();
+
+        let cp = ai.checkpoint();
+
+        assert_eq!(cp, 1);
+        ai.skip();
+        ai.skip();
+        ai.fill_current_and_next();
+        assert_eq!(ai.current(), &Some(String::from("-d")));
+
+        ai.restore(cp);
+        assert_eq!(ai.current_index(), 1);
+        assert_eq!(ai.current(), &Some(String::from("b")));
+        assert_eq!(ai.next(), &Some(String::from("c")));
+
+        // restore to the last argument and the end
+        ai.restore(4);
+        assert_eq!(ai.current(), &Some(String::from("e")));
+        assert_eq!(ai.next(), &None);
+        ai.restore(5);
+        assert!(ai.reach_end());
+        assert_eq!(ai.current(), &None);
+        ai.restore(0);
+        assert_eq!(ai.current(), &Some(String::from("-a")));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn make_sure_set_args_os_work() {
+        use std::ffi::OsString;
+        use std::os::unix::ffi::OsStringExt;
+
+        let args = || vec![OsString::from("-p"), OsString::from_vec(vec![b'f', b'o', 0x80, b'o'])];
+        let mut ai = ArgIterator::new();
+
+        assert!(ai.set_args_os(&mut args().into_iter(), OsArgPolicy::Lossy).unwrap());
+        assert_eq!(ai.count(), 2);
+        ai.skip();
+        ai.fill_current_and_next();
+        assert_eq!(ai.current(), &Some(String::from("fo\u{FFFD}o")));
+
+        let mut ai = ArgIterator::new();
+
+        assert!(matches!(
+            ai.set_args_os(&mut args().into_iter(), OsArgPolicy::Strict),
+            Err(Error::InvalidCommandLine(arg)) if arg == "fo\u{FFFD}o"
+        ));
+        assert_eq!(ai.count(), 0);
+        assert!(ai.set_args_os(&mut vec![OsString::from("-p"), OsString::from("foo")].into_iter(), OsArgPolicy::Strict).unwrap());
+        assert_eq!(ai.count(), 2);
+    }
+}
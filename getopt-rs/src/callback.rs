@@ -1,7 +1,7 @@
 use std::fmt::Debug;
 use async_trait::async_trait;
 
-use crate::opt::Opt;
+use crate::opt::{Opt, OptValue};
 use crate::error::Result;
 use crate::set::Set;
 
@@ -15,6 +15,31 @@ pub trait ValueCallback: Debug {
     async fn call(&mut self, opt: &dyn Opt) -> Result<bool>;
 }
 
+/// Same as [`ValueCallback`], but also receive the raw argument before parsed, such as `007` of `--count=007`.
+#[async_trait(?Send)]
+pub trait RawValueCallback: Debug {
+    #[cfg(not(feature="async"))]
+    fn call(&mut self, opt: &dyn Opt, raw: &str) -> Result<bool>;
+
+    #[cfg(feature="async")]
+    async fn call(&mut self, opt: &dyn Opt, raw: &str) -> Result<bool>;
+}
+
+/// Callback can modify the option after the value set, such as normalize the value.
+/// It is used by the `option` type support [`CallbackType::Value`].
+///
+/// Unlike [`ValueTransform`], which is called on the parsed value of each occurrence before it stored,
+/// the callback is called after the value stored, so it sees the accumulated value of option such as
+/// [`ArrayOpt`](crate::opt::array::ArrayOpt). Use [`ValueTransform`] if only the value needs to be changed.
+#[async_trait(?Send)]
+pub trait TransformCallback: Debug {
+    #[cfg(not(feature="async"))]
+    fn call(&mut self, opt: &mut dyn Opt) -> Result<bool>;
+
+    #[cfg(feature="async")]
+    async fn call(&mut self, opt: &mut dyn Opt) -> Result<bool>;
+}
+
 /// Callback will be used by `non-option` type [`Pos`](crate::nonopt::pos::Pos)
 #[async_trait(?Send)]
 pub trait IndexCallback: Debug {
@@ -35,11 +60,24 @@ pub trait MainCallback: Debug {
     async fn call(&mut self, set: &dyn Set, args: &Vec<String>) -> Result<bool>;
 }
 
+/// Same as [`MainCallback`], but can modify the option set, such as derive the value of option from others.
+#[async_trait(?Send)]
+pub trait MainMutCallback: Debug {
+    #[cfg(not(feature="async"))]
+    fn call(&mut self, set: &mut dyn Set, args: &[String]) -> Result<bool>;
+
+    #[cfg(feature="async")]
+    async fn call(&mut self, set: &mut dyn Set, args: &[String]) -> Result<bool>;
+}
+
 #[derive(Debug)]
 pub enum OptCallback {
     Value(Box<dyn ValueCallback>),
     Index(Box<dyn IndexCallback>),
     Main(Box<dyn MainCallback>),
+    MainMut(Box<dyn MainMutCallback>),
+    Transform(Box<dyn TransformCallback>),
+    RawValue(Box<dyn RawValueCallback>),
     Null
 }
 
@@ -56,17 +94,37 @@ impl OptCallback {
         Self::Main(cb)
     }
 
+    pub fn from_main_mut(cb: Box<dyn MainMutCallback>) -> Self {
+        Self::MainMut(cb)
+    }
+
+    pub fn from_transform(cb: Box<dyn TransformCallback>) -> Self {
+        Self::Transform(cb)
+    }
+
+    pub fn from_raw_value(cb: Box<dyn RawValueCallback>) -> Self {
+        Self::RawValue(cb)
+    }
+
+    /// Return true if the callback is [`OptCallback::Null`], it will do nothing.
+    pub fn is_null(&self) -> bool {
+        matches!(self, OptCallback::Null)
+    }
+
     pub fn to_callback_type(&self) -> CallbackType {
         match self {
-            OptCallback::Value(_) => {
+            OptCallback::Value(_) | OptCallback::RawValue(_) => {
                 CallbackType::Value
             }
             OptCallback::Index(_) => {
                 CallbackType::Index
             }
-            OptCallback::Main(_) => {
+            OptCallback::Main(_) | OptCallback::MainMut(_) => {
                 CallbackType::Main
             }
+            OptCallback::Transform(_) => {
+                CallbackType::Transform
+            }
             OptCallback::Null => {
                 CallbackType::Null
             }
@@ -85,6 +143,41 @@ impl OptCallback {
         }
     }
 
+    /// Call the [`OptCallback::Transform`] with mutable `opt`, or the [`OptCallback::Value`].
+    #[cfg(not(feature="async"))]
+    pub fn call_transform(&mut self, opt: &mut dyn Opt) -> Result<bool> {
+        match self {
+            OptCallback::Transform(cb) => {
+                cb.as_mut().call(opt)
+            }
+            OptCallback::Value(cb) => {
+                cb.as_mut().call(opt)
+            }
+            _ => {
+                Ok(false)
+            }
+        }
+    }
+
+    /// Call the callback of `option` type, the `raw` argument is only passed to the [`OptCallback::RawValue`].
+    #[cfg(not(feature="async"))]
+    pub fn call_raw_value(&mut self, opt: &mut dyn Opt, raw: &str) -> Result<bool> {
+        match self {
+            OptCallback::Value(cb) => {
+                cb.as_mut().call(opt)
+            }
+            OptCallback::Transform(cb) => {
+                cb.as_mut().call(opt)
+            }
+            OptCallback::RawValue(cb) => {
+                cb.as_mut().call(opt, raw)
+            }
+            _ => {
+                Ok(false)
+            }
+        }
+    }
+
     #[cfg(not(feature="async"))]
     pub fn call_index(&mut self, set: &dyn Set, arg: &String) -> Result<bool> {
         match self {
@@ -109,6 +202,19 @@ impl OptCallback {
         }
     }
 
+    /// Call the [`OptCallback::MainMut`] with mutable `set`, or the [`OptCallback::Main`].
+    #[cfg(not(feature="async"))]
+    pub fn call_main_mut(&mut self, set: &mut dyn Set, args: &Vec<String>) -> Result<bool> {
+        match self {
+            OptCallback::MainMut(cb) => {
+                cb.as_mut().call(set, args)
+            }
+            _ => {
+                self.call_main(set, args)
+            }
+        }
+    }
+
     #[cfg(feature="async")]
     pub async fn call_value(&mut self, opt: &dyn Opt) -> Result<bool> {
         match self {
@@ -121,6 +227,41 @@ impl OptCallback {
         }
     }
 
+    /// Call the [`OptCallback::Transform`] with mutable `opt`, or the [`OptCallback::Value`].
+    #[cfg(feature="async")]
+    pub async fn call_transform(&mut self, opt: &mut dyn Opt) -> Result<bool> {
+        match self {
+            OptCallback::Transform(cb) => {
+                cb.as_mut().call(opt).await
+            }
+            OptCallback::Value(cb) => {
+                cb.as_mut().call(opt).await
+            }
+            _ => {
+                Ok(false)
+            }
+        }
+    }
+
+    /// Call the callback of `option` type, the `raw` argument is only passed to the [`OptCallback::RawValue`].
+    #[cfg(feature="async")]
+    pub async fn call_raw_value(&mut self, opt: &mut dyn Opt, raw: &str) -> Result<bool> {
+        match self {
+            OptCallback::Value(cb) => {
+                cb.as_mut().call(opt).await
+            }
+            OptCallback::Transform(cb) => {
+                cb.as_mut().call(opt).await
+            }
+            OptCallback::RawValue(cb) => {
+                cb.as_mut().call(opt, raw).await
+            }
+            _ => {
+                Ok(false)
+            }
+        }
+    }
+
     #[cfg(feature="async")]
     pub async fn call_index(&mut self, set: &dyn Set, arg: &String) -> Result<bool> {
         match self {
@@ -144,6 +285,19 @@ impl OptCallback {
             }
         }
     }
+
+    /// Call the [`OptCallback::MainMut`] with mutable `set`, or the [`OptCallback::Main`].
+    #[cfg(feature="async")]
+    pub async fn call_main_mut(&mut self, set: &mut dyn Set, args: &Vec<String>) -> Result<bool> {
+        match self {
+            OptCallback::MainMut(cb) => {
+                cb.as_mut().call(set, args).await
+            }
+            _ => {
+                self.call_main(set, args).await
+            }
+        }
+    }
 }
 
 /// [`CallbackType`] is using for [`Opt`] identify which type [`OptCallback`] need 
@@ -159,6 +313,9 @@ pub enum CallbackType {
     /// Identify the callback type [`OptCallback::Main`]
     Main,
 
+    /// Identify the callback type [`OptCallback::Transform`], it is accepted by the option support [`CallbackType::Value`]
+    Transform,
+
     Null,
 }
 
@@ -185,6 +342,13 @@ impl CallbackType {
         }
     }
 
+    pub fn is_transform(&self) -> bool {
+        match self {
+            Self::Transform => true,
+            _ => false,
+        }
+    }
+
     pub fn is_null(&self) -> bool {
         match self {
             Self::Null=> true,
@@ -199,6 +363,52 @@ impl Default for CallbackType {
     }
 }
 
+/// ValueTransform will transform the value of option before it stored.
+/// It is called before the [`OptCallback`] of option, see [`TransformCallback`].
+pub struct ValueTransform(Box<dyn FnMut(OptValue) -> Result<OptValue>>);
+
+impl ValueTransform {
+    pub fn new<F: 'static + FnMut(OptValue) -> Result<OptValue>>(transform: F) -> Self {
+        Self(Box::new(transform))
+    }
+
+    pub fn transform(&mut self, value: OptValue) -> Result<OptValue> {
+        self.0(value)
+    }
+}
+
+impl Debug for ValueTransform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ValueTransform")
+         .field("FnMut", &String::from("..."))
+         .finish()
+    }
+}
+
+/// UnknownHandler will be called with the argument look like an option but not matched any option.
+/// Return `Ok(true)` will suppress the argument, `Ok(false)` will treat it as non-option argument.
+pub struct UnknownHandler(Box<UnknownHandlerFn>);
+
+/// The function handle the argument look like an option but not matched any option, see [`UnknownHandler`].
+pub type UnknownHandlerFn = dyn FnMut(&str) -> Result<bool>;
+
+impl UnknownHandler {
+    pub fn new(handler: Box<UnknownHandlerFn>) -> Self {
+        Self(handler)
+    }
+
+    pub fn handle(&mut self, arg: &str) -> Result<bool> {
+        self.0(arg)
+    }
+}
+
+impl Debug for UnknownHandler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UnknownHandler")
+         .field("FnMut", &String::from("..."))
+         .finish()
+    }
+}
 
 /// Simple callback implementation for [`ValueCallback`]
 #[cfg(not(feature="async"))]
@@ -227,6 +437,60 @@ impl<T: FnMut(&dyn Opt) -> Result<bool>> ValueCallback for SimpleValueCallback<T
     }
 }
 
+/// Simple callback implementation for [`RawValueCallback`]
+#[cfg(not(feature="async"))]
+pub struct SimpleRawValueCallback<T: FnMut(&dyn Opt, &str) -> Result<bool>>(T);
+
+#[cfg(not(feature="async"))]
+impl<T: FnMut(&dyn Opt, &str) -> Result<bool>> SimpleRawValueCallback<T> {
+    pub fn new(cb: T) -> Self {
+        Self(cb)
+    }
+}
+
+#[cfg(not(feature="async"))]
+impl<T: FnMut(&dyn Opt, &str) -> Result<bool>> Debug for SimpleRawValueCallback<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SimpleRawValueCallback")
+         .field("FnMut", &String::from("..."))
+         .finish()
+    }
+}
+
+#[cfg(not(feature="async"))]
+impl<T: FnMut(&dyn Opt, &str) -> Result<bool>> RawValueCallback for SimpleRawValueCallback<T> {
+    fn call(&mut self, opt: &dyn Opt, raw: &str) -> Result<bool> {
+        self.0(opt, raw)
+    }
+}
+
+/// Simple callback implementation for [`TransformCallback`]
+#[cfg(not(feature="async"))]
+pub struct SimpleTransformCallback<T: FnMut(&mut dyn Opt) -> Result<bool>>(T);
+
+#[cfg(not(feature="async"))]
+impl<T: FnMut(&mut dyn Opt) -> Result<bool>> SimpleTransformCallback<T> {
+    pub fn new(cb: T) -> Self {
+        Self(cb)
+    }
+}
+
+#[cfg(not(feature="async"))]
+impl<T: FnMut(&mut dyn Opt) -> Result<bool>> Debug for SimpleTransformCallback<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SimpleTransformCallback")
+         .field("FnMut", &String::from("..."))
+         .finish()
+    }
+}
+
+#[cfg(not(feature="async"))]
+impl<T: FnMut(&mut dyn Opt) -> Result<bool>> TransformCallback for SimpleTransformCallback<T> {
+    fn call(&mut self, opt: &mut dyn Opt) -> Result<bool> {
+        self.0(opt)
+    }
+}
+
 /// Simple callback implementation for [`IndexCallback`]
 #[cfg(not(feature="async"))]
 pub struct SimpleIndexCallback<F: FnMut( &dyn Set, &String ) -> Result<bool>>(F);
@@ -279,4 +543,31 @@ impl<F: FnMut( &dyn Set, &Vec<String> ) -> Result<bool> > MainCallback for Simpl
     fn call(&mut self, set: &dyn Set, args: &Vec<String>) -> Result<bool> {
         self.0(set, args)
     }
+}
+
+/// Simple callback implementation for [`MainMutCallback`]
+#[cfg(not(feature="async"))]
+pub struct SimpleMainMutCallback<F: FnMut( &mut dyn Set, &[String] ) -> Result<bool> >(F);
+
+#[cfg(not(feature="async"))]
+impl<F: FnMut( &mut dyn Set, &[String] ) -> Result<bool> > SimpleMainMutCallback<F> {
+    pub fn new(cb: F) -> Self {
+        Self(cb)
+    }
+}
+
+#[cfg(not(feature="async"))]
+impl<F: FnMut( &mut dyn Set, &[String] ) -> Result<bool> > Debug for SimpleMainMutCallback<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SimpleMainMutCallback")
+         .field("FnMut", &String::from("..."))
+         .finish()
+    }
+}
+
+#[cfg(not(feature="async"))]
+impl<F: FnMut( &mut dyn Set, &[String] ) -> Result<bool> > MainMutCallback for SimpleMainMutCallback<F> {
+    fn call(&mut self, set: &mut dyn Set, args: &[String]) -> Result<bool> {
+        self.0(set, args)
+    }
 }
\ No newline at end of file
@@ -1,7 +1,7 @@
 
 use std::fmt::Debug;
 
-use crate::opt::{Opt, Style};
+use crate::opt::{Opt, OptValue, Style};
 use crate::error::{Result, Error};
 use crate::id::Identifier;
 
@@ -15,6 +15,12 @@ pub trait Context: Debug {
     /// Process the option if matched successful
     fn process(&mut self, opt: &mut dyn Opt) -> Result<bool>;
 
+    /// Same as [`process`](Context::process), but the parsed value will be passed to `transform` before it stored.
+    /// In default it will ignore the `transform`, the context which set the option value need override it.
+    fn process_with(&mut self, opt: &mut dyn Opt, _transform: &mut dyn FnMut(OptValue) -> Result<OptValue>) -> Result<bool> {
+        self.process(opt)
+    }
+
     /// Return matched index if the context already matched successful
     fn get_matched_index(&self) -> Option<u64>;
 
@@ -29,6 +35,12 @@ pub trait Context: Debug {
 
     /// Return next argument
     fn get_next_argument(&self) -> &Option<String>;
+
+    /// Return the prefix and name of option can matched by the context,
+    /// or None if the context match option by other condition, such as index.
+    fn get_prefix_and_name(&self) -> Option<(&str, &str)> {
+        None
+    }
 }
 
 /// Context implementation for option. 
@@ -113,6 +125,10 @@ impl Context for OptContext {
     }
 
     fn process(&mut self, opt: &mut dyn Opt) -> Result<bool> {
+        self.process_with(opt, &mut |value| Ok(value))
+    }
+
+    fn process_with(&mut self, opt: &mut dyn Opt, transform: &mut dyn FnMut(OptValue) -> Result<OptValue>) -> Result<bool> {
         let mut value = &String::default();
         
         self.matched_index = Some(0);
@@ -124,7 +140,7 @@ impl Context for OptContext {
             value = v;
         }
         // we always call `set_value` since all option type need update value through this function
-        opt.set_value(opt.parse_value(value.as_str())?);
+        opt.set_value(transform(opt.parse_value(value.as_str())?)?);
         opt.set_need_invoke(true);
         Ok(true)
     }
@@ -148,6 +164,10 @@ impl Context for OptContext {
     fn get_next_argument(&self) -> &Option<String> {
         &self.next_argument
     }
+
+    fn get_prefix_and_name(&self) -> Option<(&str, &str)> {
+        Some((&self.opt_prefix, &self.opt_name))
+    }
 }
 
 /// Context implementation for non-option. 
@@ -223,11 +243,15 @@ impl Context for NonOptContext {
     }
 
     fn process(&mut self, opt: &mut dyn Opt) -> Result<bool> { 
+        self.process_with(opt, &mut |value| Ok(value))
+    }
+
+    fn process_with(&mut self, opt: &mut dyn Opt, transform: &mut dyn FnMut(OptValue) -> Result<OptValue>) -> Result<bool> {
         self.matched_index = Some(self.current);
         debug!("Match successed => {:?} : Opt<{:?}>", self, opt.id());
         // opt_name will be ignored, 
         // try to set value even if the value will be set in another side
-        opt.set_value(opt.parse_value(&self.opt_name)?);
+        opt.set_value(transform(opt.parse_value(&self.opt_name)?)?);
         opt.set_need_invoke(true);
         Ok(true)
     }
@@ -365,4 +389,76 @@ impl Context for DelayContext {
     fn get_next_argument(&self) -> &Option<String> {
         &self.next_argument
     }
-}
\ No newline at end of file
+
+    fn get_prefix_and_name(&self) -> Option<(&str, &str)> {
+        Some((&self.opt_prefix, &self.opt_name))
+    }
+}
+
+/// Context implementation for option type.
+/// 
+/// It will check the type name of option, such as `array`, regardless of the name.
+/// It will not set option value if matched.
+#[derive(Debug)]
+pub struct TypeContext {
+    id: Identifier,
+
+    type_name: String,
+
+    matched_index: Option<u64>,
+}
+
+impl TypeContext {
+    pub fn new(type_name: &str) -> Self {
+        Self {
+            id: Identifier::new(0),
+            type_name: type_name.to_owned(),
+            matched_index: None,
+        }
+    }
+
+    pub fn set_type_name(&mut self, type_name: &str) -> &mut Self {
+        self.type_name = type_name.to_owned();
+        self
+    }
+}
+
+impl Context for TypeContext {
+    fn id(&self) -> &Identifier {
+        &self.id
+    }
+
+    fn match_opt(&self, opt: &dyn Opt) -> bool {
+        let matched = opt.type_name() == self.type_name;
+
+        debug!("Match Type<{:?}> {:?} => ", opt.id(), opt);
+        debug!(">>## {}", if matched { "TRUE" } else { "FALSE" });
+        matched
+    }
+
+    fn process(&mut self, opt: &mut dyn Opt) -> Result<bool> {
+        self.matched_index = Some(0);
+        debug!("Match successed => {:?} : Opt<{:?}>", self, opt.id());
+        Ok(true)
+    }
+
+    fn get_matched_index(&self) -> Option<u64> {
+        self.matched_index
+    }
+
+    fn is_need_argument(&self) -> bool {
+        false
+    }
+
+    fn is_matched(&self) -> bool {
+        self.matched_index.is_some()
+    }
+
+    fn get_style(&self) -> Style {
+        Style::Null
+    }
+
+    fn get_next_argument(&self) -> &Option<String> {
+        &None
+    }
+}
@@ -13,9 +13,15 @@ pub enum Error {
     #[error("invalid option value `{0}`: `{1}`")]
     InvaldOptionValue(String, String),
 
+    #[error("value of option `{0}` is not type `{1}`")]
+    MismatchValueType(String, String),
+
     #[error("invalid option id: `{0}`")]
     InvaldOptionId(String),
 
+    #[error("invalid command line: `{0}`")]
+    InvalidCommandLine(String),
+
     #[error("no available argument left")]
     InvalidNextArgument,
 
@@ -34,15 +40,48 @@ pub enum Error {
     #[error("the given type is exists: `{0}`")]
     DuplicateOptionType(String),
 
+    #[error("the given option is exists: `{0}`")]
+    DuplicateOption(String),
+
     #[error("`{0}` need an argument")]
     ArgumentRequired(String),
 
+    #[error("missing argument of option `{0}`, it is the last argument")]
+    MissingArgument(String),
+
     #[error("option `{0}` is force required")]
     OptionForceRequired(String),
 
     #[error("need non-option: `{0}`")]
     NonOptionForceRequired(String),
 
+    #[error("positional argument `{name}` at index {index} is missing")]
+    PositionalMissing { index: i64, name: String },
+
+    #[error("invalid subcommand `{0}`, valid subcommands: {1:?}")]
+    InvalidSubcommand(String, Vec<String>),
+
+    #[error("unknown option: `{0}`")]
+    UnknownOption(String),
+
+    #[error("ambiguous option `{0}`, possible options: {1:?}")]
+    AmbiguousOption(String, Vec<String>),
+
+    #[error("options conflict with each other: `{0}`")]
+    ConflictOption(String),
+
+    #[error("option `{0}` requires `{1}`")]
+    MissingDependency(String, String),
+
+    #[error("too many positional arguments, accept at most {max} but got {got}")]
+    TooManyPositionals { max: usize, got: usize },
+
+    #[error("option value interpolation has cycle: `{0}`")]
+    InterpolationCycle(String),
+
+    #[error("response file nested too deep: `{0}`")]
+    ResponseFileTooDeep(String),
+
     #[error("invalid callback type for id(`{0}`): `{1}`")]
     InvalidCallbackType(String, String),
 
@@ -51,4 +90,64 @@ pub enum Error {
 
     #[error("catch error: `{0}`")]
     RaisedError(String),
+
+    #[error("{0}: {1}")]
+    WithContext(String, #[source] Box<Error>),
+}
+
+impl Error {
+    /// Wrap the error with context message `ctx`, such as the option string caused the error.
+    pub fn with_context(self, ctx: &str) -> Error {
+        Error::WithContext(ctx.to_owned(), Box::new(self))
+    }
+
+    /// Render the error message followed by the usage line generated by `generator`.
+    pub fn render_with_usage(&self, generator: &crate::help::SetHelpGenerator) -> String {
+        format!("error: {}\n{}\n", self, generator.gen_usage())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn make_sure_error_display_work() {
+        let error = Error::InvaldOptionValue(String::from("abc"), String::from("invalid digit found in string"));
+
+        assert_eq!(error.to_string(), "invalid option value `abc`: `invalid digit found in string`");
+
+        let error = error.with_context("--count=abc");
+
+        assert_eq!(error.to_string(), "--count=abc: invalid option value `abc`: `invalid digit found in string`");
+        assert!(std::error::Error::source(&error).is_some());
+
+        let boxed: Box<dyn std::error::Error> = Box::new(Error::NullOptionName.with_context("=int"));
+
+        assert_eq!(boxed.to_string(), "=int: option name can not be null");
+        assert_eq!(boxed.source().unwrap().to_string(), "option name can not be null");
+
+        let ret: std::result::Result<(), Box<dyn std::error::Error>> = (|| { Err(Error::InvalidNextArgument)?; Ok(()) })();
+
+        assert_eq!(ret.unwrap_err().to_string(), "no available argument left");
+    }
+
+    #[test]
+    fn make_sure_render_with_usage_work() {
+        use crate::set::{Set, DefaultSet};
+        use crate::help::SetHelpGenerator;
+
+        let mut set = DefaultSet::new();
+
+        set.initialize_utils().unwrap();
+        set.initialize_prefixs();
+        set.add_opt("--count=int").unwrap().commit().unwrap();
+        set.add_opt("file=pos!@1").unwrap().commit().unwrap();
+
+        let generator = SetHelpGenerator::new("app", &set);
+        let error = Error::NonOptionForceRequired(String::from("file=pos! @1"));
+
+        assert_eq!(generator.gen_usage(), "usage: app [OPTIONS] [ARGS]");
+        assert_eq!(error.render_with_usage(&generator), "error: need non-option: `file=pos! @1`\nusage: app [OPTIONS] [ARGS]\n");
+    }
 }
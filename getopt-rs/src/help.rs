@@ -1,4 +1,5 @@
 use crate::set::Set;
+use crate::opt::{Opt, Style, OptValue, HelpInfo};
 
 
 /// usage
@@ -33,5 +34,449 @@ pub trait HelpGenerator {
 }
 
 pub struct SetHelpGenerator<'a> {
+    name: String,
+
     set: &'a dyn Set,
-}
\ No newline at end of file
+
+    hint_template: Option<String>,
+}
+
+/// The verbosity of help generated by [`SetHelpGenerator::gen_help`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HelpVerbosity {
+    /// Only the usage and one line per command.
+    Summary,
+
+    /// The usage, commands, options and positional arguments.
+    Full,
+}
+
+/// The shell type of completion script.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shell {
+    Bash,
+
+    Zsh,
+
+    Fish,
+}
+
+impl<'a> SetHelpGenerator<'a> {
+    pub fn new(name: &str, set: &'a dyn Set) -> Self {
+        Self {
+            name: name.to_owned(),
+            set,
+            hint_template: None,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn set_name(&mut self, name: &str) {
+        self.name = name.to_owned();
+    }
+
+    /// Set the template of option hint, it will be used when no explicit hint set.
+    /// 
+    /// The template support placeholders:
+    /// 
+    /// * `{prefix}` the prefix of option, such as `--`.
+    /// * `{name}` the name of option.
+    /// * `{type}` the type name of option, such as `int`.
+    /// * `{metavar}` the uppercase name for option need an argument, or empty.
+    /// * `{default}` the default value of option, or empty.
+    /// * `{required}` the string `required` for force required option, or empty.
+    pub fn set_hint_template(&mut self, template: &str) {
+        self.hint_template = Some(template.to_owned());
+    }
+
+    pub fn hint_template(&self) -> Option<&String> {
+        self.hint_template.as_ref()
+    }
+
+    /// Generate the hint of option, the explicit hint will override the template.
+    pub fn gen_hint(&self, opt: &dyn Opt) -> String {
+        let help_info = opt.help_info();
+        let default_hint = HelpInfo::new2(opt.prefix(), opt.name(), opt.type_name(), opt.optional(), "").hint;
+
+        match self.hint_template.as_ref() {
+            Some(template) if help_info.hint == default_hint => {
+                let metavar = if opt.is_style(Style::Argument) {
+                    opt.name().to_uppercase()
+                }
+                else {
+                    String::default()
+                };
+                let default = match opt.default_value() {
+                    OptValue::Int(v) => v.to_string(),
+                    OptValue::Uint(v) => v.to_string(),
+                    OptValue::Flt(v) => v.to_string(),
+                    OptValue::Str(v) => v.clone(),
+                    OptValue::Bool(v) => v.to_string(),
+                    OptValue::Array(v) => v.join(","),
+                    OptValue::Map(v) => v.iter().map(|(k, v)| format!("{}={}", k, v)).collect::<Vec<String>>().join(","),
+                    _ => String::default(),
+                };
+
+                template.replace("{prefix}", opt.prefix())
+                        .replace("{name}", opt.name())
+                        .replace("{type}", opt.type_name())
+                        .replace("{metavar}", &metavar)
+                        .replace("{default}", &default)
+                        .replace("{required}", if opt.optional() { "" } else { "required" })
+                        .trim()
+                        .to_owned()
+            }
+            _ => help_info.hint,
+        }
+    }
+
+    /// Return all the option names and alias with prefix, such as `--debug`.
+    pub fn opt_names(&self) -> Vec<(String, String)> {
+        let mut ret = vec![];
+
+        for opt in self.set.iter() {
+            if opt.is_style(Style::Boolean)
+            || opt.is_style(Style::Argument)
+            || opt.is_style(Style::Multiple) {
+                ret.push((opt.prefix().to_owned(), opt.name().to_owned()));
+                if let Some(alias) = opt.alias() {
+                    for (prefix, name) in alias {
+                        ret.push((prefix.clone(), name.clone()));
+                    }
+                }
+            }
+        }
+        ret
+    }
+
+    /// Return all the command names.
+    pub fn cmd_names(&self) -> Vec<String> {
+        self.set.iter()
+                .filter(|opt| opt.is_style(Style::Cmd))
+                .map(|opt| opt.name().to_owned())
+                .collect()
+    }
+
+    /// Generate the usage line, such as `usage: app [OPTIONS] <COMMAND> [ARGS]`.
+    pub fn gen_usage(&self) -> String {
+        let mut usage = format!("usage: {}", self.name);
+
+        if self.set.iter().any(|opt| opt.is_style(Style::Boolean) || opt.is_style(Style::Argument) || opt.is_style(Style::Multiple)) {
+            usage.push_str(" [OPTIONS]");
+        }
+        if self.set.iter().any(|opt| opt.is_style(Style::Cmd)) {
+            usage.push_str(" <COMMAND>");
+        }
+        if self.set.iter().any(|opt| opt.is_style(Style::Pos)) {
+            usage.push_str(" [ARGS]");
+        }
+        usage
+    }
+
+    /// Generate the help of given verbosity.
+    pub fn gen_help(&self, verbosity: HelpVerbosity) -> String {
+        let mut cmds = vec![];
+        let mut sections: Vec<(String, Vec<(String, String)>)> = vec![];
+        let mut poss = vec![];
+
+        for opt in self.set.iter() {
+            let help_info = opt.help_info();
+            let help = help_info.help.clone();
+
+            if opt.is_style(Style::Cmd) {
+                match verbosity {
+                    HelpVerbosity::Summary => cmds.push((opt.name().to_owned(), self.gen_hint(opt.as_ref()))),
+                    HelpVerbosity::Full => cmds.push((opt.name().to_owned(), help)),
+                }
+            }
+            else if opt.is_style(Style::Pos) {
+                poss.push((self.gen_hint(opt.as_ref()), help));
+            }
+            else if opt.is_style(Style::Boolean) || opt.is_style(Style::Argument) || opt.is_style(Style::Multiple) {
+                let mut names = vec![self.gen_hint(opt.as_ref())];
+
+                if let Some(alias) = opt.alias() {
+                    for (prefix, name) in alias {
+                        let alias = format!("{}{}", prefix, name);
+
+                        names.push(help_info.get_alias_hint(&alias).map(|v| v.to_owned()).unwrap_or(alias));
+                    }
+                }
+                let section = if help_info.section.is_empty() {
+                    "Options"
+                }
+                else {
+                    help_info.section.as_str()
+                };
+
+                // the sections are listed in order of first appearance
+                match sections.iter_mut().find(|(title, _)| title == section) {
+                    Some((_, opts)) => opts.push((names.join(", "), help)),
+                    None => sections.push((section.to_owned(), vec![(names.join(", "), help)])),
+                }
+            }
+        }
+
+        let mut usage = self.gen_usage();
+
+        usage.push('\n');
+
+        match verbosity {
+            HelpVerbosity::Summary => {
+                usage + &gen_section("Commands", &cmds)
+            }
+            HelpVerbosity::Full => {
+                let mut help = usage + &gen_section("Commands", &cmds);
+
+                for (title, opts) in sections.iter() {
+                    help.push_str(&gen_section(title, opts));
+                }
+                help + &gen_section("Args", &poss)
+            }
+        }
+    }
+
+    /// Generate the completion script of given shell.
+    pub fn gen_completion(&self, shell: Shell) -> String {
+        let opts = self.opt_names();
+        let cmds = self.cmd_names();
+        let mut words: Vec<String> = opts.iter().map(|(p, n)| format!("{}{}", p, n)).collect();
+
+        words.extend(cmds.iter().cloned());
+
+        match shell {
+            Shell::Bash => {
+                format!(
+                    "_complete_{0}() {{\n    local cur=\"${{COMP_WORDS[COMP_CWORD]}}\"\n    COMPREPLY=( $(compgen -W \"{1}\" -- \"$cur\") )\n}}\n\ncomplete -F _complete_{0} {0}\n",
+                    self.name,
+                    words.join(" ")
+                )
+            }
+            Shell::Zsh => {
+                format!(
+                    "#compdef {0}\n\n_{0}() {{\n    compadd -- {1}\n}}\n\n_{0} \"$@\"\n",
+                    self.name,
+                    words.join(" ")
+                )
+            }
+            Shell::Fish => {
+                let mut script = String::new();
+
+                for (prefix, name) in opts.iter() {
+                    let flag = if prefix == "--" {
+                        "-l"
+                    }
+                    else if prefix == "-" && name.chars().count() == 1 {
+                        "-s"
+                    }
+                    else {
+                        "-o"
+                    };
+                    script.push_str(&format!("complete -c {} {} {}\n", self.name, flag, name));
+                }
+                for cmd in cmds.iter() {
+                    script.push_str(&format!("complete -c {} -f -a {}\n", self.name, cmd));
+                }
+                script
+            }
+        }
+    }
+}
+
+/// Generate the section with aligned rows, return empty string if no row.
+fn gen_section(title: &str, rows: &[(String, String)]) -> String {
+    let mut ret = String::new();
+
+    if ! rows.is_empty() {
+        let width = rows.iter().map(|v| v.0.len()).max().unwrap_or(0);
+
+        ret.push_str(&format!("\n{}:\n", title));
+        for (name, help) in rows.iter() {
+            ret.push_str(format!("  {:<width$}  {}", name, help, width = width).trim_end());
+            ret.push('\n');
+        }
+    }
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::set::DefaultSet;
+
+    #[test]
+    fn make_sure_gen_completion_work() {
+        let mut set = DefaultSet::new();
+
+        set.initialize_utils().unwrap();
+        set.initialize_prefixs();
+
+        if let Ok(mut commit) = set.add_opt("-d=bool") {
+            commit.add_alias("--", "debug");
+            commit.commit().unwrap();
+        }
+        if let Ok(mut commit) = set.add_opt("--count=int") {
+            commit.commit().unwrap();
+        }
+        if let Ok(mut commit) = set.add_opt("add=cmd") {
+            commit.commit().unwrap();
+        }
+        if let Ok(mut commit) = set.add_opt("file=pos@2") {
+            commit.commit().unwrap();
+        }
+
+        let generator = SetHelpGenerator::new("app", &set);
+        let bash = generator.gen_completion(Shell::Bash);
+
+        assert!(bash.contains("complete -F _complete_app app"));
+        for word in ["-d", "--debug", "--count", "add"].iter() {
+            assert!(bash.contains(word));
+        }
+        assert!(! bash.contains("file"));
+
+        let zsh = generator.gen_completion(Shell::Zsh);
+
+        assert!(zsh.starts_with("#compdef app"));
+        assert!(zsh.contains("compadd -- -d --debug --count add"));
+
+        let fish = generator.gen_completion(Shell::Fish);
+
+        assert!(fish.contains("complete -c app -s d"));
+        assert!(fish.contains("complete -c app -l debug"));
+        assert!(fish.contains("complete -c app -l count"));
+        assert!(fish.contains("complete -c app -f -a add"));
+    }
+
+    #[test]
+    fn make_sure_hint_template_work() {
+        let mut set = DefaultSet::new();
+
+        set.initialize_utils().unwrap();
+        set.initialize_prefixs();
+
+        let debug = set.add_opt("--debug=bool").unwrap().commit().unwrap();
+        let count = set.add_opt("--count=int!").unwrap().commit().unwrap();
+        let mut commit = set.add_opt("-o=str").unwrap();
+
+        commit.set_hint("-o <FILE>");
+        let output = commit.commit().unwrap();
+
+        let mut generator = SetHelpGenerator::new("app", &set);
+
+        assert_eq!(generator.gen_hint(set.get_opt(count).unwrap()), "<--count=int>");
+
+        generator.set_hint_template("{prefix}{name} {metavar}");
+
+        assert_eq!(generator.gen_hint(set.get_opt(debug).unwrap()), "--debug");
+        assert_eq!(generator.gen_hint(set.get_opt(count).unwrap()), "--count COUNT");
+        assert_eq!(generator.gen_hint(set.get_opt(output).unwrap()), "-o <FILE>");
+
+        generator.set_hint_template("{prefix}{name}={type} {required}");
+
+        assert_eq!(generator.gen_hint(set.get_opt(count).unwrap()), "--count=int required");
+        assert_eq!(generator.gen_hint(set.get_opt(debug).unwrap()), "--debug=bool");
+    }
+
+    #[test]
+    fn make_sure_gen_help_work() {
+        let mut set = DefaultSet::new();
+
+        set.initialize_utils().unwrap();
+        set.initialize_prefixs();
+
+        if let Ok(mut commit) = set.add_opt("-d=bool") {
+            commit.add_alias("--", "debug");
+            commit.set_help("print debug message");
+            commit.commit().unwrap();
+        }
+        if let Ok(mut commit) = set.add_opt("--count=int") {
+            commit.set_help("set the count");
+            commit.commit().unwrap();
+        }
+        if let Ok(mut commit) = set.add_opt("add=cmd") {
+            commit.set_help("add a file");
+            commit.commit().unwrap();
+        }
+        if let Ok(mut commit) = set.add_opt("file=pos@2") {
+            commit.set_help("the file name");
+            commit.commit().unwrap();
+        }
+
+        let generator = SetHelpGenerator::new("app", &set);
+        let summary = generator.gen_help(HelpVerbosity::Summary);
+        let full = generator.gen_help(HelpVerbosity::Full);
+
+        assert_eq!(summary, "usage: app [OPTIONS] <COMMAND> [ARGS]\n\nCommands:\n  add  <add=cmd>\n");
+        assert!(summary.lines().count() < full.lines().count());
+        for detail in ["--debug", "print debug message", "--count", "set the count", "the file name"].iter() {
+            assert!(! summary.contains(detail));
+            assert!(full.contains(detail));
+        }
+        assert!(full.contains("add  add a file"));
+    }
+
+    #[test]
+    fn make_sure_alias_hint_work() {
+        let mut set = DefaultSet::new();
+
+        set.initialize_utils().unwrap();
+        set.initialize_prefixs();
+
+        if let Ok(mut commit) = set.add_opt("-d=bool") {
+            commit.set_hint("-d");
+            commit.add_alias("--", "debug");
+            commit.add_alias_with_hint("--", "verbose", "--verbose (deprecated)");
+            commit.set_help("print debug message");
+            commit.commit().unwrap();
+        }
+
+        let generator = SetHelpGenerator::new("app", &set);
+        let full = generator.gen_help(HelpVerbosity::Full);
+
+        assert!(full.contains("  -d, --debug, --verbose (deprecated)  print debug message\n"));
+
+        let clone = set.clone_config().unwrap();
+        let generator = SetHelpGenerator::new("app", &clone);
+
+        assert_eq!(generator.gen_help(HelpVerbosity::Full), full);
+    }
+
+    #[test]
+    fn make_sure_help_section_work() {
+        let mut set = DefaultSet::new();
+
+        set.initialize_utils().unwrap();
+        set.initialize_prefixs();
+
+        if let Ok(mut commit) = set.add_opt("-d=bool") {
+            commit.set_help("print debug message");
+            commit.commit().unwrap();
+        }
+        if let Ok(mut commit) = set.add_opt("-o=str") {
+            commit.set_section("Output");
+            commit.set_help("the output file");
+            commit.commit().unwrap();
+        }
+        if let Ok(mut commit) = set.add_opt("--host=str") {
+            commit.set_section("Network");
+            commit.set_help("the host name");
+            commit.commit().unwrap();
+        }
+        if let Ok(mut commit) = set.add_opt("--color=bool") {
+            commit.set_section("Output");
+            commit.set_help("colorize the output");
+            commit.commit().unwrap();
+        }
+
+        let generator = SetHelpGenerator::new("app", &set);
+        let full = generator.gen_help(HelpVerbosity::Full);
+
+        assert_eq!(full, "usage: app [OPTIONS]\n\
+                          \nOptions:\n  [-d=bool]  print debug message\n\
+                          \nOutput:\n  [-o=str]        the output file\n  [--color=bool]  colorize the output\n\
+                          \nNetwork:\n  [--host=str]  the host name\n");
+    }
+}
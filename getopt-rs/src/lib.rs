@@ -1,14 +1,39 @@
+//! The option string parser in [`pattern`] only depend on `core` and `alloc`,
+//! it is available without the default `std` feature, such as `--name=int!`.
+//! Other modules need the `std` feature.
+#![cfg_attr(not(feature="std"), no_std)]
+
+extern crate alloc;
+
+pub mod pattern;
+
+#[cfg(feature="std")]
 pub mod id;
+#[cfg(feature="std")]
 pub mod opt;
+#[cfg(feature="std")]
 pub mod ctx;
+#[cfg(feature="std")]
 pub mod set;
+#[cfg(feature="std")]
 pub mod arg;
+#[cfg(feature="std")]
 pub mod proc;
+#[cfg(feature="std")]
 pub mod help;
+#[cfg(feature="std")]
+pub mod rule;
+#[cfg(feature="std")]
+pub mod trace;
+#[cfg(feature="std")]
 pub mod error;
+#[cfg(feature="std")]
 pub mod utils;
+#[cfg(feature="std")]
 pub mod parser;
+#[cfg(feature="std")]
 pub mod nonopt;
+#[cfg(feature="std")]
 pub mod callback;
 
 #[macro_use]
@@ -16,6 +41,7 @@ extern crate log;
 
 extern crate async_trait;
 
+#[cfg(feature="std")]
 pub mod prelude {
     pub use crate::error::{Result, Error};
     pub use crate::parser::{Parser, ForwardParser, DelayParser, PreParser};
@@ -59,12 +85,22 @@ pub mod prelude {
     ///     getopt_impl(&mut ai, parsers)
     /// }
     /// ```
+    /// 
+    /// The last argument can be a closure, it will be called with the matched parser.
+    /// `getopt(ai, parser, set, |p| p.noa().len())` will may expand to
+    /// ```ignore
+    /// {
+    ///     ...
+    ///     getopt_impl(&mut ai, parsers).map(|ret| ret.map(|p| p.noa().len()))
+    /// }
+    /// ```
     pub use getopt_rs_macro::getopt;
 }
 
+#[cfg(feature="std")]
 use prelude::*;
 
-#[cfg(not(feature="async"))]
+#[cfg(all(feature="std", not(feature="async")))]
 pub fn getopt_impl<S, G>(iter: &mut dyn IndexIterator, parsers: Vec<Box<dyn Parser<S, G>>>) -> Result<Option<Box<dyn Parser<S, G>>>>
     where S: Set, G: IdGenerator {
     for mut parser in parsers {
@@ -82,7 +118,7 @@ pub fn getopt_impl<S, G>(iter: &mut dyn IndexIterator, parsers: Vec<Box<dyn Pars
     Ok(None)
 }
 
-#[cfg(feature="async")]
+#[cfg(all(feature="std", feature="async"))]
 pub async fn getopt_impl<S, G>(iter: &mut dyn IndexIterator, parsers: Vec<Box<dyn Parser<S, G>>>) -> Result<Option<Box<dyn Parser<S, G>>>>
     where S: Set, G: IdGenerator {
     for mut parser in parsers {
@@ -100,6 +136,7 @@ pub async fn getopt_impl<S, G>(iter: &mut dyn IndexIterator, parsers: Vec<Box<dy
     Ok(None)
 }
 
+#[cfg(feature="std")]
 pub mod tools {
     use crate::prelude::*;
     use simplelog::{Config, CombinedLogger, SimpleLogger};
@@ -131,11 +168,128 @@ pub mod tools {
         ForwardParser::new(default_id_gen(id))
     }
 
+    /// Set the default value of options which name is the key of `kv`, such as the values load from config file.
+    /// The value is parsed by the option, and the current value of option is reset to the new default.
+    /// The keys not match any option are ignored.
+    pub fn apply_defaults(set: &mut dyn Set, kv: &std::collections::HashMap<String, String>) -> Result<bool> {
+        for opt in set.iter_mut() {
+            if let Some(value) = kv.get(opt.name()) {
+                let value = opt.parse_value(value).map_err(|e| e.with_context(opt.name()))?;
+
+                opt.set_default_value(value.clone());
+                opt.replace_value(value);
+            }
+        }
+        Ok(true)
+    }
+
+    /// The parser registered under the name of subcommand, see [`dispatch`].
+    pub type SubParser<S, G> = (String, Box<dyn Parser<S, G>>);
+
+    /// Return true if `arg` is an option of `set` using the next argument as value, such as `-c` of `-c 3`.
+    fn need_next_argument(set: &dyn Set, arg: &str) -> bool {
+        let arg = match crate::arg::parse_argument_impl(&Some(arg.to_owned()), set.get_prefix()) {
+            Ok(arg) => arg,
+            Err(_) => return false,
+        };
+
+        match (arg.get_prefix(), arg.get_name(), arg.get_value()) {
+            (Some(prefix), Some(name), None) => {
+                set.get_opt_by_name(prefix, name).is_some_and(|opt| opt.is_style(crate::opt::Style::Argument))
+                    || crate::parser::find_group_argument(set, &arg).is_some_and(|pos| name[pos ..].chars().count() == 1)
+            }
+            _ => false,
+        }
+    }
+
+    /// Return the first argument not start with any prefix of parsers' set,
+    /// the argument used as value of option is skipped, such as `3` of `-c 3 add`.
+    fn first_subcommand<S, G>(iter: &dyn IndexIterator, parsers: &[SubParser<S, G>]) -> Option<String>
+        where S: Set, G: IdGenerator {
+        let sets: Vec<&S> = parsers.iter().filter_map(|(_, parser)| parser.set().as_ref()).collect();
+        let prefixs: Vec<&String> = sets.iter().flat_map(|set| set.get_prefix().iter()).collect();
+        let mut skip_next = false;
+
+        for arg in (0 ..).map(|n| iter.peek_nth(n)).take_while(|arg| arg.is_some()).flatten() {
+            if skip_next {
+                skip_next = false;
+            }
+            else if prefixs.iter().any(|prefix| arg.starts_with(prefix.as_str())) {
+                skip_next = sets.iter().any(|set| need_next_argument(*set, arg));
+            }
+            else {
+                return Some(arg.clone());
+            }
+        }
+        None
+    }
+
+    /// Select the parser registered under the name of subcommand, and only run it.
+    /// The subcommand is the first argument not start with any prefix and not used as value of option.
+    /// Return [`Error::InvalidSubcommand`] if no parser registered under the subcommand.
+    #[cfg(not(feature="async"))]
+    pub fn dispatch<S, G>(iter: &mut dyn IndexIterator, parsers: Vec<SubParser<S, G>>) -> Result<Option<Box<dyn Parser<S, G>>>>
+        where S: Set, G: IdGenerator {
+        let cmd = first_subcommand(iter, &parsers).unwrap_or_default();
+        let names = parsers.iter().map(|(name, _)| name.clone()).collect();
+
+        for (name, mut parser) in parsers {
+            if name == cmd {
+                return match parser.parse(iter)? {
+                    Some(true) => Ok(Some(parser)),
+                    _ => Ok(None),
+                };
+            }
+        }
+        Err(Error::InvalidSubcommand(cmd, names))
+    }
+
+    /// Select the parser registered under the name of subcommand, and only run it.
+    /// The subcommand is the first argument not start with any prefix and not used as value of option.
+    /// Return [`Error::InvalidSubcommand`] if no parser registered under the subcommand.
+    #[cfg(feature="async")]
+    pub async fn dispatch<S, G>(iter: &mut dyn IndexIterator, parsers: Vec<SubParser<S, G>>) -> Result<Option<Box<dyn Parser<S, G>>>>
+        where S: Set, G: IdGenerator {
+        let cmd = first_subcommand(iter, &parsers).unwrap_or_default();
+        let names = parsers.iter().map(|(name, _)| name.clone()).collect();
+
+        for (name, mut parser) in parsers {
+            if name == cmd {
+                return match parser.parse(iter).await? {
+                    Some(true) => Ok(Some(parser)),
+                    _ => Ok(None),
+                };
+            }
+        }
+        Err(Error::InvalidSubcommand(cmd, names))
+    }
+
+    /// Create the [`SetHelpGenerator`](crate::help::SetHelpGenerator) of `set`,
+    /// the name of generator is the file name of current program.
+    pub fn build_help_from_set(set: &dyn Set) -> crate::help::SetHelpGenerator<'_> {
+        let name = std::env::args()
+                        .next()
+                        .and_then(|v| std::path::Path::new(&v).file_name().map(|v| v.to_string_lossy().into_owned()))
+                        .unwrap_or_default();
+
+        crate::help::SetHelpGenerator::new(&name, set)
+    }
+
     #[cfg(not(feature="async"))]
     pub fn simple_value_callback<F>(t: F) -> OptCallback where F: 'static + FnMut(&dyn Opt) -> Result<bool> {
         OptCallback::from_value(Box::new(crate::callback::SimpleValueCallback::new(t)))
     }
 
+    #[cfg(not(feature="async"))]
+    pub fn simple_raw_value_callback<F>(t: F) -> OptCallback where F: 'static + FnMut(&dyn Opt, &str) -> Result<bool> {
+        OptCallback::from_raw_value(Box::new(crate::callback::SimpleRawValueCallback::new(t)))
+    }
+
+    #[cfg(not(feature="async"))]
+    pub fn simple_transform_callback<F>(t: F) -> OptCallback where F: 'static + FnMut(&mut dyn Opt) -> Result<bool> {
+        OptCallback::from_transform(Box::new(crate::callback::SimpleTransformCallback::new(t)))
+    }
+
     #[cfg(not(feature="async"))]
     pub fn simple_index_callback<F>(t: F) -> OptCallback where F: 'static + FnMut( &dyn Set, &String ) -> Result<bool> {
         OptCallback::from_index(Box::new(crate::callback::SimpleIndexCallback::new(t))) 
@@ -145,4 +299,170 @@ pub mod tools {
     pub fn simple_main_callback<F>(t: F) -> OptCallback where F: 'static + FnMut( &dyn Set, &Vec<String> ) -> Result<bool> {
         OptCallback::from_main(Box::new(crate::callback::SimpleMainCallback::new(t))) 
     }
+
+    #[cfg(not(feature="async"))]
+    pub fn simple_main_mut_callback<F>(t: F) -> OptCallback where F: 'static + FnMut( &mut dyn Set, &[String] ) -> Result<bool> {
+        OptCallback::from_main_mut(Box::new(crate::callback::SimpleMainMutCallback::new(t)))
+    }
+}
+#[cfg(all(test, feature="std"))]
+mod tests {
+    use crate::prelude::*;
+    use crate::proc::Subscriber;
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_getopt_work() {
+        let mut set = DefaultSet::new();
+        let mut parser = ForwardParser::<DefaultSet, DefaultIdGen>::default();
+        let mut ai = ArgIterator::new();
+
+        set.initialize_utils().unwrap();
+        set.initialize_prefixs();
+        set.add_opt("-d=bool").unwrap().commit().unwrap();
+        ai.set_args(&mut ["-d", "foo"].iter().map(|&v|String::from(v)));
+
+        let ret = getopt!(ai, parser, set).unwrap();
+
+        assert!(ret.is_some());
+        assert_eq!(ret.unwrap().noa(), &vec![String::from("foo")]);
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_getopt_with_closure_work() {
+        let mut set = DefaultSet::new();
+        let mut parser = ForwardParser::<DefaultSet, DefaultIdGen>::default();
+        let mut ai = ArgIterator::new();
+
+        set.initialize_utils().unwrap();
+        set.initialize_prefixs();
+        set.add_opt("-d=bool").unwrap().commit().unwrap();
+        ai.set_args(&mut ["-d", "foo"].iter().map(|&v|String::from(v)));
+
+        let ret = getopt!(ai, parser, set, |p| {
+            assert_eq!(p.get_opt(Identifier::new(0)).unwrap().value().as_bool(), Some(&true));
+            p.noa().len()
+        }).unwrap();
+
+        assert_eq!(ret, Some(1));
+    }
+
+    #[test]
+    fn make_sure_build_help_from_set_work() {
+        use crate::help::HelpVerbosity;
+
+        let mut set = DefaultSet::new();
+
+        set.initialize_utils().unwrap();
+        set.initialize_prefixs();
+        if let Ok(mut commit) = set.add_opt("--count=int") {
+            commit.set_help("set the count");
+            commit.commit().unwrap();
+        }
+        if let Ok(mut commit) = set.add_opt("file=pos@1") {
+            commit.set_help("the input file");
+            commit.commit().unwrap();
+        }
+
+        let generator = crate::tools::build_help_from_set(&set);
+        let help = generator.gen_help(HelpVerbosity::Full);
+
+        assert!(! generator.name().is_empty());
+        assert!(help.starts_with(&format!("usage: {} [OPTIONS] [ARGS]", generator.name())));
+        assert!(help.contains("--count"));
+        assert!(help.contains("set the count"));
+        assert!(help.contains("file"));
+        assert!(help.contains("the input file"));
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_apply_defaults_work() {
+        use std::collections::HashMap;
+        use crate::tools::apply_defaults;
+
+        let kv: HashMap<String, String> = [("count", "7"), ("unknown", "x")].iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+
+        for (args, value) in [(vec![], 7), (vec!["--count", "3"], 3)].iter() {
+            let mut set = DefaultSet::new();
+            let mut parser = ForwardParser::<DefaultSet, DefaultIdGen>::default();
+            let mut ai = ArgIterator::new();
+
+            set.initialize_utils().unwrap();
+            set.initialize_prefixs();
+            set.add_opt("--count=int").unwrap().commit().unwrap();
+            apply_defaults(&mut set, &kv).unwrap();
+            assert_eq!(set[Identifier::new(0)].default_value().as_int(), Some(&7));
+            ai.set_args(&mut args.iter().map(|&v|String::from(v)));
+            set.subscribe_from(&mut parser);
+            parser.publish_to(set);
+            parser.parse(&mut ai).unwrap();
+            assert_eq!(parser.get_opt(Identifier::new(0)).unwrap().value().as_int(), Some(value));
+        }
+
+        let mut set = DefaultSet::new();
+
+        set.initialize_utils().unwrap();
+        set.initialize_prefixs();
+        set.add_opt("--count=int").unwrap().commit().unwrap();
+
+        let kv: HashMap<String, String> = [("count".to_string(), "seven".to_string())].iter().cloned().collect();
+
+        assert!(matches!(apply_defaults(&mut set, &kv), Err(Error::WithContext(name, _)) if name == "count"));
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_dispatch_work() {
+        use crate::tools::dispatch;
+
+        for (args, ret) in [
+            (vec!["add", "-f", "foo"], Some("add")),
+            (vec!["-v", "remove", "-r", "bar"], Some("remove")),
+            (vec!["-c", "3", "add", "-f"], Some("add")),
+            (vec!["-vc", "3", "remove", "-r"], Some("remove")),
+            (vec!["-c=3", "add", "-f"], Some("add")),
+            (vec!["commit"], None),
+            (vec!["-v"], None),
+        ].iter() {
+            let mut parsers: Vec<(String, Box<dyn Parser<DefaultSet, DefaultIdGen>>)> = vec![];
+
+            for (name, opt) in [("add", "-f=bool"), ("remove", "-r=bool")].iter() {
+                let mut set = DefaultSet::new();
+                let mut parser = ForwardParser::<DefaultSet, DefaultIdGen>::default();
+
+                set.initialize_utils().unwrap();
+                set.initialize_prefixs();
+                set.add_opt(&format!("{}=cmd", name)).unwrap().commit().unwrap();
+                set.add_opt(opt).unwrap().commit().unwrap();
+                set.add_opt("-v=bool").unwrap().commit().unwrap();
+                set.add_opt("-c=int").unwrap().commit().unwrap();
+                set.subscribe_from(&mut parser);
+                parser.publish_to(set);
+                parsers.push((name.to_string(), Box::new(parser)));
+            }
+
+            let mut ai = ArgIterator::new();
+
+            ai.set_args(&mut args.iter().map(|&v|String::from(v)));
+
+            match ret {
+                Some(name) => {
+                    let parser = dispatch(&mut ai, parsers).unwrap().unwrap();
+
+                    assert_eq!(parser.get_opt(Identifier::new(0)).unwrap().name(), *name);
+                    assert_eq!(parser.get_opt(Identifier::new(1)).unwrap().value().as_bool(), Some(&true));
+                }
+                None => {
+                    let cmd = args.iter().find(|v| ! v.starts_with('-')).map(|v| v.to_string()).unwrap_or_default();
+
+                    assert!(matches!(
+                        dispatch(&mut ai, parsers),
+                        Err(Error::InvalidSubcommand(name, names)) if name == cmd && names == vec!["add", "remove"]
+                    ));
+                }
+            }
+        }
+    }
 }
\ No newline at end of file
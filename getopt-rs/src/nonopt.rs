@@ -37,6 +37,8 @@ pub mod pos {
     /// * The option support callback type [`CallbackType::Index`].
     ///
     /// User can set it at specify index of command line non-option argument.
+    /// With a value option such as `directory=int@1`, the matched argument is parsed
+    /// by that option, and the value is an typed [`OptValue`].
     #[derive(Debug)]
     pub struct PosNonOpt {
         id: IIdentifier,
@@ -54,6 +56,8 @@ pub mod pos {
         default_value: OptValue,
 
         help: HelpInfo,
+
+        value_opt: Option<Box<dyn Opt>>,
     }
 
     impl PosNonOpt {
@@ -67,8 +71,21 @@ pub mod pos {
                 default_value: OptValue::default(),
                 callback: CallbackType::default(),
                 help,
+                value_opt: None,
             }
         }
+
+        /// Set the option used to parse the matched argument, the default value is taken from it.
+        pub fn set_value_opt(&mut self, value_opt: Box<dyn Opt>) {
+            self.default_value = value_opt.default_value().clone();
+            self.value = value_opt.value().clone();
+            self.value_opt = Some(value_opt);
+        }
+
+        /// Return the type name of value option, or None if the value is not typed.
+        pub fn value_type(&self) -> Option<&str> {
+            self.value_opt.as_ref().map(|opt| opt.type_name())
+        }
     }
 
     opt_def!(PosNonOpt, Pos, NonOpt);
@@ -169,12 +186,18 @@ pub mod pos {
 
         }
 
-        fn parse_value(&self, _: &str) -> Result<OptValue> {
-            return Ok(OptValue::from_bool(true));
+        fn parse_value(&self, value_para: &str) -> Result<OptValue> {
+            match self.value_opt.as_ref() {
+                Some(value_opt) => value_opt.parse_value(value_para),
+                None => Ok(OptValue::from_bool(true)),
+            }
         }
 
         fn has_value(&self) -> bool {
-            self.value().is_bool()
+            match self.value_opt.as_ref() {
+                Some(_) => ! self.value().is_null(),
+                None => self.value().is_bool(),
+            }
         }
 
         fn reset_value(&mut self) {
@@ -780,6 +803,31 @@ mod tests {
         assert_eq!(nonopt.as_ref().as_any().is::<pos::PosNonOpt>(), true);
     }
 
+    #[test]
+    fn make_opt_type_pos_range_work() {
+        let pos_utils = pos::PosUtils::new();
+
+        let ci = CreateInfo::parse("nonopt=pos@2..4", &vec![]).unwrap();
+        let nonopt = pos_utils.create(IIdentifier::new(1), &ci).unwrap();
+
+        assert_eq!(nonopt.index(), &NonOptIndex::Range(2, Some(4)));
+        assert_eq!(nonopt.match_index(6, 1), false);
+        assert_eq!(nonopt.match_index(6, 2), true);
+        assert_eq!(nonopt.match_index(6, 3), true);
+        assert_eq!(nonopt.match_index(6, 4), true);
+        assert_eq!(nonopt.match_index(6, 5), false);
+        assert_eq!(nonopt.match_index(3, 4), false);
+
+        let ci = CreateInfo::parse("nonopt=pos@2..", &vec![]).unwrap();
+        let nonopt = pos_utils.create(IIdentifier::new(2), &ci).unwrap();
+
+        assert_eq!(nonopt.index(), &NonOptIndex::Range(2, None));
+        assert_eq!(nonopt.match_index(6, 1), false);
+        assert_eq!(nonopt.match_index(6, 2), true);
+        assert_eq!(nonopt.match_index(6, 6), true);
+        assert_eq!(nonopt.match_index(6, 7), false);
+    }
+
     #[test]
     fn make_opt_type_cmd_work() {
         let cmd_utils = cmd::CmdUtils::new();
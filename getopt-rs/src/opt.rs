@@ -1,12 +1,16 @@
 
 use std::fmt::Debug;
 use std::any::Any;
+use std::rc::Rc;
+use std::convert::TryFrom;
+use std::collections::BTreeMap;
 
 use crate::callback::CallbackType;
 use crate::id::Identifier as IIdentifier;
 use crate::utils::{Utils, CreateInfo};
 use crate::proc::Info;
 use crate::error::{Error, Result};
+use crate::pattern::PatternIndex;
 
 /// The option style type.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -42,6 +46,38 @@ pub enum Style {
     Null,
 }
 
+impl Style {
+    /// Return the style of given `name`, it is the inverse of [`Display`](std::fmt::Display).
+    pub fn from_name(name: &str) -> Option<Style> {
+        match name {
+            "boolean" => Some(Style::Boolean),
+            "argument" => Some(Style::Argument),
+            "multiple" => Some(Style::Multiple),
+            "pos" => Some(Style::Pos),
+            "cmd" => Some(Style::Cmd),
+            "main" => Some(Style::Main),
+            "null" => Some(Style::Null),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for Style {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Style::Boolean => "boolean",
+            Style::Argument => "argument",
+            Style::Multiple => "multiple",
+            Style::Pos => "pos",
+            Style::Cmd => "cmd",
+            Style::Main => "main",
+            Style::Null => "null",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
 /// The option value type.
 /// 
 /// It support `i64`, `u64`, `String` and `Vec<String>`, etc.
@@ -68,6 +104,9 @@ pub enum OptValue {
     /// An vector can hold multiple value
     Array(Vec<String>),
 
+    /// An map can hold multiple key value pair
+    Map(BTreeMap<String, String>),
+
     /// Any type
     Any(Box<dyn Any>),
 
@@ -93,6 +132,11 @@ pub enum NonOptIndex {
 
     Except(Vec<u64>),
 
+    /// The range of position, the end is included, `None` means no end.
+    Range(u64, Option<u64>),
+
+    /// Any position, it is not matched if there is no non-option argument,
+    /// so the force required one will cause [`Error::NonOptionForceRequired`].
     AnyWhere,
 
     Null
@@ -115,6 +159,10 @@ impl NonOptIndex {
         Self::Except(list)
     }
 
+    pub fn range(begin: u64, end: Option<u64>) -> Self {
+        Self::Range(begin, end)
+    }
+
     pub fn anywhere() -> Self {
         Self::AnyWhere
     }
@@ -123,6 +171,8 @@ impl NonOptIndex {
         Self::Null
     }
 
+    /// Return the 1-based index matched by current index of `total` non-option arguments,
+    /// `current` is the index of argument being matched, such as `@-1` return `total`.
     pub fn calc_index(&self, total: u64, current: u64) -> Option<u64> {
         match self {
             NonOptIndex::Forward(offset) => {
@@ -152,6 +202,13 @@ impl NonOptIndex {
                     }
                 }
             }
+            NonOptIndex::Range(begin, end) => {
+                let end = end.map_or(total, |end| end.min(total));
+
+                if *begin <= current && current <= end {
+                    return Some(current);
+                }
+            }
             _ => { }
         }
         None
@@ -171,17 +228,62 @@ impl Default for NonOptIndex {
     }
 }
 
-/// Currently `OptionInfo` only hold a option identifier.
+impl From<PatternIndex> for NonOptIndex {
+    fn from(index: PatternIndex) -> Self {
+        match index {
+            PatternIndex::Forward(offset) => Self::Forward(offset),
+            PatternIndex::Backward(offset) => Self::Backward(offset),
+            PatternIndex::Range(begin, end) => Self::Range(begin, end),
+            PatternIndex::AnyWhere => Self::AnyWhere,
+            PatternIndex::Null => Self::Null,
+        }
+    }
+}
+
+impl std::fmt::Display for NonOptIndex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let join = |list: &Vec<u64>| list.iter().map(|v| v.to_string()).collect::<Vec<String>>().join(",");
+
+        match self {
+            NonOptIndex::Forward(offset) => write!(f, "@{}", offset),
+            NonOptIndex::Backward(offset) => write!(f, "@-{}", offset),
+            NonOptIndex::List(list) => write!(f, "@[{}]", join(list)),
+            NonOptIndex::Except(list) => write!(f, "@![{}]", join(list)),
+            NonOptIndex::Range(begin, Some(end)) => write!(f, "@{}..{}", begin, end),
+            NonOptIndex::Range(begin, None) => write!(f, "@{}..", begin),
+            NonOptIndex::AnyWhere => write!(f, "@0"),
+            NonOptIndex::Null => Ok(()),
+        }
+    }
+}
+
+/// `OptionInfo` hold a option identifier and the names of option.
 /// `Parser` can get the option from `Set` using this identifier.
 #[derive(Debug)]
 pub struct OptionInfo {
     id: IIdentifier,
+
+    names: Vec<(String, String)>,
 }
 
 impl OptionInfo {
     pub fn new(id: IIdentifier) -> Self {
         Self {
-            id
+            id,
+            names: vec![],
+        }
+    }
+
+    /// Create the info with the prefix, name and alias of `opt`.
+    pub fn from_opt(opt: &dyn Opt) -> Self {
+        let mut names = vec![(opt.prefix().to_owned(), opt.name().to_owned())];
+
+        if let Some(alias) = opt.alias() {
+            names.extend(alias.iter().cloned());
+        }
+        Self {
+            id: opt.id(),
+            names,
         }
     }
 }
@@ -190,6 +292,10 @@ impl Info for OptionInfo {
     fn id(&self) -> IIdentifier {
         self.id
     }
+
+    fn names(&self) -> &[(String, String)] {
+        &self.names
+    }
 }
 
 /// Some specify interface of an option type.
@@ -585,6 +691,13 @@ pub trait Value {
 
     /// Reset value
     fn reset_value(&mut self);
+
+    /// Replace the option value with `v`.
+    /// In default it is same as [`set_value`](Value::set_value),
+    /// the option type which accumulate value in `set_value` need override it.
+    fn replace_value(&mut self, v: OptValue) {
+        self.set_value(v);
+    }
 }
 
 /// The index interface of an non-option.
@@ -647,6 +760,11 @@ pub trait Help {
     fn set_help(&mut self, help: &str);
 
     fn help_info(&self) -> HelpInfo;
+
+    /// The help message of option, it is set by [`Commit::set_help`](crate::set::Commit::set_help) or [`set_help`](Help::set_help).
+    fn help(&self) -> String {
+        self.help_info().help
+    }
 }
 
 /// HelpInfo using for generate usage.
@@ -655,6 +773,74 @@ pub struct HelpInfo {
     pub hint: String,
 
     pub help: String,
+
+    /// The hint of alias, such as (`--debug`, `--debug[=LEVEL]`).
+    pub alias_hints: Vec<(String, String)>,
+
+    /// The section of option in help, such as `Output`, empty means the default section.
+    pub section: String,
+}
+
+/// The function check the option value, see [`ValueValidator`].
+pub type ValidatorFn = dyn Fn(&OptValue) -> Result<()>;
+
+/// ValueValidator will check the value after it parsed from command line argument.
+#[derive(Clone)]
+pub struct ValueValidator(Rc<ValidatorFn>);
+
+impl ValueValidator {
+    pub fn new(validator: Box<ValidatorFn>) -> Self {
+        Self(Rc::from(validator))
+    }
+
+    /// Return the `value` if it pass the validator, or [`Error::InvaldOptionValue`].
+    pub fn validate(&self, raw: &str, value: OptValue) -> Result<OptValue> {
+        match self.0(&value) {
+            Ok(_) => Ok(value),
+            Err(e) => Err(Error::InvaldOptionValue(raw.to_owned(), format!("{}", e))),
+        }
+    }
+}
+
+impl Debug for ValueValidator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ValueValidator")
+         .field("Fn", &String::from("..."))
+         .finish()
+    }
+}
+
+/// ValueReader read the value of option from other source, such as stdin.
+#[derive(Clone)]
+pub struct ValueReader(Rc<dyn Fn() -> std::io::Result<String>>);
+
+impl ValueReader {
+    pub fn new(reader: Box<dyn Fn() -> std::io::Result<String>>) -> Self {
+        Self(Rc::from(reader))
+    }
+
+    /// Return a reader read all the content of stdin.
+    pub fn stdin() -> Self {
+        Self::new(Box::new(|| {
+            let mut content = String::new();
+
+            std::io::Read::read_to_string(&mut std::io::stdin(), &mut content)?;
+            Ok(content)
+        }))
+    }
+
+    /// Return the content read by the reader, or [`Error::CatchIOError`].
+    pub fn read(&self) -> Result<String> {
+        Ok(self.0()?)
+    }
+}
+
+impl Debug for ValueReader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ValueReader")
+         .field("Fn", &String::from("..."))
+         .finish()
+    }
 }
 
 /// The option trait type, you need implement follow traits:
@@ -668,10 +854,37 @@ pub struct HelpInfo {
 /// * [`Index`]
 /// * [`Callback`]
 /// * [`Help`]
-pub trait Opt: Type + Identifier + Name + Alias + Optional + Value + Index + Callback + Help + Debug { }
+pub trait Opt: Type + Identifier + Name + Alias + Optional + Value + Index + Callback + Help + Debug {
+    /// Return a one line description of option, such as `--count=int! @1 (aliases: -c)`.
+    fn describe(&self) -> String {
+        let mut desc = format!("{}{}={}", self.prefix(), self.name(), self.type_name());
+
+        if ! self.optional() {
+            desc.push('!');
+        }
+        if ! self.index().is_null() {
+            desc.push_str(&format!(" {}", self.index()));
+        }
+        if let Some(alias) = self.alias() {
+            if ! alias.is_empty() {
+                let aliases: Vec<String> = alias.iter().map(|(prefix, name)| format!("{}{}", prefix, name)).collect();
+
+                desc.push_str(&format!(" (aliases: {})", aliases.join(", ")));
+            }
+        }
+        desc
+    }
+}
 
 /// Helper function clone the any value
-pub struct CloneHelper(Box< dyn Fn (&dyn Any) -> Box<dyn Any>>);
+#[derive(Clone)]
+pub struct CloneHelper(Rc< dyn Fn (&dyn Any) -> Box<dyn Any>>);
+
+impl CloneHelper {
+    pub fn new<F>(f: F) -> Self where F: Fn (&dyn Any) -> Box<dyn Any> + 'static {
+        Self(Rc::new(f))
+    }
+}
 
 impl Debug for CloneHelper {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -686,6 +899,8 @@ impl HelpInfo {
         Self {
             hint: hint.to_owned(),
             help: help.to_owned(),
+            alias_hints: vec![],
+            section: String::default(),
         }
     }
 
@@ -700,43 +915,109 @@ impl HelpInfo {
                 type_name,
                 if optional { "]" } else { ">" },
             ),
+            alias_hints: vec![],
+            section: String::default(),
         }
     }
 
     pub fn clone_or(&self, opt: &dyn Opt) -> Self {
         if self.hint.is_empty() {
-            Self::new2(
+            let mut ret = Self::new2(
                 opt.prefix(),
                 opt.name(),
                 opt.type_name(),
                 opt.optional(),
                 self.help.as_str(),
-            )
+            );
+
+            ret.alias_hints = self.alias_hints.clone();
+            ret.section = self.section.clone();
+            ret
         }
         else {
             self.clone()
         }
     }
+
+    /// Set the hint of `alias` with prefix, such as `--debug`.
+    pub fn add_alias_hint(&mut self, alias: &str, hint: &str) {
+        self.rem_alias_hint(alias);
+        self.alias_hints.push((alias.to_owned(), hint.to_owned()));
+    }
+
+    pub fn rem_alias_hint(&mut self, alias: &str) {
+        self.alias_hints.retain(|(name, _)| name != alias);
+    }
+
+    /// Get the hint of `alias` with prefix, such as `--debug`.
+    pub fn get_alias_hint(&self, alias: &str) -> Option<&str> {
+        self.alias_hints.iter().find(|(name, _)| name == alias).map(|(_, hint)| hint.as_str())
+    }
+}
+
+/// Split the integer string to sign, radix and digits, the radix is detected from prefix `0x`, `0o` or `0b`.
+fn split_radix(s: &str) -> (&str, u32, &str) {
+    let (sign, digits) = match s.strip_prefix('-') {
+        Some(digits) => ("-", digits),
+        None => ("", s.strip_prefix('+').unwrap_or(s)),
+    };
+
+    for (prefix, radix) in [("0x", 16), ("0X", 16), ("0o", 8), ("0O", 8), ("0b", 2), ("0B", 2)].iter() {
+        if let Some(digits) = digits.strip_prefix(prefix) {
+            return (sign, *radix, digits);
+        }
+    }
+    (sign, 10, digits)
 }
 
 impl OptValue {
+    /// Parse the string to [`OptValue::Int`], the prefix `0x`, `0o` and `0b` is supported, such as `-0xFF`.
     pub fn parse_int(s: &str) -> Result<Self> {
-        match s.parse::<i64>() {
+        let (sign, radix, digits) = split_radix(s);
+
+        if digits.starts_with(|c| c == '+' || c == '-') {
+            // the sign is only allowed before the radix prefix, such as `-0xFF`
+            return Err(Error::InvaldOptionValue(s.to_owned(), String::from("the sign is only allowed at the beginning")));
+        }
+        match i64::from_str_radix(&format!("{}{}", sign, digits), radix) {
             Ok(value) => Ok(Self::from_int(value)),
             Err(e) => Err(Error::InvaldOptionValue(s.to_owned(), format!("{:?}", e))),
         }
     }
 
+    /// Parse the string to [`OptValue::Uint`], the prefix `0x`, `0o` and `0b` is supported, such as `0o755`.
     pub fn parse_uint(s: &str) -> Result<Self> {
-        match s.parse::<u64>() {
+        let (sign, radix, digits) = split_radix(s);
+
+        if ! sign.is_empty() {
+            return Err(Error::InvaldOptionValue(s.to_owned(), String::from("negative value is not allowed")));
+        }
+        if digits.starts_with(|c| c == '+' || c == '-') {
+            // the sign `+` is only allowed before the radix prefix, such as `+0xFF`
+            return Err(Error::InvaldOptionValue(s.to_owned(), String::from("the sign is only allowed at the beginning")));
+        }
+        match u64::from_str_radix(digits, radix) {
             Ok(value) => Ok(Self::from_uint(value)),
             Err(e) => Err(Error::InvaldOptionValue(s.to_owned(), format!("{:?}", e))),
         }
     }
 
+    /// Parse the string to [`OptValue::Flt`], the `NaN` and infinity are rejected.
     pub fn parse_flt(s: &str) -> Result<Self> {
+        Self::parse_flt_with(s, false)
+    }
+
+    /// Parse the string to [`OptValue::Flt`], the `NaN` and infinity are accepted if `allow_non_finite` is true.
+    pub fn parse_flt_with(s: &str, allow_non_finite: bool) -> Result<Self> {
         match s.parse::<f64>() {
-            Ok(value) => Ok(Self::from_flt(value)),
+            Ok(value) => {
+                if allow_non_finite || value.is_finite() {
+                    Ok(Self::from_flt(value))
+                }
+                else {
+                    Err(Error::InvaldOptionValue(s.to_owned(), String::from("non-finite value is not allowed")))
+                }
+            }
             Err(e) => Err(Error::InvaldOptionValue(s.to_owned(), format!("{:?}", e))),
         }
     }
@@ -765,10 +1046,19 @@ impl OptValue {
         Self::Array(t.into())
     }
 
+    pub fn from_map<T: Into<BTreeMap<String, String>>>(t: T) -> Self {
+        Self::Map(t.into())
+    }
+
     pub fn from_any<T: Any>(t: Box<T>) -> Self {
         Self::Any(t)
     }
 
+    /// Create an OptValue::Any hold a [`PathBuf`](std::path::PathBuf)
+    pub fn from_path<P: Into<std::path::PathBuf>>(p: P) -> Self {
+        Self::Any(Box::new(p.into()))
+    }
+
     pub fn null() -> Self {
         Self::Null
     }
@@ -797,6 +1087,19 @@ impl OptValue {
         }
     }
 
+    /// Return the string of OptValue::Flt formatted with `precision` digits after the decimal point,
+    /// such as `2.50` of `2.5` with `Some(2)`, or the shortest string if `precision` is None.
+    /// Return None if the value is not an OptValue::Flt
+    pub fn as_flt_string(&self, precision: Option<usize>) -> Option<String> {
+        match self {
+            Self::Flt(v) => match precision {
+                Some(precision) => Some(format!("{:.*}", precision, v)),
+                None => Some(v.to_string()),
+            },
+            _ => None,
+        }
+    }
+
     /// Return None if the value is not an OptValue::Str
     pub fn as_str(&self) -> Option<&String> {
         match self {
@@ -830,6 +1133,14 @@ impl OptValue {
         }
     }
 
+    /// Return None if the value is not an OptValue::Map
+    pub fn as_map(&self) -> Option<&BTreeMap<String, String>> {
+        match self {
+            Self::Map(v) => Some(v),
+            _ => None,
+        }
+    }
+
     /// Return None if the value is not an OptValue::Any
     pub fn as_any(&self) -> Option<&Box<dyn Any>> {
         match self {
@@ -846,6 +1157,61 @@ impl OptValue {
         }
     }
 
+    /// Return None if the value is not an OptValue::Any hold a [`PathBuf`](std::path::PathBuf)
+    pub fn as_path(&self) -> Option<&std::path::Path> {
+        self.downcast_ref::<std::path::PathBuf>().map(|v| v.as_path())
+    }
+
+    /// Convert the OptValue::Int, OptValue::Uint or OptValue::Flt to i64,
+    /// the fraction of float is truncated.
+    /// Return None if the value is not numeric or out of range.
+    pub fn as_i64_lossy(&self) -> Option<i64> {
+        match self {
+            Self::Int(v) => Some(*v),
+            Self::Uint(v) => i64::try_from(*v).ok(),
+            Self::Flt(v) => {
+                if *v >= i64::MIN as f64 && *v < i64::MAX as f64 {
+                    Some(*v as i64)
+                }
+                else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Convert the OptValue::Int, OptValue::Uint or OptValue::Flt to u64,
+    /// the fraction of float is truncated.
+    /// Return None if the value is not numeric or out of range.
+    pub fn as_u64_lossy(&self) -> Option<u64> {
+        match self {
+            Self::Int(v) => u64::try_from(*v).ok(),
+            Self::Uint(v) => Some(*v),
+            Self::Flt(v) => {
+                if *v > -1.0 && *v < u64::MAX as f64 {
+                    Some(*v as u64)
+                }
+                else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Convert the OptValue::Int, OptValue::Uint or OptValue::Flt to f64,
+    /// the large integer may lose precision.
+    /// Return None if the value is not numeric.
+    pub fn as_f64_lossy(&self) -> Option<f64> {
+        match self {
+            Self::Int(v) => Some(*v as f64),
+            Self::Uint(v) => Some(*v as f64),
+            Self::Flt(v) => Some(*v),
+            _ => None,
+        }
+    }
+
     /// Return None if the value is not an OptValue::Int
     pub fn as_int_mut(&mut self) -> Option<&mut i64> {
         match self {
@@ -894,6 +1260,14 @@ impl OptValue {
         }
     }
 
+    /// Return None if the value is not an OptValue::Map
+    pub fn as_map_mut(&mut self) -> Option<&mut BTreeMap<String, String>> {
+        match self {
+            Self::Map(v) => Some(v),
+            _ => None,
+        }
+    }
+
     pub fn app_value(&mut self, s: String) -> &mut Self {
         match self {
             Self::Array(v) => {
@@ -962,6 +1336,13 @@ impl OptValue {
         }
     }
 
+    pub fn is_map(&self) -> bool {
+        match self {
+            Self::Map(_) => true,
+            _ => false,
+        }
+    }
+
     pub fn is_any(&self) -> bool {
         match self {
             Self::Any(_) => true,
@@ -998,6 +1379,126 @@ impl OptValue {
             }
         }
     }
+
+    /// Merge the `other` value into current value, such as combine the values of different parser.
+    /// 
+    /// * `OptValue::Array` append the values of `other` array.
+    /// * `OptValue::Map` insert the key value pairs of `other` map.
+    /// * `OptValue::Null` `other` will not change current value.
+    /// * `OptValue::Any` do nothing, see [`merge_or`](OptValue::merge_or).
+    /// * Other value will be replaced by `other`.
+    pub fn merge(&mut self, other: OptValue) -> &mut Self {
+        self.merge_or(other, &None)
+    }
+
+    /// Same as [`merge`](OptValue::merge),
+    /// but the `OptValue::Any` is replaced by the value cloned by `clone_helper` if it is present.
+    pub fn merge_or(&mut self, other: OptValue, clone_helper: &Option<CloneHelper>) -> &mut Self {
+        match (&mut *self, other) {
+            (_, Self::Null) => { }
+            (Self::Array(v), Self::Array(ov)) => {
+                v.extend(ov);
+            }
+            (Self::Map(v), Self::Map(ov)) => {
+                v.extend(ov);
+            }
+            (Self::Any(_), other) | (_, other @ Self::Any(_)) => {
+                if let Some(clone_helper) = clone_helper {
+                    *self = other.clone_or(&Some(clone_helper.clone()));
+                }
+            }
+            (_, other) => {
+                *self = other;
+            }
+        }
+        self
+    }
+
+    /// Return the type tag used by [`to_storage_string`](OptValue::to_storage_string),
+    /// None if the value can not be stored.
+    pub fn storage_type_tag(&self) -> Option<&'static str> {
+        match self {
+            Self::Int(_) => Some("int"),
+            Self::Uint(_) => Some("uint"),
+            Self::Flt(_) => Some("flt"),
+            Self::Str(_) => Some("str"),
+            Self::Bool(_) => Some("bool"),
+            Self::Array(_) => Some("array"),
+            _ => None,
+        }
+    }
+
+    /// Convert the value to a string which can be loaded by [`from_storage_string`](OptValue::from_storage_string).
+    /// 
+    /// * `OptValue::Array` every value is followed by `,`, the `,` and `\` in value are escaped with `\`.
+    /// * `OptValue::Map`, `OptValue::Any` and `OptValue::Null` return None.
+    pub fn to_storage_string(&self) -> Option<String> {
+        match self {
+            Self::Int(v) => Some(v.to_string()),
+            Self::Uint(v) => Some(v.to_string()),
+            Self::Flt(v) => Some(v.to_string()),
+            Self::Str(v) => Some(v.clone()),
+            Self::Bool(v) => Some(v.to_string()),
+            Self::Array(v) => {
+                let mut ret = String::new();
+
+                for value in v {
+                    for ch in value.chars() {
+                        if ch == ',' || ch == '\\' {
+                            ret.push('\\');
+                        }
+                        ret.push(ch);
+                    }
+                    ret.push(',');
+                }
+                Some(ret)
+            }
+            _ => None,
+        }
+    }
+
+    /// Load the value from string generated by [`to_storage_string`](OptValue::to_storage_string),
+    /// the `type_tag` is the type return by [`storage_type_tag`](OptValue::storage_type_tag).
+    pub fn from_storage_string(type_tag: &str, s: &str) -> Result<Self> {
+        match type_tag {
+            "int" => Self::parse_int(s),
+            "uint" => Self::parse_uint(s),
+            "flt" => Self::parse_flt_with(s, true),
+            "str" => Ok(Self::from_str(s)),
+            "bool" => match s {
+                "true" => Ok(Self::from_bool(true)),
+                "false" => Ok(Self::from_bool(false)),
+                _ => Err(Error::InvaldOptionValue(s.to_owned(), String::from("invalid boolean value"))),
+            },
+            "array" => {
+                let mut ret = vec![];
+                let mut value = String::new();
+                let mut chars = s.chars();
+
+                while let Some(ch) = chars.next() {
+                    match ch {
+                        '\\' => {
+                            match chars.next() {
+                                Some(ch) => value.push(ch),
+                                None => return Err(Error::InvaldOptionValue(s.to_owned(), String::from("unterminated escape"))),
+                            }
+                        }
+                        ',' => {
+                            ret.push(std::mem::take(&mut value));
+                        }
+                        ch => {
+                            value.push(ch);
+                        }
+                    }
+                }
+                if ! value.is_empty() {
+                    return Err(Error::InvaldOptionValue(s.to_owned(), String::from("unterminated array value")));
+                }
+                Ok(Self::from_vec(ret))
+            }
+            _ => Err(Error::InvalidOptionType(type_tag.to_owned())),
+        }
+    }
 }
 
 /// Clone the option value except `OptValue::Any`.
@@ -1016,6 +1517,8 @@ impl Clone for OptValue {
 
             Self::Array(vv) => { Self::Array(vv.clone()) },
 
+            Self::Map(mv) => { Self::Map(mv.clone()) },
+
             Self::Null => { Self::Null },
 
             Self::Any(_) => {
@@ -1025,6 +1528,23 @@ impl Clone for OptValue {
     }
 }
 
+/// Compare the option value, the `OptValue::Any` is never equal to other value.
+impl PartialEq for OptValue {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Int(l), Self::Int(r)) => l == r,
+            (Self::Uint(l), Self::Uint(r)) => l == r,
+            (Self::Flt(l), Self::Flt(r)) => l == r,
+            (Self::Str(l), Self::Str(r)) => l == r,
+            (Self::Bool(l), Self::Bool(r)) => l == r,
+            (Self::Array(l), Self::Array(r)) => l == r,
+            (Self::Map(l), Self::Map(r)) => l == r,
+            (Self::Null, Self::Null) => true,
+            _ => false,
+        }
+    }
+}
+
 impl Default for OptValue {
     fn default() -> Self {
         OptValue::Null
@@ -1760,6 +2280,7 @@ pub mod str {
     /// * The option support callback type [`CallbackType::Value`].
     ///
     /// User can set it at `anywhere` of command line argument, using the string `-s "value"`, `-s=value`, `--str "value"`, etc.
+    /// If the option [`read stdin on dash`](StrOpt::set_stdin_on_dash), the value `-` will be replaced with the content of stdin.
     #[derive(Debug)]
     pub struct StrOpt {
         id: IIdentifier,
@@ -1779,6 +2300,10 @@ pub mod str {
         callback: CallbackType,
         
         help: HelpInfo,
+
+        stdin_on_dash: bool,
+
+        reader: Option<ValueReader>,
     }
 
     impl StrOpt {
@@ -1793,8 +2318,28 @@ pub mod str {
                 alias: vec![],
                 callback: CallbackType::Null,
                 help: help,
+                stdin_on_dash: false,
+                reader: None,
             }
         }
+
+        /// Read the value from stdin if the value is exactly `-`, otherwise `-` is a literal value.
+        pub fn set_stdin_on_dash(&mut self, stdin_on_dash: bool) {
+            self.stdin_on_dash = stdin_on_dash;
+        }
+
+        pub fn is_stdin_on_dash(&self) -> bool {
+            self.stdin_on_dash
+        }
+
+        /// Set the reader used instead of stdin when the value is `-`.
+        pub fn set_reader(&mut self, reader: Option<ValueReader>) {
+            self.reader = reader;
+        }
+
+        pub fn get_reader(&self) -> Option<&ValueReader> {
+            self.reader.as_ref()
+        }
     }
 
     opt_def!(StrOpt, Str);
@@ -1813,7 +2358,8 @@ pub mod str {
         CallbackType::Value,
         CallbackType::Null,
         CallbackType::Null,
-        CallbackType::Value
+        CallbackType::Value,
+        CallbackType::Transform
     );
 
     opt_identifier_def!(
@@ -1877,6 +2423,11 @@ pub mod str {
         }
 
         fn parse_value(&self, value_para: &str) -> Result<OptValue> {
+            if self.stdin_on_dash && value_para == "-" {
+                let reader = self.reader.clone().unwrap_or_else(ValueReader::stdin);
+
+                return Ok(OptValue::from_str(reader.read()?));
+            }
             return Ok(OptValue::from_str(value_para));
         }
 
@@ -1948,12 +2499,14 @@ pub mod str {
                     opt.add_alias(&a.0, &a.1);
                 }
             }
+            opt.set_stdin_on_dash(ci.is_stdin_on_dash());
+            opt.set_reader(ci.get_reader().cloned());
 
             Ok(opt)
         }
 
         fn gen_info(&self, opt: &dyn Opt) -> Box<dyn Info> {
-            Box::new(OptionInfo::new(opt.id()))
+            Box::new(OptionInfo::from_opt(opt))
         }
     }
 }
@@ -1979,6 +2532,9 @@ pub mod bool {
     /// * The option support callback type [`CallbackType::Value`].
     ///
     /// User can set it at `anywhere` of command line argument, using the string `-q`, `-s`, `-qs`, `--quite`, `--slient`, etc.
+    /// The value can be attached explicitly, such as `--quite=false`, 
+    /// the accepted values are `true`, `false`, `1`, `0`, `yes` and `no`.
+    /// The deactivate style option not accept value.
     #[derive(Debug)]
     pub struct BoolOpt {
         id: IIdentifier,
@@ -2040,7 +2596,8 @@ pub mod bool {
         CallbackType::Value,
         CallbackType::Null,
         CallbackType::Null,
-        CallbackType::Value
+        CallbackType::Value,
+        CallbackType::Transform
     );
 
     opt_identifier_def!(
@@ -2103,8 +2660,16 @@ pub mod bool {
             self.default_value = default_value_para;
         }
 
-        fn parse_value(&self, _: &str) -> Result<OptValue> {
-            Ok(OptValue::from_bool(! self.is_deactivate_style()))
+        fn parse_value(&self, value_para: &str) -> Result<OptValue> {
+            if self.is_deactivate_style() && ! value_para.is_empty() {
+                return Err(Error::InvaldOptionValue(value_para.to_owned(), String::from("deactivate style option not accept value")));
+            }
+            match value_para {
+                "" => Ok(OptValue::from_bool(! self.is_deactivate_style())),
+                "true" | "1" | "yes" => Ok(OptValue::from_bool(true)),
+                "false" | "0" | "no" => Ok(OptValue::from_bool(false)),
+                _ => Err(Error::InvaldOptionValue(value_para.to_owned(), String::from("expect one of true/false/1/0/yes/no"))),
+            }
         }
 
         /// For [`BoolOpt`], it need return true if current value is not equal default value
@@ -2192,7 +2757,7 @@ pub mod bool {
         }
 
         fn gen_info(&self, opt: &dyn Opt) -> Box<dyn Info> {
-            Box::new(OptionInfo::new(opt.id()))
+            Box::new(OptionInfo::from_opt(opt))
         }
     }
 }
@@ -2218,7 +2783,11 @@ pub mod array {
     /// * The option support callback type [`CallbackType::Value`].
     ///
     /// User can set it at `anywhere` of command line argument, using the string `-a "foo"`, `-a "bar"`, `-a=foo`, `--append=bar`, `--append=foo`, etc.
-    /// Set value to `ArrayOpt` will append the value to it.
+    /// Set value to `ArrayOpt` will append the value to it,
+    /// the value already exists will be skipped if the option is [`unique`](ArrayOpt::set_unique).
+    /// The value is splitted into multiple values if the [`delimiter`](ArrayOpt::set_delimiter) is set, such as `-a foo,bar`.
+    /// Each value must be parsed as the [`element type`](ArrayOpt::set_element_type) if it is set, such as `--ports=array:uint`,
+    /// the value is still stored as string.
     #[derive(Debug)]
     pub struct ArrayOpt {
         id: IIdentifier,
@@ -2238,6 +2807,12 @@ pub mod array {
         callback: CallbackType,
 
         help: HelpInfo,
+
+        unique: bool,
+
+        delimiter: Option<char>,
+
+        element_type: Option<String>,
     }
 
     impl ArrayOpt {
@@ -2252,8 +2827,65 @@ pub mod array {
                 alias: vec![],
                 callback: CallbackType::Null,
                 help,
+                unique: false,
+                delimiter: None,
+                element_type: None,
+            }
+        }
+
+        /// Skip the value already exists when set value, the insertion order is kept.
+        pub fn set_unique(&mut self, unique: bool) {
+            self.unique = unique;
+        }
+
+        pub fn is_unique(&self) -> bool {
+            self.unique
+        }
+
+        /// Split the value by `delimiter` when parse value, the escaped delimiter such as `\,` is treated literally.
+        pub fn set_delimiter(&mut self, delimiter: char) {
+            self.delimiter = Some(delimiter);
+        }
+
+        pub fn get_delimiter(&self) -> Option<char> {
+            self.delimiter
+        }
+
+        /// Check each value is the `element_type` when parse value, support `int`, `uint`, `flt` and `str`.
+        pub fn set_element_type(&mut self, element_type: &str) {
+            self.element_type = Some(element_type.to_owned());
+        }
+
+        pub fn get_element_type(&self) -> Option<&str> {
+            self.element_type.as_deref()
+        }
+    }
+
+    /// Return true if the `element_type` is supported by [`ArrayOpt`].
+    pub fn is_support_element_type(element_type: &str) -> bool {
+        matches!(element_type, "int" | "uint" | "flt" | "str")
+    }
+
+    /// Split the `value` by `delimiter`, the escaped delimiter such as `\,` is treated literally.
+    pub fn split_value(value: &str, delimiter: char) -> Vec<String> {
+        let mut ret = vec![];
+        let mut item = String::new();
+        let mut chars = value.chars().peekable();
+
+        while let Some(ch) = chars.next() {
+            if ch == '\\' && chars.peek() == Some(&delimiter) {
+                item.push(delimiter);
+                chars.next();
+            }
+            else if ch == delimiter {
+                ret.push(std::mem::take(&mut item));
+            }
+            else {
+                item.push(ch);
             }
         }
+        ret.push(item);
+        ret
     }
 
     opt_def!(ArrayOpt, Array);
@@ -2272,7 +2904,8 @@ pub mod array {
         CallbackType::Value,
         CallbackType::Null,
         CallbackType::Null,
-        CallbackType::Value
+        CallbackType::Value,
+        CallbackType::Transform
     );
 
     opt_identifier_def!(
@@ -2336,10 +2969,18 @@ pub mod array {
                 if self.value.is_null() {
                     self.value = OptValue::from_vec(vec![]);
                 }
-                self.value
-                .as_vec_mut()
-                .unwrap()
-                .append(value_para.as_vec_mut().unwrap());
+                let values = self.value.as_vec_mut().unwrap();
+
+                if self.unique {
+                    for value in value_para.as_vec_mut().unwrap().drain(..) {
+                        if ! values.contains(&value) {
+                            values.push(value);
+                        }
+                    }
+                }
+                else {
+                    values.append(value_para.as_vec_mut().unwrap());
+                }
             }
         }
 
@@ -2350,11 +2991,30 @@ pub mod array {
         fn parse_value(&self, value: &str) -> Result<OptValue> {
             let mut realv = OptValue::from_vec(vec![]);
 
-            realv.app_value(value.to_owned());
-            
-            Ok(realv)
-        }
-
+            match self.delimiter {
+                Some(delimiter) => {
+                    for item in split_value(value, delimiter) {
+                        realv.app_value(item);
+                    }
+                }
+                None => {
+                    realv.app_value(value.to_owned());
+                }
+            }
+            if let Some(element_type) = self.element_type.as_deref() {
+                for item in realv.as_vec().unwrap().iter() {
+                    match element_type {
+                        "int" => { OptValue::parse_int(item)?; }
+                        "uint" => { OptValue::parse_uint(item)?; }
+                        "flt" => { OptValue::parse_flt(item)?; }
+                        _ => { }
+                    }
+                }
+            }
+            
+            Ok(realv)
+        }
+
         fn has_value(&self) -> bool {
             self.value().is_array()
         }
@@ -2363,6 +3023,11 @@ pub mod array {
             self.value = OptValue::default();
             self.set_value(self.default_value().clone());
         }
+
+        fn replace_value(&mut self, value_para: OptValue) {
+            self.value = OptValue::default();
+            self.set_value(value_para);
+        }
     }
 
     /// Default [`Utils`] implementation for [`ArrayOpt`].
@@ -2417,6 +3082,295 @@ pub mod array {
                 ci.get_help_info().clone(),
             ));
 
+            opt.set_unique(ci.is_unique());
+            if let Some(delimiter) = ci.get_delimiter() {
+                opt.set_delimiter(delimiter);
+            }
+            if let Some(element_type) = ci.get_element_type() {
+                if ! is_support_element_type(element_type) {
+                    return Err(Error::UtilsNotSupportTypeName(self.type_name().to_owned(), format!("{}:{}", ci.get_type_name(), element_type)));
+                }
+                opt.set_element_type(element_type);
+            }
+
+            let alias = ci.get_alias();
+
+            if alias.len() > 0 {
+                for a in alias.iter() {
+                    opt.add_alias(&a.0, &a.1);
+                }
+            }
+
+            Ok(opt)
+        }
+
+        fn gen_info(&self, opt: &dyn Opt) -> Box<dyn Info> {
+            Box::new(OptionInfo::from_opt(opt))
+        }
+    }
+}
+
+pub mod map {
+    use crate::opt::*;
+    use crate::id::Identifier as IIdentifier;
+
+    pub fn current_type() -> &'static str {
+        "map"
+    }
+
+    pub fn default_delimiter() -> char {
+        ','
+    }
+
+    pub trait Map: Opt { }
+
+    /// MapOpt target the value to [`BTreeMap<String, String>`], 
+    /// 
+    /// * The option type name is `map`.
+    /// * The option is not support deactivate style.
+    /// * The option accept the style [`Style::Argument`].
+    /// * In default, the option is `optional`, it can be change through the [`set_optional`](crate::opt::Optional::set_optional).
+    /// * The option need an [`OptValue::Map`] argument, the default value is [`OptValue::default()`].
+    /// * The option support multiple alias with different prefix and name.
+    /// * The option support callback type [`CallbackType::Value`].
+    ///
+    /// User can set it at `anywhere` of command line argument, using the string `-l a=1,b=2`, `-l=c=3`, `--labels a=1`, etc.
+    /// The argument will be split by the item delimiter, then each item split by the first `=`.
+    /// The item without `=` is invalid, escape the delimiter in value, such as `-D K=a\,b`.
+    /// Set value to `MapOpt` will merge the value to it.
+    #[derive(Debug)]
+    pub struct MapOpt {
+        id: IIdentifier,
+
+        name: String,
+
+        prefix: String,
+
+        optional: bool,
+
+        value: OptValue,
+
+        default_value: OptValue,
+
+        alias: Vec<(String, String)>,
+
+        callback: CallbackType,
+
+        help: HelpInfo,
+
+        delimiter: Option<char>,
+    }
+
+    impl MapOpt {
+        pub fn new(id: IIdentifier, name: String, prefix: String, optional: bool, default_value: OptValue, help: HelpInfo) -> Self {
+            Self {
+                id,
+                name,
+                prefix,
+                optional,
+                value: default_value.clone_or(&None),
+                default_value,
+                alias: vec![],
+                callback: CallbackType::Null,
+                help,
+                delimiter: Some(default_delimiter()),
+            }
+        }
+
+        /// Split the items by `delimiter` when parse value, the escaped delimiter such as `\,` is treated literally.
+        /// The delimiter is [`default_delimiter`] if not set.
+        pub fn set_delimiter(&mut self, delimiter: char) {
+            self.delimiter = Some(delimiter);
+        }
+
+        pub fn get_delimiter(&self) -> Option<char> {
+            self.delimiter
+        }
+    }
+
+    opt_def!(MapOpt, Map);
+
+    opt_type_def!(
+        MapOpt, 
+        current_type(),
+        false,
+        { style, Style::Argument }
+    );
+
+    opt_callback_def!(
+        MapOpt,
+        callback,
+        callback,
+        CallbackType::Value,
+        CallbackType::Null,
+        CallbackType::Null,
+        CallbackType::Value,
+        CallbackType::Transform
+    );
+
+    opt_identifier_def!(
+        MapOpt,
+        id,
+        para,
+    );
+
+    opt_name_def!(
+        MapOpt,
+        prefix,
+        name,
+        prefix,
+        name,
+    );
+
+    opt_optional_def!(
+        MapOpt,
+        optional,
+        optional,
+    );
+
+    opt_alias_def!(
+        MapOpt,
+        alias,
+        prefix,
+        name,
+    );
+
+    opt_index_def!( MapOpt );
+
+    impl Help for MapOpt {
+        fn set_hint(&mut self, hint: &str) {
+            self.help.hint = hint.to_owned()
+        }
+
+        fn set_help(&mut self, help: &str) {
+            self.help.help = help.to_owned()
+        }
+
+        fn help_info(&self) -> HelpInfo {
+            self.help.clone_or(self)
+        }
+    }
+
+    impl Value for MapOpt {
+        fn value(&self) -> &OptValue {
+            &self.value
+        }
+
+        fn default_value(&self) -> &OptValue {
+            &self.default_value
+        }
+
+        /// WARNING! 
+        /// This function will merge the `value` to option's value
+        fn set_value(&mut self, value_para: OptValue) {
+            let mut value_para = value_para;
+
+            if value_para.is_map() {
+                if self.value.is_null() {
+                    self.value = OptValue::from_map(BTreeMap::new());
+                }
+                self.value
+                .as_map_mut()
+                .unwrap()
+                .append(value_para.as_map_mut().unwrap());
+            }
+        }
+
+        fn set_default_value(&mut self, default_value_para: OptValue) {
+            self.default_value = default_value_para;
+        }
+        
+        fn parse_value(&self, value: &str) -> Result<OptValue> {
+            let mut map = BTreeMap::new();
+            let items = match self.delimiter {
+                Some(delimiter) => array::split_value(value, delimiter),
+                None => vec![value.to_owned()],
+            };
+
+            for item in items {
+                match item.find('=') {
+                    Some(index) => {
+                        map.insert(item[0 .. index].to_owned(), item[index + 1 ..].to_owned());
+                    }
+                    None => {
+                        return Err(Error::InvaldOptionValue(value.to_owned(), format!("item `{}` need a `=`", item)));
+                    }
+                }
+            }
+            Ok(OptValue::from_map(map))
+        }
+
+        fn has_value(&self) -> bool {
+            self.value().is_map()
+        }
+
+        fn reset_value(&mut self) {
+            self.value = OptValue::default();
+            self.set_value(self.default_value().clone());
+        }
+
+        fn replace_value(&mut self, value_para: OptValue) {
+            self.value = OptValue::default();
+            self.set_value(value_para);
+        }
+    }
+
+    /// Default [`Utils`] implementation for [`MapOpt`].
+    #[derive(Debug)]
+    pub struct MapUtils;
+
+    impl MapUtils {
+        pub fn new() -> Self {
+            Self {}
+        }
+    }
+
+    impl Utils for MapUtils {
+        fn type_name(&self) -> &str {
+            current_type()
+        }
+
+        fn is_support_deactivate_style(&self) -> bool {
+            false
+        }
+
+        /// Create an [`MapOpt`] using option information [`CreateInfo`].
+        /// 
+        /// ```no_run
+        /// use getopt_rs::utils::{Utils, CreateInfo};
+        /// use getopt_rs::opt::map::*;
+        /// use getopt_rs::id::*;
+        /// 
+        /// let prefixs = vec![String::from("--")];
+        /// let utils = MapUtils::new();
+        /// let ci = CreateInfo::parse("--name=map!", &prefixs).unwrap();
+        /// let _opt = utils.create(Identifier::new(1), &ci);
+        /// ```
+        fn create(&self, id: IIdentifier, ci: &CreateInfo) -> Result<Box<dyn Opt>> {
+            if ci.is_deactivate_style() {
+                if ! self.is_support_deactivate_style() {
+                    return Err(Error::UtilsNotSupportDeactivateStyle(ci.get_name().to_owned()));
+                }
+            }
+            if ci.get_type_name() != self.type_name() {
+                return Err(Error::UtilsNotSupportTypeName(self.type_name().to_owned(), ci.get_type_name().to_owned()))
+            }
+            
+            assert_eq!(ci.get_type_name(), self.type_name());
+
+            let mut opt = Box::new(MapOpt::new(
+                id,
+                ci.get_name().to_owned(),
+                ci.get_prefix().to_owned(),
+                ci.is_optional(),
+                ci.get_default_value().clone_or(&None),
+                ci.get_help_info().clone(),
+            ));
+
+            if let Some(delimiter) = ci.get_delimiter() {
+                opt.set_delimiter(delimiter);
+            }
+
             let alias = ci.get_alias();
 
             if alias.len() > 0 {
@@ -2429,7 +3383,7 @@ pub mod array {
         }
 
         fn gen_info(&self, opt: &dyn Opt) -> Box<dyn Info> {
-            Box::new(OptionInfo::new(opt.id()))
+            Box::new(OptionInfo::from_opt(opt))
         }
     }
 }
@@ -2474,6 +3428,8 @@ pub mod int {
         callback: CallbackType,
 
         help: HelpInfo,
+
+        validator: Option<ValueValidator>,
     }
 
     impl IntOpt {
@@ -2488,8 +3444,13 @@ pub mod int {
                 alias: vec![],
                 callback: CallbackType::Null,
                 help,
+                validator: None,
             }
         }
+
+        pub fn set_validator(&mut self, validator: Option<ValueValidator>) {
+            self.validator = validator;
+        }
     }
 
     opt_def!(IntOpt, Int);
@@ -2508,7 +3469,8 @@ pub mod int {
         CallbackType::Value,
         CallbackType::Null,
         CallbackType::Null,
-        CallbackType::Value
+        CallbackType::Value,
+        CallbackType::Transform
     );
 
     opt_identifier_def!(
@@ -2526,21 +3488,255 @@ pub mod int {
     );
 
     opt_optional_def!(
-        IntOpt,
+        IntOpt,
+        optional,
+        optional,
+    );
+
+    opt_alias_def!(
+        IntOpt,
+        alias,
+        prefix,
+        name,
+    );
+
+    opt_index_def!( IntOpt );
+
+    impl Help for IntOpt {
+        fn set_hint(&mut self, hint: &str) {
+            self.help.hint = hint.to_owned()
+        }
+
+        fn set_help(&mut self, help: &str) {
+            self.help.help = help.to_owned()
+        }
+
+        fn help_info(&self) -> HelpInfo {
+            self.help.clone_or(self)
+        }
+    }
+
+    impl Value for IntOpt {
+        fn value(&self) -> &OptValue {
+            &self.value
+        }
+
+        fn default_value(&self) -> &OptValue {
+            &self.default_value
+        }
+
+        fn set_value(&mut self, value_para: OptValue) {
+            self.value = value_para;
+        }
+
+        fn set_default_value(&mut self, default_value_para: OptValue) {
+            self.default_value = default_value_para;
+        }
+
+        fn parse_value(&self, value_para: &str) -> Result<OptValue> {
+            let value = OptValue::parse_int(value_para)?;
+
+            match self.validator.as_ref() {
+                Some(validator) => validator.validate(value_para, value),
+                None => Ok(value),
+            }
+        }
+
+        fn has_value(&self) -> bool {
+            self.value().is_int()
+        }
+
+        fn reset_value(&mut self) {
+            self.set_value(self.default_value().clone());
+        }
+    }
+
+    /// Default [`Utils`] implementation for [`IntOpt`].
+    #[derive(Debug)]
+    pub struct IntUtils;
+
+    impl IntUtils {
+        pub fn new() -> Self {
+            Self {}
+        }
+    }
+
+    impl Utils for IntUtils {
+        fn type_name(&self) -> &str {
+            current_type()
+        }
+
+        fn is_support_deactivate_style(&self) -> bool {
+            false
+        }
+
+        /// Create an [`IntOpt`] using option information [`CreateInfo`].
+        /// 
+        /// ```no_run
+        /// use getopt_rs::utils::{Utils, CreateInfo};
+        /// use getopt_rs::opt::int::*;
+        /// use getopt_rs::id::*;
+        /// 
+        /// let prefixs = vec![String::from("--")];
+        /// let utils = IntUtils::new();
+        /// let ci = CreateInfo::parse("--name=int!", &prefixs).unwrap();
+        /// let _opt = utils.create(Identifier::new(1), &ci);
+        /// ```
+        fn create(&self, id: IIdentifier, ci: &CreateInfo) -> Result<Box<dyn Opt>> {
+            if ci.is_deactivate_style() {
+                if ! self.is_support_deactivate_style() {
+                    return Err(Error::UtilsNotSupportDeactivateStyle(ci.get_name().to_owned()));
+                }
+            }
+            if ci.get_type_name() != self.type_name() {
+                return Err(Error::UtilsNotSupportTypeName(self.type_name().to_owned(), ci.get_type_name().to_owned()))
+            }
+            
+            assert_eq!(ci.get_type_name(), self.type_name());
+
+            let mut opt = Box::new(IntOpt::new(
+                id,
+                ci.get_name().to_owned(),
+                ci.get_prefix().to_owned(),
+                ci.is_optional(),
+                ci.get_default_value().clone_or(&None),
+                ci.get_help_info().clone(),
+            ));
+
+            opt.set_validator(ci.get_validator().cloned());
+
+            let alias = ci.get_alias();
+
+            if alias.len() > 0 {
+                for a in alias.iter() {
+                    opt.add_alias(&a.0, &a.1);
+                }
+            }
+
+            Ok(opt)
+        }
+
+        fn gen_info(&self, opt: &dyn Opt) -> Box<dyn Info> {
+            Box::new(OptionInfo::from_opt(opt))
+        }
+    }
+}
+
+pub mod uint {
+    use crate::opt::*;
+    use crate::id::Identifier as IIdentifier;
+
+    pub fn current_type() -> &'static str {
+        "uint"
+    }
+
+    pub trait Uint: Opt { }
+
+    /// UintOpt target the value to [`u64`], 
+    /// 
+    /// * The option type name is `uint`.
+    /// * The option is not support deactivate style.
+    /// * The option accept the style [`Style::Argument`].
+    /// * In default, the option is `optional`, it can be change through the [`set_optional`](crate::opt::Optional::set_optional).
+    /// * The option need an [`OptValue::Uint`] argument, the default value is [`OptValue::default()`].
+    /// * The option support multiple alias with different prefix and name.
+    /// * The option support callback type [`CallbackType::Value`].
+    ///
+    /// User can set it at `anywhere` of command line argument, using the string `-c 1`, `-c=2`, `--count 4`, `--count=8`, etc.
+    #[derive(Debug)]
+    pub struct UintOpt {
+        id: IIdentifier,
+
+        name: String,
+
+        prefix: String,
+
+        optional: bool,
+
+        value: OptValue,
+
+        default_value: OptValue,
+
+        alias: Vec<(String, String)>,
+
+        callback: CallbackType,
+
+        help: HelpInfo,
+
+        validator: Option<ValueValidator>,
+    }
+
+    impl UintOpt {
+        pub fn new(id: IIdentifier, name: String, prefix: String, optional: bool, default_value: OptValue, help: HelpInfo) -> Self {
+            Self {
+                id,
+                name,
+                prefix,
+                optional,
+                value: default_value.clone_or(&None),
+                default_value,
+                alias: vec![],
+                callback: CallbackType::Null,
+                help,
+                validator: None,
+            }
+        }
+
+        pub fn set_validator(&mut self, validator: Option<ValueValidator>) {
+            self.validator = validator;
+        }
+    }
+
+    opt_def!(UintOpt, Uint);
+
+    opt_type_def!(
+        UintOpt, 
+        current_type(),
+        false,
+        { style, Style::Argument }
+    );
+
+    opt_callback_def!(
+        UintOpt,
+        callback,
+        callback,
+        CallbackType::Value,
+        CallbackType::Null,
+        CallbackType::Null,
+        CallbackType::Value,
+        CallbackType::Transform
+    );
+
+    opt_identifier_def!(
+        UintOpt,
+        id,
+        para,
+    );
+
+    opt_name_def!(
+        UintOpt,
+        prefix,
+        name,
+        prefix,
+        name,
+    );
+
+    opt_optional_def!(
+        UintOpt,
         optional,
         optional,
     );
 
     opt_alias_def!(
-        IntOpt,
+        UintOpt,
         alias,
         prefix,
         name,
     );
 
-    opt_index_def!( IntOpt );
+    opt_index_def!( UintOpt );
 
-    impl Help for IntOpt {
+    impl Help for UintOpt {
         fn set_hint(&mut self, hint: &str) {
             self.help.hint = hint.to_owned()
         }
@@ -2554,7 +3750,7 @@ pub mod int {
         }
     }
 
-    impl Value for IntOpt {
+    impl Value for UintOpt {
         fn value(&self) -> &OptValue {
             &self.value
         }
@@ -2572,11 +3768,16 @@ pub mod int {
         }
 
         fn parse_value(&self, value_para: &str) -> Result<OptValue> {
-            return OptValue::parse_int(value_para);
+            let value = OptValue::parse_uint(value_para)?;
+
+            match self.validator.as_ref() {
+                Some(validator) => validator.validate(value_para, value),
+                None => Ok(value),
+            }
         }
 
         fn has_value(&self) -> bool {
-            self.value().is_int()
+            self.value().is_uint()
         }
 
         fn reset_value(&mut self) {
@@ -2584,17 +3785,17 @@ pub mod int {
         }
     }
 
-    /// Default [`Utils`] implementation for [`IntOpt`].
+    /// Default [`Utils`] implementation for [`UintOpt`].
     #[derive(Debug)]
-    pub struct IntUtils;
+    pub struct UintUtils;
 
-    impl IntUtils {
+    impl UintUtils {
         pub fn new() -> Self {
             Self {}
         }
     }
 
-    impl Utils for IntUtils {
+    impl Utils for UintUtils {
         fn type_name(&self) -> &str {
             current_type()
         }
@@ -2603,16 +3804,16 @@ pub mod int {
             false
         }
 
-        /// Create an [`IntOpt`] using option information [`CreateInfo`].
+        /// Create an [`UintOpt`] using option information [`CreateInfo`].
         /// 
         /// ```no_run
         /// use getopt_rs::utils::{Utils, CreateInfo};
-        /// use getopt_rs::opt::int::*;
+        /// use getopt_rs::opt::uint::*;
         /// use getopt_rs::id::*;
         /// 
         /// let prefixs = vec![String::from("--")];
-        /// let utils = IntUtils::new();
-        /// let ci = CreateInfo::parse("--name=int!", &prefixs).unwrap();
+        /// let utils = UintUtils::new();
+        /// let ci = CreateInfo::parse("--name=uint!", &prefixs).unwrap();
         /// let _opt = utils.create(Identifier::new(1), &ci);
         /// ```
         fn create(&self, id: IIdentifier, ci: &CreateInfo) -> Result<Box<dyn Opt>> {
@@ -2627,7 +3828,7 @@ pub mod int {
             
             assert_eq!(ci.get_type_name(), self.type_name());
 
-            let mut opt = Box::new(IntOpt::new(
+            let mut opt = Box::new(UintOpt::new(
                 id,
                 ci.get_name().to_owned(),
                 ci.get_prefix().to_owned(),
@@ -2636,6 +3837,8 @@ pub mod int {
                 ci.get_help_info().clone(),
             ));
 
+            opt.set_validator(ci.get_validator().cloned());
+
             let alias = ci.get_alias();
 
             if alias.len() > 0 {
@@ -2648,34 +3851,35 @@ pub mod int {
         }
 
         fn gen_info(&self, opt: &dyn Opt) -> Box<dyn Info> {
-            Box::new(OptionInfo::new(opt.id()))
+            Box::new(OptionInfo::from_opt(opt))
         }
     }
 }
 
-pub mod uint {
+pub mod flt {
     use crate::opt::*;
     use crate::id::Identifier as IIdentifier;
 
     pub fn current_type() -> &'static str {
-        "uint"
+        "flt"
     }
 
-    pub trait Uint: Opt { }
+    pub trait Flt: Opt { }
 
-    /// UintOpt target the value to [`u64`], 
+    /// FltOpt target the value to [`f64`], 
     /// 
-    /// * The option type name is `uint`.
+    /// * The option type name is `flt`.
     /// * The option is not support deactivate style.
     /// * The option accept the style [`Style::Argument`].
     /// * In default, the option is `optional`, it can be change through the [`set_optional`](crate::opt::Optional::set_optional).
-    /// * The option need an [`OptValue::Uint`] argument, the default value is [`OptValue::default()`].
+    /// * The option need an [`OptValue::Flt`] argument, the default value is [`OptValue::default()`].
     /// * The option support multiple alias with different prefix and name.
     /// * The option support callback type [`CallbackType::Value`].
     ///
-    /// User can set it at `anywhere` of command line argument, using the string `-c 1`, `-c=2`, `--count 4`, `--count=8`, etc.
+    /// User can set it at `anywhere` of command line argument, using the string `-c 1.1`, `-c -2.2`, `-c=2.4`, `--count 4.2`, `--count=8.8`, etc.
+    /// The `NaN` and infinity are rejected unless the option [`allow non-finite`](FltOpt::set_allow_non_finite) value.
     #[derive(Debug)]
-    pub struct UintOpt {
+    pub struct FltOpt {
         id: IIdentifier,
 
         name: String,
@@ -2693,9 +3897,13 @@ pub mod uint {
         callback: CallbackType,
 
         help: HelpInfo,
+
+        validator: Option<ValueValidator>,
+
+        allow_non_finite: bool,
     }
 
-    impl UintOpt {
+    impl FltOpt {
         pub fn new(id: IIdentifier, name: String, prefix: String, optional: bool, default_value: OptValue, help: HelpInfo) -> Self {
             Self {
                 id,
@@ -2707,37 +3915,53 @@ pub mod uint {
                 alias: vec![],
                 callback: CallbackType::Null,
                 help,
+                validator: None,
+                allow_non_finite: false,
             }
         }
+
+        pub fn set_validator(&mut self, validator: Option<ValueValidator>) {
+            self.validator = validator;
+        }
+
+        /// Accept the `NaN` and infinity value, such as `nan`, `inf` or `-inf`.
+        pub fn set_allow_non_finite(&mut self, allow_non_finite: bool) {
+            self.allow_non_finite = allow_non_finite;
+        }
+
+        pub fn is_allow_non_finite(&self) -> bool {
+            self.allow_non_finite
+        }
     }
 
-    opt_def!(UintOpt, Uint);
+    opt_def!(FltOpt, Flt);
 
     opt_type_def!(
-        UintOpt, 
+        FltOpt, 
         current_type(),
         false,
         { style, Style::Argument }
     );
 
     opt_callback_def!(
-        UintOpt,
+        FltOpt,
         callback,
         callback,
         CallbackType::Value,
         CallbackType::Null,
         CallbackType::Null,
-        CallbackType::Value
+        CallbackType::Value,
+        CallbackType::Transform
     );
 
     opt_identifier_def!(
-        UintOpt,
+        FltOpt,
         id,
         para,
     );
 
     opt_name_def!(
-        UintOpt,
+        FltOpt,
         prefix,
         name,
         prefix,
@@ -2745,21 +3969,21 @@ pub mod uint {
     );
 
     opt_optional_def!(
-        UintOpt,
+        FltOpt,
         optional,
         optional,
     );
 
     opt_alias_def!(
-        UintOpt,
+        FltOpt,
         alias,
         prefix,
         name,
     );
 
-    opt_index_def!( UintOpt );
+    opt_index_def!( FltOpt );
 
-    impl Help for UintOpt {
+    impl Help for FltOpt {
         fn set_hint(&mut self, hint: &str) {
             self.help.hint = hint.to_owned()
         }
@@ -2773,7 +3997,7 @@ pub mod uint {
         }
     }
 
-    impl Value for UintOpt {
+    impl Value for FltOpt {
         fn value(&self) -> &OptValue {
             &self.value
         }
@@ -2791,11 +4015,16 @@ pub mod uint {
         }
 
         fn parse_value(&self, value_para: &str) -> Result<OptValue> {
-            return OptValue::parse_uint(value_para);
+            let value = OptValue::parse_flt_with(value_para, self.allow_non_finite)?;
+
+            match self.validator.as_ref() {
+                Some(validator) => validator.validate(value_para, value),
+                None => Ok(value),
+            }
         }
 
         fn has_value(&self) -> bool {
-            self.value().is_uint()
+            self.value().is_flt()
         }
 
         fn reset_value(&mut self) {
@@ -2803,17 +4032,17 @@ pub mod uint {
         }
     }
 
-    /// Default [`Utils`] implementation for [`UintOpt`].
+    /// Default [`Utils`] implementation for [`FltOpt`].
     #[derive(Debug)]
-    pub struct UintUtils;
+    pub struct FltUtils;
 
-    impl UintUtils {
+    impl FltUtils {
         pub fn new() -> Self {
             Self {}
         }
     }
 
-    impl Utils for UintUtils {
+    impl Utils for FltUtils {
         fn type_name(&self) -> &str {
             current_type()
         }
@@ -2822,20 +4051,19 @@ pub mod uint {
             false
         }
 
-        /// Create an [`UintOpt`] using option information [`CreateInfo`].
+        /// Create an [`FltOpt`] using option information [`CreateInfo`].
         /// 
         /// ```no_run
         /// use getopt_rs::utils::{Utils, CreateInfo};
-        /// use getopt_rs::opt::uint::*;
+        /// use getopt_rs::opt::flt::*;
         /// use getopt_rs::id::*;
         /// 
         /// let prefixs = vec![String::from("--")];
-        /// let utils = UintUtils::new();
-        /// let ci = CreateInfo::parse("--name=uint!", &prefixs).unwrap();
+        /// let utils = FltUtils::new();
+        /// let ci = CreateInfo::parse("--name=flt!", &prefixs).unwrap();
         /// let _opt = utils.create(Identifier::new(1), &ci);
         /// ```
-        fn create(&self, id: IIdentifier, ci: &CreateInfo) -> Result<Box<dyn Opt>> {
-            if ci.is_deactivate_style() {
+        fn create(&self, id: IIdentifier, ci: &CreateInfo) -> Result<Box<dyn Opt>> {if ci.is_deactivate_style() {
                 if ! self.is_support_deactivate_style() {
                     return Err(Error::UtilsNotSupportDeactivateStyle(ci.get_name().to_owned()));
                 }
@@ -2846,7 +4074,8 @@ pub mod uint {
             
             assert_eq!(ci.get_type_name(), self.type_name());
 
-            let mut opt = Box::new(UintOpt::new(
+
+            let mut opt = Box::new(FltOpt::new(
                 id,
                 ci.get_name().to_owned(),
                 ci.get_prefix().to_owned(),
@@ -2855,6 +4084,9 @@ pub mod uint {
                 ci.get_help_info().clone(),
             ));
 
+            opt.set_validator(ci.get_validator().cloned());
+            opt.set_allow_non_finite(ci.is_allow_non_finite());
+
             let alias = ci.get_alias();
 
             if alias.len() > 0 {
@@ -2867,34 +4099,35 @@ pub mod uint {
         }
 
         fn gen_info(&self, opt: &dyn Opt) -> Box<dyn Info> {
-            Box::new(OptionInfo::new(opt.id()))
+            Box::new(OptionInfo::from_opt(opt))
         }
     }
 }
 
-pub mod flt {
+pub mod count {
     use crate::opt::*;
     use crate::id::Identifier as IIdentifier;
 
     pub fn current_type() -> &'static str {
-        "flt"
+        "count"
     }
 
-    pub trait Flt: Opt { }
+    pub trait Count: Opt { }
 
-    /// FltOpt target the value to [`f64`], 
+    /// CountOpt target the value to [`u64`], 
     /// 
-    /// * The option type name is `flt`.
+    /// * The option type name is `count`.
     /// * The option is not support deactivate style.
-    /// * The option accept the style [`Style::Argument`].
+    /// * The option accept the style [`Style::Boolean`] and [`Style::Multiple`].
     /// * In default, the option is `optional`, it can be change through the [`set_optional`](crate::opt::Optional::set_optional).
-    /// * The option need an [`OptValue::Flt`] argument, the default value is [`OptValue::default()`].
+    /// * The option need an [`OptValue::Uint`] argument, the default value is `0`.
     /// * The option support multiple alias with different prefix and name.
     /// * The option support callback type [`CallbackType::Value`].
     ///
-    /// User can set it at `anywhere` of command line argument, using the string `-c 1.1`, `-c -2.2`, `-c=2.4`, `--count 4.2`, `--count=8.8`, etc.
+    /// User can set it at `anywhere` of command line argument, using the string `-v`, `-vvv`, `--verbose --verbose`, etc.
+    /// Set value to `CountOpt` will add the value to it.
     #[derive(Debug)]
-    pub struct FltOpt {
+    pub struct CountOpt {
         id: IIdentifier,
 
         name: String,
@@ -2914,8 +4147,13 @@ pub mod flt {
         help: HelpInfo,
     }
 
-    impl FltOpt {
+    impl CountOpt {
         pub fn new(id: IIdentifier, name: String, prefix: String, optional: bool, default_value: OptValue, help: HelpInfo) -> Self {
+            let default_value = if default_value.is_uint() {
+                default_value
+            } else {
+                OptValue::from_uint(0u64)
+            };
             Self {
                 id,
                 name,
@@ -2930,33 +4168,34 @@ pub mod flt {
         }
     }
 
-    opt_def!(FltOpt, Flt);
+    opt_def!(CountOpt, Count);
 
     opt_type_def!(
-        FltOpt, 
+        CountOpt, 
         current_type(),
         false,
-        { style, Style::Argument }
+        { style, Style::Boolean, Style::Multiple }
     );
 
     opt_callback_def!(
-        FltOpt,
+        CountOpt,
         callback,
         callback,
         CallbackType::Value,
         CallbackType::Null,
         CallbackType::Null,
-        CallbackType::Value
+        CallbackType::Value,
+        CallbackType::Transform
     );
 
     opt_identifier_def!(
-        FltOpt,
+        CountOpt,
         id,
         para,
     );
 
     opt_name_def!(
-        FltOpt,
+        CountOpt,
         prefix,
         name,
         prefix,
@@ -2964,21 +4203,21 @@ pub mod flt {
     );
 
     opt_optional_def!(
-        FltOpt,
+        CountOpt,
         optional,
         optional,
     );
 
     opt_alias_def!(
-        FltOpt,
+        CountOpt,
         alias,
         prefix,
         name,
     );
 
-    opt_index_def!( FltOpt );
+    opt_index_def!( CountOpt );
 
-    impl Help for FltOpt {
+    impl Help for CountOpt {
         fn set_hint(&mut self, hint: &str) {
             self.help.hint = hint.to_owned()
         }
@@ -2992,7 +4231,7 @@ pub mod flt {
         }
     }
 
-    impl Value for FltOpt {
+    impl Value for CountOpt {
         fn value(&self) -> &OptValue {
             &self.value
         }
@@ -3001,38 +4240,54 @@ pub mod flt {
             &self.default_value
         }
 
+        /// WARNING! 
+        /// This function will add the `value` to option's value
         fn set_value(&mut self, value_para: OptValue) {
-            self.value = value_para;
+            if let Some(count) = value_para.as_uint() {
+                let current = *self.value.as_uint().unwrap_or(&0);
+
+                self.value = OptValue::from_uint(current + count);
+            }
         }
 
         fn set_default_value(&mut self, default_value_para: OptValue) {
             self.default_value = default_value_para;
         }
-
+        
         fn parse_value(&self, value_para: &str) -> Result<OptValue> {
-            return OptValue::parse_flt(value_para);
+            if ! value_para.is_empty() {
+                return Err(Error::InvaldOptionValue(value_para.to_owned(), String::from("count option not accept value")));
+            }
+            Ok(OptValue::from_uint(1u64))
         }
 
+        /// For [`CountOpt`], it need return true if current value is not equal default value
         fn has_value(&self) -> bool {
-            self.value().is_flt()
+            self.value().as_uint() != self.default_value().as_uint()
         }
 
         fn reset_value(&mut self) {
+            self.value = OptValue::default();
             self.set_value(self.default_value().clone());
         }
+
+        fn replace_value(&mut self, value_para: OptValue) {
+            self.value = OptValue::default();
+            self.set_value(value_para);
+        }
     }
 
-    /// Default [`Utils`] implementation for [`FltOpt`].
+    /// Default [`Utils`] implementation for [`CountOpt`].
     #[derive(Debug)]
-    pub struct FltUtils;
+    pub struct CountUtils;
 
-    impl FltUtils {
+    impl CountUtils {
         pub fn new() -> Self {
             Self {}
         }
     }
 
-    impl Utils for FltUtils {
+    impl Utils for CountUtils {
         fn type_name(&self) -> &str {
             current_type()
         }
@@ -3041,19 +4296,20 @@ pub mod flt {
             false
         }
 
-        /// Create an [`FltOpt`] using option information [`CreateInfo`].
+        /// Create an [`CountOpt`] using option information [`CreateInfo`].
         /// 
         /// ```no_run
         /// use getopt_rs::utils::{Utils, CreateInfo};
-        /// use getopt_rs::opt::flt::*;
+        /// use getopt_rs::opt::count::*;
         /// use getopt_rs::id::*;
         /// 
-        /// let prefixs = vec![String::from("--")];
-        /// let utils = FltUtils::new();
-        /// let ci = CreateInfo::parse("--name=flt!", &prefixs).unwrap();
+        /// let prefixs = vec![String::from("-")];
+        /// let utils = CountUtils::new();
+        /// let ci = CreateInfo::parse("-v=count", &prefixs).unwrap();
         /// let _opt = utils.create(Identifier::new(1), &ci);
         /// ```
-        fn create(&self, id: IIdentifier, ci: &CreateInfo) -> Result<Box<dyn Opt>> {if ci.is_deactivate_style() {
+        fn create(&self, id: IIdentifier, ci: &CreateInfo) -> Result<Box<dyn Opt>> {
+            if ci.is_deactivate_style() {
                 if ! self.is_support_deactivate_style() {
                     return Err(Error::UtilsNotSupportDeactivateStyle(ci.get_name().to_owned()));
                 }
@@ -3064,8 +4320,7 @@ pub mod flt {
             
             assert_eq!(ci.get_type_name(), self.type_name());
 
-
-            let mut opt = Box::new(FltOpt::new(
+            let mut opt = Box::new(CountOpt::new(
                 id,
                 ci.get_name().to_owned(),
                 ci.get_prefix().to_owned(),
@@ -3086,7 +4341,7 @@ pub mod flt {
         }
 
         fn gen_info(&self, opt: &dyn Opt) -> Box<dyn Info> {
-            Box::new(OptionInfo::new(opt.id()))
+            Box::new(OptionInfo::from_opt(opt))
         }
     }
 }
@@ -3155,7 +4410,8 @@ pub mod example {
         CallbackType::Value,
         CallbackType::Null,
         CallbackType::Null,
-        CallbackType::Value
+        CallbackType::Value,
+        CallbackType::Transform
     );
 
     opt_identifier_def!(
@@ -3222,9 +4478,9 @@ pub mod example {
             let pathbuf = PathBuf::from(value_para);
 
             if ! pathbuf.exists() {
-                return Err(Error::InvaldOptionValue(value_para.to_owned(), format!("the path is not eixst")));
+                return Err(Error::InvaldOptionValue(value_para.to_owned(), String::from("the path is not eixst")));
             }
-            return Ok(OptValue::from_any(Box::new(pathbuf)));
+            return Ok(OptValue::from_path(pathbuf));
         }
 
         fn has_value(&self) -> bool {
@@ -3266,21 +4522,18 @@ pub mod example {
             
             assert_eq!(ci.get_type_name(), self.type_name());
             
-            let clone_helper: CloneHelper = CloneHelper(Box::new(
-                |pathbuf: & dyn std::any::Any| {
-                    Box::new(pathbuf.downcast_ref::<PathBuf>().unwrap().clone())
-                }
-            ));
-
+            let clone_helper = self.clone_helper();
             let mut opt = Box::new(PathOpt::new(
                 id,
                 ci.get_name().to_owned(),
                 ci.get_prefix().to_owned(),
                 ci.is_optional(),
-                ci.get_default_value().clone_or(&Some(clone_helper)),
+                ci.get_default_value().clone_or(&clone_helper),
                 ci.get_help_info().clone(),
             ));
 
+            opt.set_value(opt.default_value().clone_or(&clone_helper));
+
             let alias = ci.get_alias();
 
             if alias.len() > 0 {
@@ -3293,7 +4546,15 @@ pub mod example {
         }
 
         fn gen_info(&self, opt: &dyn Opt) -> Box<dyn Info> {
-            Box::new(OptionInfo::new(opt.id()))
+            Box::new(OptionInfo::from_opt(opt))
+        }
+
+        fn clone_helper(&self) -> Option<CloneHelper> {
+            Some(CloneHelper::new(
+                |pathbuf: & dyn std::any::Any| {
+                    Box::new(pathbuf.downcast_ref::<PathBuf>().unwrap().clone())
+                }
+            ))
         }
     }
 }
@@ -3519,6 +4780,29 @@ mod tests {
         assert_eq!(opt.as_ref().as_any().is::<flt::FltOpt>(), true);
     }
 
+    #[test]
+    fn make_opt_type_flt_non_finite_work() {
+        let prefixs = vec!["--".to_owned()];
+        let flt_utils = flt::FltUtils::new();
+        let mut ci = CreateInfo::parse("--ratio=flt", &prefixs).unwrap();
+        let opt = flt_utils.create(IIdentifier::new(1), &ci).unwrap();
+
+        assert_eq!(opt.parse_value("1.5").unwrap(), OptValue::from_flt(1.5));
+        for value in ["NaN", "inf", "-inf", "infinity"].iter() {
+            assert!(matches!(opt.parse_value(value), Err(Error::InvaldOptionValue(v, _)) if &v == value));
+        }
+        assert!(OptValue::parse_flt("NaN").is_err());
+
+        ci.set_allow_non_finite(true);
+
+        let opt = flt_utils.create(IIdentifier::new(2), &ci).unwrap();
+
+        assert_eq!(opt.parse_value("1.5").unwrap(), OptValue::from_flt(1.5));
+        assert!(opt.parse_value("NaN").unwrap().as_flt().unwrap().is_nan());
+        assert_eq!(opt.parse_value("-inf").unwrap(), OptValue::from_flt(f64::NEG_INFINITY));
+        assert!(CreateInfo::from_opt(opt.as_ref()).is_allow_non_finite());
+    }
+
     #[test]
     fn make_opt_type_bool_work() {
         let prefixs = vec!["--".to_owned()];
@@ -3594,6 +4878,77 @@ mod tests {
         assert_eq!(opt.as_ref().as_any().is::<bool::BoolOpt>(), true);
     }
 
+    #[test]
+    fn make_opt_type_count_work() {
+        let prefixs = vec!["-".to_owned()];
+        let count_utils = count::CountUtils::new();
+
+        assert_eq!(count_utils.type_name(), count::current_type());
+        assert_eq!(count_utils.is_support_deactivate_style(), false);
+        assert!(CreateInfo::parse("-v=count/", &prefixs).and_then(|ci| count_utils.create(IIdentifier::new(1), &ci)).is_err());
+
+        let ci = CreateInfo::parse("-v=count", &prefixs).unwrap();
+        let mut opt = count_utils.create(IIdentifier::new(1), &ci).unwrap();
+
+        assert_eq!(opt.type_name(), "count");
+        assert_eq!(opt.is_style(Style::Boolean), true);
+        assert_eq!(opt.is_style(Style::Multiple), true);
+        assert_eq!(opt.is_style(Style::Argument), false);
+        assert_eq!(opt.value().as_uint(), Some(&0));
+        assert_eq!(opt.has_value(), false);
+
+        opt.set_value(opt.parse_value("").unwrap());
+        opt.set_value(opt.parse_value("").unwrap());
+        assert_eq!(opt.value().as_uint(), Some(&2));
+        assert_eq!(opt.has_value(), true);
+        opt.reset_value();
+        assert_eq!(opt.value().as_uint(), Some(&0));
+    }
+
+    #[test]
+    fn make_opt_type_map_work() {
+        let prefixs = vec!["--".to_owned()];
+        let map_utils = map::MapUtils::new();
+
+        assert_eq!(map_utils.type_name(), map::current_type());
+        assert_eq!(map_utils.is_support_deactivate_style(), false);
+
+        let ci = CreateInfo::parse("--labels=map", &prefixs).unwrap();
+        let mut opt = map_utils.create(IIdentifier::new(1), &ci).unwrap();
+
+        assert_eq!(opt.type_name(), "map");
+        assert_eq!(opt.is_style(Style::Argument), true);
+        assert_eq!(opt.has_value(), false);
+
+        opt.set_value(opt.parse_value("a=1,b=2").unwrap());
+        assert_eq!(opt.value().as_map().unwrap().get("a"), Some(&String::from("1")));
+        assert_eq!(opt.value().as_map().unwrap().get("b"), Some(&String::from("2")));
+        assert_eq!(opt.has_value(), true);
+
+        opt.set_value(opt.parse_value("b=3,c=").unwrap());
+        assert_eq!(opt.value().as_map().unwrap().len(), 3);
+        assert_eq!(opt.value().as_map().unwrap().get("b"), Some(&String::from("3")));
+        assert_eq!(opt.value().as_map().unwrap().get("c"), Some(&String::new()));
+
+        assert!(matches!(opt.parse_value("a"), Err(Error::InvaldOptionValue(_, _))));
+        assert!(matches!(opt.parse_value("b,a=1"), Err(Error::InvaldOptionValue(_, _))));
+        assert!(matches!(opt.parse_value("a=1,b"), Err(Error::InvaldOptionValue(value, reason)) if value == "a=1,b" && reason == "item `b` need a `=`"));
+        assert_eq!(opt.parse_value("a=1\\,b").unwrap().as_map().unwrap().get("a"), Some(&String::from("1,b")));
+
+        opt.reset_value();
+        assert_eq!(opt.has_value(), false);
+
+        let mut ci = CreateInfo::parse("--labels=map", &prefixs).unwrap();
+
+        ci.set_delimiter(' ');
+
+        let opt = map_utils.create(IIdentifier::new(2), &ci).unwrap();
+        let value = opt.parse_value("a=1 b=2\\ c").unwrap();
+
+        assert_eq!(value.as_map().unwrap().len(), 2);
+        assert_eq!(value.as_map().unwrap().get("b"), Some(&String::from("2 c")));
+    }
+
     #[test]
     fn make_opt_type_array_work() {
         let prefixs = vec!["--".to_owned()];
@@ -3684,6 +5039,7 @@ mod tests {
         make_optvalue_str_work();
         make_optvalue_null_work();
         make_optvalue_flt_work();
+        make_optvalue_flt_string_work();
         make_optvalue_bool_work();
         make_optvalue_arr_work();
         make_optvalue_any_work();
@@ -3786,6 +5142,17 @@ mod tests {
         }
     }
 
+    fn make_optvalue_flt_string_work() {
+        let value = OptValue::from_flt(2.5);
+
+        assert_eq!(value.as_flt_string(None), Some(String::from("2.5")));
+        assert_eq!(value.as_flt_string(Some(2)), Some(String::from("2.50")));
+        assert_eq!(value.as_flt_string(Some(0)), Some(String::from("2")));
+        assert_eq!(OptValue::from_flt(1.0).as_flt_string(None), Some(String::from("1")));
+        assert_eq!(OptValue::from_flt(1.23456).as_flt_string(Some(3)), Some(String::from("1.235")));
+        assert_eq!(OptValue::from_int(2).as_flt_string(Some(2)), None);
+    }
+
     fn make_optvalue_bool_work() {
         let mut value = OptValue::from_bool(true);
 
@@ -3833,4 +5200,175 @@ mod tests {
             assert!(! r);
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn make_optvalue_path_work() {
+        let value = OptValue::from_path("/usr/include");
+
+        assert!(value.is_any());
+        assert_eq!(value.as_path(), Some(std::path::Path::new("/usr/include")));
+        assert_eq!(value.downcast_ref::<std::path::PathBuf>(), Some(&std::path::PathBuf::from("/usr/include")));
+        assert_eq!(OptValue::from_any(Box::new(42i64)).as_path(), None);
+        assert_eq!(OptValue::from_str("/usr/include").as_path(), None);
+    }
+
+    #[test]
+    fn make_opt_value_lossy_work() {
+        assert_eq!(OptValue::from_uint(5u64).as_i64_lossy(), Some(5));
+        assert_eq!(OptValue::from_uint(u64::MAX).as_i64_lossy(), None);
+        assert_eq!(OptValue::from_flt(-2.7).as_i64_lossy(), Some(-2));
+        assert_eq!(OptValue::from_flt(1e19).as_i64_lossy(), None);
+        assert_eq!(OptValue::from_flt(f64::NAN).as_i64_lossy(), None);
+
+        assert_eq!(OptValue::from_flt(2.0).as_u64_lossy(), Some(2));
+        assert_eq!(OptValue::from_flt(-0.5).as_u64_lossy(), Some(0));
+        assert_eq!(OptValue::from_flt(-1.0).as_u64_lossy(), None);
+        assert_eq!(OptValue::from_flt(f64::INFINITY).as_u64_lossy(), None);
+        assert_eq!(OptValue::from_int(42).as_u64_lossy(), Some(42));
+        assert_eq!(OptValue::from_int(-1).as_u64_lossy(), None);
+
+        assert_eq!(OptValue::from_int(-3).as_f64_lossy(), Some(-3.0));
+        assert_eq!(OptValue::from_uint(7u64).as_f64_lossy(), Some(7.0));
+        assert_eq!(OptValue::from_flt(1.5).as_f64_lossy(), Some(1.5));
+        assert_eq!(OptValue::from_str("1").as_f64_lossy(), None);
+        assert_eq!(OptValue::null().as_i64_lossy(), None);
+    }
+
+    #[test]
+    fn make_opt_describe_work() {
+        let prefixs = vec!["--".to_owned(), "-".to_owned()];
+        let int_utils = int::IntUtils::new();
+
+        let ci = CreateInfo::parse("--count=int!", &prefixs).unwrap();
+        let mut opt = int_utils.create(IIdentifier::new(1), &ci).unwrap();
+
+        assert_eq!(opt.describe(), "--count=int!");
+        opt.add_alias("-", "c");
+        opt.add_alias("--", "cnt");
+        assert_eq!(opt.describe(), "--count=int! (aliases: -c, --cnt)");
+
+        let pos_utils = crate::nonopt::pos::PosUtils::new();
+
+        for (opt_str, desc) in [
+            ("file=pos!@1", "file=pos! @1"),
+            ("last=pos@-1", "last=pos @-1"),
+            ("files=pos@2..", "files=pos @2.."),
+        ].iter() {
+            let ci = CreateInfo::parse(opt_str, &vec![]).unwrap();
+            let nonopt = pos_utils.create(IIdentifier::new(2), &ci).unwrap();
+
+            assert_eq!(&nonopt.describe(), desc);
+        }
+    }
+
+    #[test]
+    fn make_optvalue_parse_radix_work() {
+        assert_eq!(OptValue::parse_uint("0xFF").unwrap().as_uint(), Some(&255));
+        assert_eq!(OptValue::parse_uint("0o755").unwrap().as_uint(), Some(&493));
+        assert_eq!(OptValue::parse_uint("0b1010").unwrap().as_uint(), Some(&10));
+        assert_eq!(OptValue::parse_uint("42").unwrap().as_uint(), Some(&42));
+        assert_eq!(OptValue::parse_int("0xff").unwrap().as_int(), Some(&255));
+        assert_eq!(OptValue::parse_int("-0x10").unwrap().as_int(), Some(&-16));
+        assert_eq!(OptValue::parse_int("-0b11").unwrap().as_int(), Some(&-3));
+        assert_eq!(OptValue::parse_int("-42").unwrap().as_int(), Some(&-42));
+        assert_eq!(OptValue::parse_int("+42").unwrap().as_int(), Some(&42));
+        assert!(matches!(OptValue::parse_uint("0xZZ"), Err(Error::InvaldOptionValue(value, _)) if value == "0xZZ"));
+        assert!(OptValue::parse_int("0xZZ").is_err());
+        assert!(matches!(OptValue::parse_int("0x-1"), Err(Error::InvaldOptionValue(_, msg)) if msg == "the sign is only allowed at the beginning"));
+        assert!(matches!(OptValue::parse_int("--1"), Err(Error::InvaldOptionValue(_, msg)) if msg == "the sign is only allowed at the beginning"));
+        assert!(OptValue::parse_int("0x").is_err());
+        assert!(matches!(OptValue::parse_uint("-0x1"), Err(Error::InvaldOptionValue(_, msg)) if msg == "negative value is not allowed"));
+        assert!(matches!(OptValue::parse_uint("0x+1"), Err(Error::InvaldOptionValue(_, msg)) if msg == "the sign is only allowed at the beginning"));
+        assert_eq!(OptValue::parse_uint("+0x1").unwrap().as_uint(), Some(&1));
+        assert!(OptValue::parse_uint("0o8").is_err());
+    }
+
+    #[test]
+    fn make_optvalue_storage_string_work() {
+        for value in [
+            OptValue::from_int(-42),
+            OptValue::from_int(i64::MIN),
+            OptValue::from_uint(u64::MAX),
+            OptValue::from_flt(0.1),
+            OptValue::from_flt(-1e300),
+            OptValue::from_flt(f64::INFINITY),
+            OptValue::from_str(""),
+            OptValue::from_str("a, b\\c"),
+            OptValue::from_bool(true),
+            OptValue::from_bool(false),
+        ].iter() {
+            let tag = value.storage_type_tag().unwrap();
+            let loaded = OptValue::from_storage_string(tag, &value.to_storage_string().unwrap()).unwrap();
+
+            assert_eq!(format!("{:?}", loaded), format!("{:?}", value));
+        }
+
+        for array in [
+            vec![],
+            vec![""],
+            vec!["a", "b"],
+            vec!["a,b", "c\\", "", "\\,d"],
+        ].iter() {
+            let value = OptValue::from_vec(array.iter().map(|v| v.to_string()).collect::<Vec<String>>());
+            let storage = value.to_storage_string().unwrap();
+            let loaded = OptValue::from_storage_string("array", &storage).unwrap();
+
+            assert_eq!(loaded.as_vec(), value.as_vec());
+        }
+        assert_eq!(OptValue::from_vec(vec![String::from("a,b"), String::from("c")]).to_storage_string(), Some(String::from("a\\,b,c,")));
+
+        let nan = OptValue::from_storage_string("flt", &OptValue::from_flt(f64::NAN).to_storage_string().unwrap()).unwrap();
+
+        assert!(nan.as_flt().unwrap().is_nan());
+        assert_eq!(OptValue::null().to_storage_string(), None);
+        assert_eq!(OptValue::from_any(Box::new(1i32)).to_storage_string(), None);
+        assert_eq!(OptValue::from_any(Box::new(1i32)).storage_type_tag(), None);
+        assert!(OptValue::from_storage_string("bool", "yes").is_err());
+        assert!(OptValue::from_storage_string("int", "1.5").is_err());
+        assert!(OptValue::from_storage_string("array", "a,b").is_err());
+        assert!(OptValue::from_storage_string("array", "a\\").is_err());
+        assert!(matches!(OptValue::from_storage_string("any", ""), Err(Error::InvalidOptionType(tag)) if tag == "any"));
+    }
+
+    #[test]
+    fn make_optvalue_merge_work() {
+        let mut value = OptValue::from_vec(vec![String::from("a")]);
+
+        value.merge(OptValue::from_vec(vec![String::from("b"), String::from("c")]));
+        assert_eq!(value.as_vec(), Some(&vec![String::from("a"), String::from("b"), String::from("c")]));
+
+        let mut value = OptValue::null();
+
+        value.merge(OptValue::from_int(42));
+        assert_eq!(value.as_int(), Some(&42));
+        value.merge(OptValue::from_int(7));
+        assert_eq!(value.as_int(), Some(&7));
+
+        // the null never override the value
+        let mut value = OptValue::from_str("foo");
+
+        value.merge(OptValue::null());
+        assert_eq!(value.as_str(), Some(&String::from("foo")));
+
+        let mut value = OptValue::from_any(Box::new(1i64));
+
+        value.merge(OptValue::from_any(Box::new(2i64)));
+        assert_eq!(value.downcast_ref::<i64>(), Some(&1));
+
+        let clone_helper = Some(CloneHelper::new(|v| Box::new(*v.downcast_ref::<i64>().unwrap())));
+
+        value.merge_or(OptValue::from_any(Box::new(2i64)), &clone_helper);
+        assert_eq!(value.downcast_ref::<i64>(), Some(&2));
+    }
+
+    #[test]
+    fn make_opt_style_name_work() {
+        for style in [Style::Boolean, Style::Argument, Style::Multiple, Style::Pos, Style::Cmd, Style::Main].iter() {
+            assert_eq!(Style::from_name(&style.to_string()).as_ref(), Some(style));
+        }
+        assert_eq!(Style::Null.to_string(), "null");
+        assert_eq!(Style::Boolean.to_string(), "boolean");
+        assert_eq!(Style::from_name("Boolean"), None);
+        assert_eq!(Style::from_name(""), None);
+    }
+}
@@ -1,15 +1,21 @@
 
-use crate::ctx::{Context, OptContext, NonOptContext, DelayContext};
-use crate::proc::{Info, Proc, Publisher, SequenceProc, SingleCtxProc};
+use crate::ctx::{Context, OptContext, NonOptContext, DelayContext, TypeContext};
+use crate::proc::{Info, InfoIndex, Proc, Publisher, SequenceProc, SingleCtxProc};
 use crate::opt::{Opt, Style, OptValue};
-use crate::callback::{OptCallback, CallbackType};
+use crate::callback::{OptCallback, CallbackType, ValueTransform, UnknownHandler, UnknownHandlerFn};
 use crate::id::{Identifier, IdGenerator};
-use crate::arg::{IndexIterator, Argument};
+use crate::arg::{ArgIterator, IndexIterator, Argument, is_terminator, is_option_name, parse_argument_impl, take_left_arguments, TERMINATOR};
 use crate::error::{Error, Result};
 use crate::set::Set;
+use crate::rule::{GroupRule, ValidationReport};
+use crate::trace::{ParseTrace, MatchRecorder};
+#[cfg(feature="profile")]
+use crate::trace::ProfileReport;
 
 use std::fmt::Debug;
 use std::collections::HashMap;
+#[cfg(not(feature="async"))]
+use std::collections::VecDeque;
 use async_trait::async_trait;
 
 /// Parser will parse the given argument, generate the [`Context`] and publish it to [`Publisher`].
@@ -109,6 +115,58 @@ pub trait Parser<S, G>: Debug + Publisher<Box<dyn Proc>>
     /// Set the callback of option.
     fn set_callback(&mut self, id: Identifier, callback: OptCallback);
 
+    /// Append a value transform of option, the transforms will be called in registration order
+    /// on the parsed value of each occurrence before it stored.
+    fn add_transform(&mut self, id: Identifier, transform: ValueTransform);
+
+    /// Add a [`GroupRule`] between options.
+    fn add_rule(&mut self, rule: GroupRule);
+
+    /// Add a group of options, at most one of them can be matched on command line.
+    /// It will be checked in [`check_other`](Parser::check_other).
+    fn add_exclusive_group(&mut self, ids: &[Identifier]);
+
+    /// The option `id` requires `dependency` matched on command line if it matched.
+    /// It will be checked in [`check_other`](Parser::check_other).
+    fn add_requires(&mut self, id: Identifier, dependency: Identifier);
+
+    /// Evaluate all the [`GroupRule`] and return the report.
+    /// The report is empty if no [`Set`] published to the parser.
+    fn validation_report(&self) -> ValidationReport;
+
+    /// Return the [`ParseTrace`] of last parse.
+    fn trace(&self) -> ParseTrace;
+
+    /// Return how many times the option matched in last parse.
+    fn occurrence(&self, id: Identifier) -> u64;
+
+    /// Return the last option matched in last parse, the non-option is not included.
+    fn last_matched_option(&self) -> Option<Identifier>;
+
+    /// Set the maximum number of non-option arguments, `None` means no limit.
+    fn set_max_positionals(&mut self, max: Option<usize>);
+
+    /// Set the handler called with the argument look like an option but not matched any option,
+    /// see [`UnknownHandler`].
+    fn set_unknown_handler(&mut self, handler: Box<UnknownHandlerFn>);
+
+    /// Enable or disable the strict mode, in strict mode the argument look like an option but not matched
+    /// any option, such as `-z`, will cause [`Error::UnknownOption`] if the unknown handler not suppress it.
+    /// The argument which name is not an option name, such as `/etc/passwd`, is always a non-option argument,
+    /// see [`is_option_name`].
+    fn set_strict(&mut self, strict: bool);
+
+    /// Enable or disable the `${name}` placeholder interpolation after parsing,
+    /// it is disabled by default, see [`interpolate_values`].
+    fn set_interpolate(&mut self, interpolate: bool);
+
+    /// Enable or disable the audit in [`check_other`](Parser::check_other), see [`audit`](Parser::audit).
+    fn set_audit(&mut self, audit: bool);
+
+    /// Log the options has no callback and not matched in last parse at `warn` level,
+    /// such option may be a typo of option name. Return the name of the options.
+    fn audit(&self) -> Vec<String>;
+
     /// Get the [`Set`] of parser.
     fn set(&self) -> &Option<S>;
 
@@ -131,7 +189,151 @@ pub trait Parser<S, G>: Debug + Publisher<Box<dyn Proc>>
     /// Check and make sure the other things valid.
     fn check_other(&self) -> Result<bool>;
 
-    fn reset(&mut self);
+    /// Reset the state of previous parse, see [`reset_values`](Parser::reset_values).
+    fn reset(&mut self) {
+        self.reset_values();
+    }
+
+    /// Reset the value of every option to default and clear the non-option argument,
+    /// but keep the callbacks and other state of parser.
+    /// The option is reset through [`Set::reset`], so the raw tokens recorded by set are cleared too.
+    fn reset_values(&mut self);
+
+    /// Set the invoke flag of every option has callback, so the callback will be invoked when it matched in next parse.
+    fn rearm_callbacks(&mut self);
+
+    /// Return the timing metrics of the [`Proc`] processed by parser.
+    #[cfg(feature="profile")]
+    fn profile_report(&self) -> ProfileReport;
+
+    /// Return the option names and alias with prefix which start with `partial`, such as `--count` for `--co`.
+    /// The non-option such as positional and command are not included.
+    fn complete(&self, partial: &str) -> Vec<String> {
+        let mut ret: Vec<String> = vec![];
+
+        if let Some(set) = self.set() {
+            for opt in set.iter() {
+                if opt.is_style(Style::Boolean) || opt.is_style(Style::Argument) || opt.is_style(Style::Multiple) {
+                    let mut names = vec![format!("{}{}", opt.prefix(), opt.name())];
+
+                    if let Some(alias) = opt.alias() {
+                        names.extend(alias.iter().map(|(prefix, name)| format!("{}{}", prefix, name)));
+                    }
+                    for name in names {
+                        if name.starts_with(partial) && ! ret.contains(&name) {
+                            ret.push(name);
+                        }
+                    }
+                }
+            }
+        }
+        ret
+    }
+
+    /// Copy the value of options matched in last parse to the option has same prefix and name in `target`,
+    /// such as merge the global options into the set of subcommand. Return the number of options copied.
+    /// The non-option such as positional and command are not included.
+    fn merge_results_into(&self, target: &mut dyn Set) -> usize {
+        let mut count = 0;
+
+        if let Some(set) = self.set() {
+            for opt in set.iter() {
+                if (opt.is_style(Style::Boolean) || opt.is_style(Style::Argument) || opt.is_style(Style::Multiple))
+                && self.occurrence(opt.id()) > 0 {
+                    if let Some(target_opt) = target.get_opt_mut_by_name(opt.prefix(), opt.name()) {
+                        target_opt.replace_value(opt.value().clone());
+                        count += 1;
+                    }
+                }
+            }
+        }
+        count
+    }
+
+    /// Set the value of options without parsing the command line, such as inject values in test.
+    /// The [`OptValue::Str`] value is parsed by the [`parse_value`](crate::opt::Value::parse_value) of option,
+    /// so `(id, OptValue::from_str("42"))` set an `int` option to 42.
+    /// The value replace the current value of option, it is not accumulated by the array, map and count option.
+    /// Return [`Error::InvaldOptionId`] if the option not exists.
+    fn set_values(&mut self, values: &[(Identifier, OptValue)]) -> Result<()> {
+        for (id, value) in values {
+            let opt = self.get_opt_mut(*id).ok_or_else(|| Error::InvaldOptionId(format!("{:?}", id)))?;
+            let value = match value {
+                OptValue::Str(raw) => opt.parse_value(raw)?,
+                _ => value.clone(),
+            };
+
+            opt.replace_value(value);
+        }
+        Ok(())
+    }
+
+    /// Return the canonical command line of current parse result, see [`canonical_command_line`].
+    fn canonical_command_line(&self) -> Vec<String> {
+        match self.set() {
+            Some(set) => canonical_command_line(set, self.noa()),
+            None => vec![],
+        }
+    }
+
+    /// Return the parse result as JSON, it is the programmatic counterpart to [`canonical_command_line`](Parser::canonical_command_line).
+    /// The result has the options has value and the non-option arguments,
+    /// such as `{"options":[{"name":"--count","type":"int","value":42,"occurrence":1}],"positionals":["a"]}`.
+    #[cfg(feature="serde")]
+    fn result_json(&self) -> String {
+        let mut options = vec![];
+
+        if let Some(set) = self.set() {
+            for opt in set.iter() {
+                if (opt.is_style(Style::Boolean) || opt.is_style(Style::Argument) || opt.is_style(Style::Multiple))
+                && opt.has_value() {
+                    options.push(serde_json::json!({
+                        "name": format!("{}{}", opt.prefix(), opt.name()),
+                        "type": opt.type_name(),
+                        "value": opt_value_to_json(opt.value()),
+                        "occurrence": self.occurrence(opt.id()),
+                    }));
+                }
+            }
+        }
+        serde_json::json!({
+            "options": options,
+            "positionals": self.noa(),
+        }).to_string()
+    }
+
+    /// Return the value of option `id`, or an error if the value is not [`OptValue::Int`].
+    fn value_of_int(&self, id: Identifier) -> Result<i64> {
+        value_of(self.get_opt(id), id, "int", |v| v.as_int().cloned())
+    }
+
+    /// Return the value of option `id`, or an error if the value is not [`OptValue::Uint`].
+    fn value_of_uint(&self, id: Identifier) -> Result<u64> {
+        value_of(self.get_opt(id), id, "uint", |v| v.as_uint().cloned())
+    }
+
+    /// Return the value of option `id`, or an error if the value is not [`OptValue::Flt`].
+    fn value_of_flt(&self, id: Identifier) -> Result<f64> {
+        value_of(self.get_opt(id), id, "flt", |v| v.as_flt().cloned())
+    }
+
+    /// Return the value of option `id`, or an error if the value is not [`OptValue::Str`].
+    fn value_of_str(&self, id: Identifier) -> Result<&String> {
+        value_of(self.get_opt(id), id, "str", |v| v.as_str())
+    }
+
+    /// Return the value of option `id`, or an error if the value is not [`OptValue::Bool`].
+    fn value_of_bool(&self, id: Identifier) -> Result<bool> {
+        value_of(self.get_opt(id), id, "bool", |v| v.as_bool().cloned())
+    }
+}
+
+/// Return [`Error::InvaldOptionId`] if the `opt` not exist,
+/// or [`Error::MismatchValueType`] if `f` can not get the value.
+fn value_of<'a, T>(opt: Option<&'a dyn Opt>, id: Identifier, type_name: &str, f: impl Fn(&'a OptValue) -> Option<T>) -> Result<T> {
+    let opt = opt.ok_or_else(|| Error::InvaldOptionId(format!("{:?}", id)))?;
+
+    f(opt.value()).ok_or_else(|| Error::MismatchValueType(format!("{}{}", opt.prefix(), opt.name()), type_name.to_owned()))
 }
 
 /// ForwardParser will generate and publish the [`Context`] with order 
@@ -139,8 +341,8 @@ pub trait Parser<S, G>: Debug + Publisher<Box<dyn Proc>>
 /// * GenStyle::GS_Equal_With_Value
 /// * GenStyle::GS_Argument
 /// * GenStyle::GS_Boolean
-/// * GenStyle::GS_Mutliple_Option
 /// * GenStyle::GS_Embedded_Value
+/// * GenStyle::GS_Mutliple_Option
 /// 
 /// The [`Context`] will set the value if any option matched.
 /// Parser will call the [`Parser::check_opt`] do option check after the option processed.
@@ -148,13 +350,20 @@ pub trait Parser<S, G>: Debug + Publisher<Box<dyn Proc>>
 /// call the [`Parser::check_nonopt`] do non-option check.
 /// In last, the parser will publish GenStyle::GS_Non_Main non-option,
 /// and call [`Parser::check_other`] do other thing check.
+/// 
+/// If the first non-option argument is a command has [`subparser`](ForwardParser::add_subparser),
+/// the arguments after it are parsed by the subparser.
 #[derive(Debug)]
 pub struct ForwardParser<S, G>
     where S: Set, G: IdGenerator {
     msg_id_gen: G,
 
+    subparsers: HashMap<String, Box<dyn Parser<S, G>>>,
+
     cached_infos: Vec<Box<dyn Info>>,
 
+    info_index: InfoIndex,
+
     noa: Vec<String>,
 
     set: Option<S>,
@@ -162,6 +371,28 @@ pub struct ForwardParser<S, G>
     argument_matched: bool,
 
     callbacks: HashMap<Identifier, OptCallback>,
+
+    transforms: HashMap<Identifier, Vec<ValueTransform>>,
+
+    rules: Vec<GroupRule>,
+
+    exclusive_groups: Vec<Vec<Identifier>>,
+
+    requires: HashMap<Identifier, Vec<Identifier>>,
+
+    record: MatchRecorder,
+
+    max_positionals: Option<usize>,
+
+    unknown_handler: Option<UnknownHandler>,
+
+    audit: bool,
+
+    strict: bool,
+
+    interpolate: bool,
+
+    matched_ids: Vec<Identifier>,
 }
 
 impl<S, G> ForwardParser<S, G>
@@ -169,158 +400,247 @@ impl<S, G> ForwardParser<S, G>
     pub fn new(msg_id_gen: G) -> Self {
         Self {
             msg_id_gen: msg_id_gen,
+            subparsers: HashMap::new(),
             cached_infos: vec![],
+            info_index: InfoIndex::new(),
             noa: vec![],
             set: None,
             argument_matched: false,
             callbacks: HashMap::new(),
+            transforms: HashMap::new(),
+            rules: vec![],
+            exclusive_groups: vec![],
+            requires: HashMap::new(),
+            record: MatchRecorder::new(),
+            max_positionals: None,
+            unknown_handler: None,
+            audit: false,
+            strict: false,
+            interpolate: false,
+            matched_ids: vec![],
         }
     }
 
-    pub fn set_argument_matched(&mut self) {
-        self.argument_matched = true;
+    /// Add the parser of subcommand `cmd`, it will parse the arguments after the command.
+    /// The command is the first non-option argument, it should be matched by a `cmd` non-option of current set.
+    pub fn add_subparser(&mut self, cmd: &str, parser: Box<dyn Parser<S, G>>) {
+        self.subparsers.insert(cmd.to_owned(), parser);
     }
 
-    pub fn get_prefix(&self) -> &Vec<String> {
-        self.set.as_ref().unwrap().get_prefix()
+    pub fn get_subparser(&self, cmd: &str) -> Option<&dyn Parser<S, G>> {
+        self.subparsers.get(cmd).map(|parser| parser.as_ref())
     }
 
+    pub fn get_subparser_mut(&mut self, cmd: &str) -> Option<&mut (dyn Parser<S, G> + 'static)> {
+        self.subparsers.get_mut(cmd).map(|parser| parser.as_mut())
+    }
+
+    /// Return the subcommand if current argument is the first non-option argument and it has subparser.
+    fn current_subcommand(&self, iter: &dyn IndexIterator, like_opt: bool, unmatched_opts: &[usize]) -> Option<String> {
+        if like_opt || self.noa.len() > unmatched_opts.len() {
+            return None;
+        }
+        iter.current().as_ref().filter(|arg| self.subparsers.contains_key(arg.as_str())).cloned()
+    }
+
+    /// Return an [`ArgIterator`] of the arguments after current argument.
+    fn left_arguments(iter: &mut dyn IndexIterator) -> ArgIterator {
+        ArgIterator::from_args(&mut take_left_arguments(iter).into_iter())
+    }
+
+    /// Return a [`ParseStream`] process the arguments of `iter` lazily,
+    /// the options are processed only when the stream is advanced.
     #[cfg(not(feature="async"))]
-    pub fn invoke_callback(&mut self, id: &Identifier, callback_type: CallbackType, index: u64) -> Result<bool> {
-        let opt = self.set.as_mut().unwrap().get_opt_mut(id.clone()).unwrap();
+    pub fn into_stream(self, iter: &mut dyn IndexIterator) -> ParseStream<'_, S, G> {
+        ParseStream::new(self, iter)
+    }
 
-        if let Some(callback) = self.callbacks.get_mut(id) {
-            debug!("!!!! Calling callback of {:?}", opt);
-            match callback_type {
-                CallbackType::Value => {
-                    callback.call_value(opt)?;
-                }
-                CallbackType::Index => {
-                    let length = self.noa.len();
-                    let index = opt.index().calc_index(length as u64, index);
+    /// Process the current argument of `iter`, return the events generated.
+    #[cfg(not(feature="async"))]
+    fn parse_arg(&mut self, iter: &mut dyn IndexIterator, unmatched_opts: &mut Vec<usize>) -> Result<Vec<ParseEvent>> {
+        let opt_order = [
+            GenStyle::GS_Equal_With_Value,
+            GenStyle::GS_Argument,
+            GenStyle::GS_Boolean,
+            GenStyle::GS_Embedded_Value,
+            GenStyle::GS_Mutliple_Option,
+        ];
+        let mut events = vec![];
+        let mut matched = false;
+        let mut like_opt = false;
 
-                    println!("--> {:?} and {:?}", index, opt);
-                    if let Some(index) = index {
-                        let ret = callback.call_index(self.set.as_ref().unwrap(), &self.noa[index as usize - 1])?;
-                        // can we fix this long call?
-                        self.set.as_mut().unwrap().get_opt_mut(id.clone()).unwrap().set_value(OptValue::from_bool(ret));
+        iter.fill_current_and_next();
+        self.argument_matched = false;
+        self.matched_ids.clear();
+        debug!("**** ArgIterator [{:?}, {:?}]", iter.current(), iter.next());
+
+        // the standalone `--` terminate the option processing
+        if is_terminator(iter.current()) {
+            debug!("---- In ForwardParser, got terminator, left arguments are non-option");
+            for arg in take_left_arguments(iter) {
+                self.noa.push(arg.clone());
+                events.push(ParseEvent::NonOption(arg));
+            }
+            return Ok(events);
+        }
+
+        if let Ok(mut arg) = iter.parse(self.get_prefix()) {
+            let group = resolve_argument(self.set.as_ref().unwrap(), &mut arg, false)?;
+            debug!("parse ... {:?}", arg);
+            like_opt = is_option_name(&arg);
+            for opt_style in &opt_order {
+                let multiple_ctx = opt_style.gen_opt_with_group(&arg, iter.next(), group);
+
+                if multiple_ctx.len() > 0 {
+                    let mut cp = Box::new(SequenceProc::from(self.msg_id_gen.next_id()));
+
+                    for ctx in multiple_ctx {
+                        cp.app_ctx(ctx);
+                    }
+
+                    self.record.gen_style = Some(format!("{:?}", opt_style));
+                    self.record.gen_tokens = iter.current().iter().chain(iter.next().iter()).cloned().collect();
+                    matched = catch_missing_argument(self.publish(cp), iter.next().is_none(), &mut self.record.missing_argument)?;
+
+                    // the first matched style wins, skip the other styles
+                    if matched {
+                        break;
                     }
                 }
-                CallbackType::Main => {
-                    let ret = callback.call_main(self.set.as_ref().unwrap(), &self.noa)?;
-                    // can we fix this long call?
-                    self.set.as_mut().unwrap().get_opt_mut(id.clone()).unwrap().set_value(OptValue::from_bool(ret));
-                }
-                _ => { }
             }
         }
-        Ok(true)
-    }
-
-    #[cfg(feature="async")]
-    pub async fn invoke_callback(&mut self, id: &Identifier, callback_type: CallbackType, index: u64) -> Result<bool> {
-        let opt = self.set.as_mut().unwrap().get_opt_mut(id.clone()).unwrap();
 
-        if let Some(callback) = self.callbacks.get_mut(id) {
-            debug!("!!!! Calling callback of {:?}", id);
-            match callback_type {
-                CallbackType::Value => {
-                    callback.call_value(opt).await?;
+        // If next argument matched, skip it
+        if matched && self.argument_matched {
+            iter.skip();
+        }
+        if matched {
+            for id in self.matched_ids.iter() {
+                if let Some(opt) = self.set.as_ref().unwrap().get_opt(*id) {
+                    events.push(ParseEvent::OptionMatched { id: *id, value: opt.value().clone() });
                 }
-                CallbackType::Index => {
-                    let length = self.noa.len();
-                    let index = opt.index().calc_index(length as u64, index);
+            }
+        }
+        else {
+            if let Some(arg) = iter.current() {
+                let suppressed = parser_default_unknown_check(self.unknown_handler.as_mut(), self.strict, like_opt, arg)?;
 
-                    if let Some(index) = index {
-                        let ret = callback.call_index(self.set.as_ref().unwrap(), &self.noa[index as usize - 1]).await?;
-                        // can we fix this long call?
-                        self.set.as_mut().unwrap().get_opt_mut(id.clone()).unwrap().set_value(OptValue::from_bool(ret));
+                if ! suppressed {
+                    if like_opt {
+                        unmatched_opts.push(self.noa.len());
                     }
+                    self.noa.push(arg.clone());
+                    events.push(ParseEvent::NonOption(arg.clone()));
                 }
-                CallbackType::Main => {
-                    let ret = callback.call_main(self.set.as_ref().unwrap(), &self.noa).await?;
-                    // can we fix this long call?
-                    self.set.as_mut().unwrap().get_opt_mut(id.clone()).unwrap().set_value(OptValue::from_bool(ret));
-                }
-                _ => { }
             }
         }
-        Ok(true)
-    }
-}
 
-impl<S, G> Default for ForwardParser<S, G>
-    where S: Set, G: IdGenerator {
-    fn default() -> Self {
-        Self::new(G::default())
+        iter.skip();
+        Ok(events)
     }
-}
 
-#[async_trait(?Send)]
-impl<S, G> Parser<S, G> for ForwardParser<S, G>
-    where S: Set, G: IdGenerator {
-    #[cfg(not(feature="async"))]
-    fn parse(&mut self, iter: &mut dyn IndexIterator) -> Result<Option<bool>> {
-        if self.set.is_none() {
-            return Ok(None);
-        }
+    /// Process the current argument of `iter`, return the events generated.
+    #[cfg(feature="async")]
+    async fn parse_arg(&mut self, iter: &mut dyn IndexIterator, unmatched_opts: &mut Vec<usize>) -> Result<Vec<ParseEvent>> {
         let opt_order = [
             GenStyle::GS_Equal_With_Value,
             GenStyle::GS_Argument,
             GenStyle::GS_Boolean,
-            GenStyle::GS_Mutliple_Option,
             GenStyle::GS_Embedded_Value,
+            GenStyle::GS_Mutliple_Option,
         ];
+        let mut events = vec![];
+        let mut matched = false;
+        let mut like_opt = false;
 
-        self.pre_check()?;
-        debug!("---- In ForwardParser, start process option");
-        while ! iter.reach_end() {
-            let mut matched = false;
+        iter.fill_current_and_next();
+        self.argument_matched = false;
+        self.matched_ids.clear();
+        debug!("**** ArgIterator [{:?}, {:?}]", iter.current(), iter.next());
+
+        // the standalone `--` terminate the option processing
+        if is_terminator(iter.current()) {
+            debug!("---- In ForwardParser, got terminator, left arguments are non-option");
+            for arg in take_left_arguments(iter) {
+                self.noa.push(arg.clone());
+                events.push(ParseEvent::NonOption(arg));
+            }
+            return Ok(events);
+        }
 
-            iter.fill_current_and_next();
-            self.argument_matched = false;
-            debug!("**** ArgIterator [{:?}, {:?}]", iter.current(), iter.next());
+        if let Ok(mut arg) = iter.parse(self.get_prefix()).await {
+            let group = resolve_argument(self.set.as_ref().unwrap(), &mut arg, false)?;
+            debug!("parse ... {:?}", arg);
+            like_opt = is_option_name(&arg);
+            for opt_style in &opt_order {
+                let multiple_ctx = opt_style.gen_opt_with_group(&arg, iter.next(), group);
 
-            if let Ok(arg) = iter.parse(self.get_prefix()) {
-                debug!("parse ... {:?}", arg);
-                for opt_style in &opt_order {
-                    if ! matched {
-                        let multiple_ctx = opt_style.gen_opt(&arg, iter.next());
+                if multiple_ctx.len() > 0 {
+                    let mut cp = Box::new(SequenceProc::from(self.msg_id_gen.next_id()));
 
-                        if multiple_ctx.len() > 0 {
-                            let mut cp = Box::new(SequenceProc::from(self.msg_id_gen.next_id()));
+                    for ctx in multiple_ctx {
+                        cp.app_ctx(ctx);
+                    }
 
-                            for ctx in multiple_ctx {
-                                cp.app_ctx(ctx);
-                            }
+                    self.record.gen_style = Some(format!("{:?}", opt_style));
+                    self.record.gen_tokens = iter.current().iter().chain(iter.next().iter()).cloned().collect();
+                    matched = catch_missing_argument(self.publish(cp).await, iter.next().is_none(), &mut self.record.missing_argument)?;
 
-                            matched = self.publish(cp)?;
-                        }
+                    // the first matched style wins, skip the other styles
+                    if matched {
+                        break;
                     }
                 }
             }
+        }
 
-            // If next argument matched, skip it
-            if matched && self.argument_matched {
-                iter.skip();
+        // If next argument matched, skip it
+        if matched && self.argument_matched {
+            iter.skip();
+        }
+        if matched {
+            for id in self.matched_ids.iter() {
+                if let Some(opt) = self.set.as_ref().unwrap().get_opt(*id) {
+                    events.push(ParseEvent::OptionMatched { id: *id, value: opt.value().clone() });
+                }
             }
-            if !matched {
-                if let Some(arg) = iter.current() {
+        }
+        else {
+            if let Some(arg) = iter.current() {
+                let suppressed = parser_default_unknown_check(self.unknown_handler.as_mut(), self.strict, like_opt, arg)?;
+
+                if ! suppressed {
+                    if like_opt {
+                        unmatched_opts.push(self.noa.len());
+                    }
                     self.noa.push(arg.clone());
+                    events.push(ParseEvent::NonOption(arg.clone()));
                 }
             }
-
-            iter.skip();
         }
 
+        iter.skip();
+        Ok(events)
+    }
+
+    /// Process the non-option arguments after all the options processed, and check the result.
+    #[cfg(not(feature="async"))]
+    fn parse_nonopt(&mut self, unmatched_opts: &[usize]) -> Result<bool> {
+        apply_env_values(self.set.as_mut().unwrap())?;
+        if self.interpolate {
+            interpolate_values(self.set.as_mut().unwrap())?;
+        }
         self.check_opt()?;
+        check_max_positionals(self.max_positionals, self.noa.len())?;
 
         let noa_total = self.noa().len();
+        // the command is the first non-option argument which is not an unmatched option
+        let cmd_index = (0 .. noa_total).find(|index| ! unmatched_opts.contains(index)).unwrap_or(0);
 
         // process cmd and pos
         if noa_total > 0 {
             debug!("---- In ForwardParser, start process {:?}", GenStyle::GS_Non_Cmd);
-            let non_opt_cmd = GenStyle::GS_Non_Cmd.gen_nonopt(&self.noa()[0], noa_total as u64, 1);
+            let non_opt_cmd = GenStyle::GS_Non_Cmd.gen_nonopt(&self.noa()[cmd_index], noa_total as u64, 1);
 
             if non_opt_cmd.len() > 0 {
                 let mut cp = Box::new(SingleCtxProc::from(self.msg_id_gen.next_id()));
@@ -360,73 +680,27 @@ impl<S, G> Parser<S, G> for ForwardParser<S, G>
             self.publish(cp)?;
         }
 
-        self.check_other()?;
-
-        Ok(Some(true))
+        self.check_other()
     }
 
+    /// Process the non-option arguments after all the options processed, and check the result.
     #[cfg(feature="async")]
-    async fn parse(&mut self, iter: &mut dyn IndexIterator) -> Result<Option<bool>> {
-        if self.set.is_none() {
-            return Ok(None);
-        }
-        let opt_order = [
-            GenStyle::GS_Equal_With_Value,
-            GenStyle::GS_Argument,
-            GenStyle::GS_Boolean,
-            GenStyle::GS_Mutliple_Option,
-            GenStyle::GS_Embedded_Value,
-        ];
-
-        self.pre_check().await?;
-        debug!("---- In ForwardParser, start process option");
-        while ! iter.reach_end() {
-            let mut matched = false;
-
-            iter.fill_current_and_next();
-            self.argument_matched = false;
-            debug!("**** ArgIterator [{:?}, {:?}]", iter.current(), iter.next());
-
-            if let Ok(arg) = iter.parse(self.get_prefix()).await {
-                debug!("parse ... {:?}", arg);
-                for opt_style in &opt_order {
-                    if ! matched {
-                        let multiple_ctx = opt_style.gen_opt(&arg, iter.next());
-
-                        if multiple_ctx.len() > 0 {
-                            let mut cp = Box::new(SequenceProc::from(self.msg_id_gen.next_id()));
-
-                            for ctx in multiple_ctx {
-                                cp.app_ctx(ctx);
-                            }
-
-                            matched = self.publish(cp).await?;
-                        }
-                    }
-                }
-            }
-
-            // If next argument matched, skip it
-            if matched && self.argument_matched {
-                iter.skip();
-            }
-            if !matched {
-                if let Some(arg) = iter.current() {
-                    self.noa.push(arg.clone());
-                }
-            }
-
-            iter.skip();
+    async fn parse_nonopt(&mut self, unmatched_opts: &[usize]) -> Result<bool> {
+        apply_env_values(self.set.as_mut().unwrap())?;
+        if self.interpolate {
+            interpolate_values(self.set.as_mut().unwrap())?;
         }
-
         self.check_opt()?;
+        check_max_positionals(self.max_positionals, self.noa.len())?;
 
         let noa_total = self.noa().len();
+        // the command is the first non-option argument which is not an unmatched option
+        let cmd_index = (0 .. noa_total).find(|index| ! unmatched_opts.contains(index)).unwrap_or(0);
 
         // process cmd and pos
         if noa_total > 0 {
             debug!("---- In ForwardParser, start process {:?}", GenStyle::GS_Non_Cmd);
-            let non_opt_cmd = GenStyle::GS_Non_Cmd.gen_nonopt(&self.noa()[0], noa_total as i64, 1);
+            let non_opt_cmd = GenStyle::GS_Non_Cmd.gen_nonopt(&self.noa()[cmd_index], noa_total as u64, 1);
 
             if non_opt_cmd.len() > 0 {
                 let mut cp = Box::new(SingleCtxProc::from(self.msg_id_gen.next_id()));
@@ -439,7 +713,7 @@ impl<S, G> Parser<S, G> for ForwardParser<S, G>
 
             debug!("---- In ForwardParser, start process {:?}", GenStyle::GS_Non_Pos);
             for index in 1 ..= noa_total {
-                let non_opt_pos = GenStyle::GS_Non_Pos.gen_nonopt(&self.noa()[index - 1], noa_total as i64, index as i64);
+                let non_opt_pos = GenStyle::GS_Non_Pos.gen_nonopt(&self.noa()[index - 1], noa_total as u64, index as u64);
 
                 if non_opt_pos.len() > 0  {
                     let mut cp = Box::new(SingleCtxProc::from(self.msg_id_gen.next_id()));
@@ -466,30 +740,232 @@ impl<S, G> Parser<S, G> for ForwardParser<S, G>
             self.publish(cp).await?;
         }
 
-        self.check_other()?;
-
-        Ok(Some(true))
-    }
-
-    fn set_id_generator(&mut self, id_generator: G) {
-        self.msg_id_gen = id_generator;
+        self.check_other()
     }
 
-    fn set_callback(&mut self, id: Identifier, callback: OptCallback) {
-        self.callbacks.insert(id, callback);
+    pub fn set_argument_matched(&mut self) {
+        self.argument_matched = true;
     }
 
-    fn publish_to(&mut self, set: S) {
-        self.set = Some(set);
+    pub fn get_prefix(&self) -> &Vec<String> {
+        self.set.as_ref().unwrap().get_prefix()
     }
 
-    fn set(&self) -> &Option<S> {
-        &self.set
-    }
+    #[cfg(not(feature="async"))]
+    pub fn invoke_callback(&mut self, id: &Identifier, callback_type: CallbackType, index: u64) -> Result<bool> {
+        let opt = self.set.as_mut().unwrap().get_opt_mut(id.clone()).unwrap();
 
-    fn get_opt(&self, id: Identifier) -> Option<& dyn Opt> {
-        self.set.as_ref().unwrap().get_opt(id)
-    }
+        if let Some(callback) = self.callbacks.get_mut(id) {
+            debug!("!!!! Calling callback of {:?}", opt);
+            match callback_type {
+                CallbackType::Value | CallbackType::Transform => {
+                    callback.call_raw_value(opt, self.record.raw_values.get(id).and_then(|raws| raws.last()).map(|v| v.as_str()).unwrap_or_default())?;
+                }
+                CallbackType::Index => {
+                    let length = self.noa.len();
+                    let index = opt.index().calc_index(length as u64, index);
+
+                    println!("--> {:?} and {:?}", index, opt);
+                    if let Some(index) = index {
+                        let ret = callback.call_index(self.set.as_ref().unwrap(), &self.noa[index as usize - 1])?;
+                        // can we fix this long call?
+                        self.set.as_mut().unwrap().get_opt_mut(id.clone()).unwrap().set_value(OptValue::from_bool(ret));
+                    }
+                }
+                CallbackType::Main => {
+                    let ret = callback.call_main_mut(self.set.as_mut().unwrap(), &self.noa)?;
+                    // can we fix this long call?
+                    self.set.as_mut().unwrap().get_opt_mut(id.clone()).unwrap().set_value(OptValue::from_bool(ret));
+                }
+                _ => { }
+            }
+        }
+        Ok(true)
+    }
+
+    #[cfg(feature="async")]
+    pub async fn invoke_callback(&mut self, id: &Identifier, callback_type: CallbackType, index: u64) -> Result<bool> {
+        let opt = self.set.as_mut().unwrap().get_opt_mut(id.clone()).unwrap();
+
+        if let Some(callback) = self.callbacks.get_mut(id) {
+            debug!("!!!! Calling callback of {:?}", id);
+            match callback_type {
+                CallbackType::Value | CallbackType::Transform => {
+                    callback.call_raw_value(opt, self.record.raw_values.get(id).and_then(|raws| raws.last()).map(|v| v.as_str()).unwrap_or_default()).await?;
+                }
+                CallbackType::Index => {
+                    let length = self.noa.len();
+                    let index = opt.index().calc_index(length as u64, index);
+
+                    if let Some(index) = index {
+                        let ret = callback.call_index(self.set.as_ref().unwrap(), &self.noa[index as usize - 1]).await?;
+                        // can we fix this long call?
+                        self.set.as_mut().unwrap().get_opt_mut(id.clone()).unwrap().set_value(OptValue::from_bool(ret));
+                    }
+                }
+                CallbackType::Main => {
+                    let ret = callback.call_main_mut(self.set.as_mut().unwrap(), &self.noa).await?;
+                    // can we fix this long call?
+                    self.set.as_mut().unwrap().get_opt_mut(id.clone()).unwrap().set_value(OptValue::from_bool(ret));
+                }
+                _ => { }
+            }
+        }
+        Ok(true)
+    }
+}
+
+impl<S, G> Default for ForwardParser<S, G>
+    where S: Set, G: IdGenerator {
+    fn default() -> Self {
+        Self::new(G::default())
+    }
+}
+
+#[async_trait(?Send)]
+impl<S, G> Parser<S, G> for ForwardParser<S, G>
+    where S: Set, G: IdGenerator {
+    #[cfg(not(feature="async"))]
+    fn parse(&mut self, iter: &mut dyn IndexIterator) -> Result<Option<bool>> {
+        if self.set.is_none() {
+            return Ok(None);
+        }
+        self.pre_check()?;
+        debug!("---- In ForwardParser, start process option");
+        let mut unmatched_opts: Vec<usize> = vec![];
+
+        while ! iter.reach_end() {
+            iter.fill_current_and_next();
+
+            let like_opt = iter.parse(self.get_prefix()).is_ok_and(|arg| is_option_name(&arg));
+
+            if let Some(cmd) = self.current_subcommand(iter, like_opt, &unmatched_opts) {
+                debug!("---- In ForwardParser, left arguments are parsed by subparser of {}", cmd);
+                let mut sub_iter = Self::left_arguments(iter);
+
+                self.subparsers.get_mut(&cmd).unwrap().parse(&mut sub_iter)?;
+                self.noa.push(cmd);
+                break;
+            }
+            self.parse_arg(iter, &mut unmatched_opts)?;
+        }
+        self.parse_nonopt(&unmatched_opts)?;
+
+        Ok(Some(true))
+    }
+
+    #[cfg(feature="async")]
+    async fn parse(&mut self, iter: &mut dyn IndexIterator) -> Result<Option<bool>> {
+        if self.set.is_none() {
+            return Ok(None);
+        }
+        self.pre_check()?;
+        debug!("---- In ForwardParser, start process option");
+        let mut unmatched_opts: Vec<usize> = vec![];
+
+        while ! iter.reach_end() {
+            iter.fill_current_and_next();
+
+            let like_opt = iter.parse(self.get_prefix()).await.is_ok_and(|arg| is_option_name(&arg));
+
+            if let Some(cmd) = self.current_subcommand(iter, like_opt, &unmatched_opts) {
+                debug!("---- In ForwardParser, left arguments are parsed by subparser of {}", cmd);
+                let mut sub_iter = Self::left_arguments(iter);
+
+                self.subparsers.get_mut(&cmd).unwrap().parse(&mut sub_iter).await?;
+                self.noa.push(cmd);
+                break;
+            }
+            self.parse_arg(iter, &mut unmatched_opts).await?;
+        }
+        self.parse_nonopt(&unmatched_opts).await?;
+
+        Ok(Some(true))
+    }
+
+    fn set_id_generator(&mut self, id_generator: G) {
+        self.msg_id_gen = id_generator;
+    }
+
+    fn set_callback(&mut self, id: Identifier, callback: OptCallback) {
+        self.callbacks.insert(id, callback);
+    }
+
+    fn add_transform(&mut self, id: Identifier, transform: ValueTransform) {
+        self.transforms.entry(id).or_insert(vec![]).push(transform);
+    }
+
+    fn add_rule(&mut self, rule: GroupRule) {
+        self.rules.push(rule);
+    }
+
+    fn add_exclusive_group(&mut self, ids: &[Identifier]) {
+        self.exclusive_groups.push(ids.to_vec());
+    }
+
+    fn add_requires(&mut self, id: Identifier, dependency: Identifier) {
+        self.requires.entry(id).or_insert(vec![]).push(dependency);
+    }
+
+    fn validation_report(&self) -> ValidationReport {
+        match self.set.as_ref() {
+            Some(_) => ValidationReport::from_rules(&self.record.matched_styles, &self.rules),
+            None => ValidationReport::new(),
+        }
+    }
+
+    fn trace(&self) -> ParseTrace {
+        ParseTrace::from_set(self.set.as_ref().unwrap(), &self.record.matched_styles)
+    }
+
+    fn occurrence(&self, id: Identifier) -> u64 {
+        self.record.matched_styles.get(&id).map(|v| v.len() as u64).unwrap_or(0)
+    }
+
+    fn last_matched_option(&self) -> Option<Identifier> {
+        self.record.last_matched
+    }
+
+    fn set_max_positionals(&mut self, max: Option<usize>) {
+        self.max_positionals = max;
+    }
+
+    fn set_unknown_handler(&mut self, handler: Box<UnknownHandlerFn>) {
+        self.unknown_handler = Some(UnknownHandler::new(handler));
+    }
+
+    fn set_audit(&mut self, audit: bool) {
+        self.audit = audit;
+    }
+
+    fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+
+    fn set_interpolate(&mut self, interpolate: bool) {
+        self.interpolate = interpolate;
+    }
+
+    fn audit(&self) -> Vec<String> {
+        parser_default_audit(self.set.as_ref().unwrap(), &self.callbacks, &self.record.matched_styles)
+    }
+
+    #[cfg(feature="profile")]
+    fn profile_report(&self) -> ProfileReport {
+        self.record.profile.clone()
+    }
+
+    fn publish_to(&mut self, set: S) {
+        self.set = Some(set);
+    }
+
+    fn set(&self) -> &Option<S> {
+        &self.set
+    }
+
+    fn get_opt(&self, id: Identifier) -> Option<& dyn Opt> {
+        self.set.as_ref().unwrap().get_opt(id)
+    }
 
     fn get_opt_mut(&mut self, id: Identifier) -> Option<&mut dyn Opt> {
         self.set.as_mut().unwrap().get_opt_mut(id)
@@ -504,6 +980,9 @@ impl<S, G> Parser<S, G> for ForwardParser<S, G>
     }
 
     fn check_opt(&self) -> Result<bool> {
+        if let Some(name) = &self.record.missing_argument {
+            return Err(Error::MissingArgument(name.clone()));
+        }
         parser_default_opt_check(self.set.as_ref().unwrap())
     }
 
@@ -512,13 +991,34 @@ impl<S, G> Parser<S, G> for ForwardParser<S, G>
     }
 
     fn check_other(&self) -> Result<bool> {
-        Ok(true)
+        if self.audit {
+            self.audit();
+        }
+        parser_default_exclusive_check(self.set.as_ref().unwrap(), &self.exclusive_groups, &self.record.matched_styles)?;
+        parser_default_requires_check(self.set.as_ref().unwrap(), &self.requires, &self.record.matched_styles)
     }
 
-    fn reset(&mut self) {
+    fn reset_values(&mut self) {
         self.noa.clear();
-        self.set.as_mut().unwrap().reset();
+        self.record.clear();
+        self.matched_ids.clear();
         self.argument_matched = false;
+        if let Some(set) = self.set.as_mut() {
+            set.reset();
+        }
+        for parser in self.subparsers.values_mut() {
+            parser.reset_values();
+        }
+    }
+
+    fn rearm_callbacks(&mut self) {
+        if let Some(set) = self.set.as_mut() {
+            for id in self.callbacks.keys() {
+                if let Some(opt) = set.get_opt_mut(*id) {
+                    opt.set_need_invoke(true);
+                }
+            }
+        }
     }
 }
 
@@ -531,18 +1031,16 @@ impl<S, G> Publisher<Box<dyn Proc>> for ForwardParser<S, G>
 
         debug!("Receive msg<{:?}> => {:?}", &proc.id(), &proc);
 
-        for index in 0 .. self.cached_infos.len() {
+        self.record.begin();
+        for index in self.info_index.candidates(proc.as_ref()) {
             let info = self.cached_infos.get_mut(index).unwrap();
             let opt = self.set.as_mut().unwrap().get_opt_mut(info.id()).unwrap(); // id always exist, so just unwrap
-            let res = proc.process(opt)?;
-            let need_invoke = opt.is_need_invoke();
-            let callback_type = opt.callback_type();
+            let (id, transforms) = (info.id(), &mut self.transforms);
+            let res = proc.process_with(opt, &mut |value| apply_transform(transforms, &id, value))?;
 
-            if let Some(index)  = res {
-                if need_invoke {
-                    let id = info.id();
-                    
-                    opt.set_need_invoke(false);
+            self.record.record(proc.as_ref(), opt, res.is_some());
+            if let Some(index) = res {
+                if let Some(callback_type) = take_callback(opt) {
                     self.invoke_callback(&id, callback_type, index)?;
                 }
             }
@@ -551,6 +1049,8 @@ impl<S, G> Publisher<Box<dyn Proc>> for ForwardParser<S, G>
                 break;
             }
         }
+        self.record.finish(self.set.as_mut().unwrap(), proc.as_ref());
+        self.matched_ids.extend_from_slice(self.record.matched());
         if proc.is_matched() && proc.is_need_argument() {
             self.set_argument_matched();
         }
@@ -564,18 +1064,16 @@ impl<S, G> Publisher<Box<dyn Proc>> for ForwardParser<S, G>
 
         debug!("Receive msg<{:?}> => {:?}", &proc.id(), &proc);
 
-        for index in 0 .. self.cached_infos.len() {
+        self.record.begin();
+        for index in self.info_index.candidates(proc.as_ref()) {
             let info = self.cached_infos.get_mut(index).unwrap();
             let opt = self.set.as_mut().unwrap().get_opt_mut(info.id()).unwrap(); // id always exist, so just unwrap
-            let res = proc.process(opt).await?;
-            let need_invoke = opt.is_need_invoke();
-            let callback_type = opt.callback_type();
+            let (id, transforms) = (info.id(), &mut self.transforms);
+            let res = proc.process_with(opt, &mut |value| apply_transform(transforms, &id, value)).await?;
 
-            if let Some(index)  = res {
-                if need_invoke {
-                    let id = info.id();
-                    
-                    opt.set_need_invoke(false);
+            self.record.record(proc.as_ref(), opt, res.is_some());
+            if let Some(index) = res {
+                if let Some(callback_type) = take_callback(opt) {
                     self.invoke_callback(&id, callback_type, index).await?;
                 }
             }
@@ -584,6 +1082,8 @@ impl<S, G> Publisher<Box<dyn Proc>> for ForwardParser<S, G>
                 break;
             }
         }
+        self.record.finish(self.set.as_mut().unwrap(), proc.as_ref());
+        self.matched_ids.extend_from_slice(self.record.matched());
         if proc.is_matched() && proc.is_need_argument() {
             self.set_argument_matched();
         }
@@ -592,11 +1092,111 @@ impl<S, G> Publisher<Box<dyn Proc>> for ForwardParser<S, G>
     }
 
     fn reg_subscriber(&mut self, info: Box<dyn Info>) {
+        self.info_index.insert(self.cached_infos.len(), info.as_ref());
         self.cached_infos.push(info);
     }
 
     fn clean(&mut self) {
         self.cached_infos.clear();
+        self.info_index.clear();
+    }
+}
+
+/// The event generated by [`ParseStream`].
+#[derive(Debug)]
+pub enum ParseEvent {
+    /// The option matched, with the value after matched.
+    OptionMatched { id: Identifier, value: OptValue },
+
+    /// The argument not matched any option.
+    NonOption(String),
+
+    /// All the arguments are processed, and the non-option and result are checked.
+    Finished,
+}
+
+/// ParseStream process the arguments one by one when it advanced, see [`ForwardParser::into_stream`].
+/// It yield the [`ParseEvent::Finished`] at last, or stop after the first error.
+#[cfg(not(feature="async"))]
+pub struct ParseStream<'a, S, G>
+    where S: Set, G: IdGenerator {
+    parser: ForwardParser<S, G>,
+
+    iter: &'a mut dyn IndexIterator,
+
+    events: VecDeque<ParseEvent>,
+
+    unmatched_opts: Vec<usize>,
+
+    started: bool,
+
+    finished: bool,
+}
+
+#[cfg(not(feature="async"))]
+impl<'a, S, G> ParseStream<'a, S, G>
+    where S: Set, G: IdGenerator {
+    pub fn new(parser: ForwardParser<S, G>, iter: &'a mut dyn IndexIterator) -> Self {
+        Self {
+            parser,
+            iter,
+            events: VecDeque::new(),
+            unmatched_opts: vec![],
+            started: false,
+            finished: false,
+        }
+    }
+
+    pub fn parser(&self) -> &ForwardParser<S, G> {
+        &self.parser
+    }
+
+    /// Return the parser, the state of it is the processed part of arguments.
+    pub fn into_parser(self) -> ForwardParser<S, G> {
+        self.parser
+    }
+
+    fn advance(&mut self) -> Result<()> {
+        if ! self.started {
+            self.started = true;
+            if self.parser.set().is_none() {
+                self.finished = true;
+                return Ok(());
+            }
+            self.parser.pre_check()?;
+        }
+        else if ! self.iter.reach_end() {
+            let events = self.parser.parse_arg(self.iter, &mut self.unmatched_opts)?;
+
+            self.events.extend(events);
+        }
+        else {
+            self.finished = true;
+            self.parser.parse_nonopt(&self.unmatched_opts)?;
+            self.events.push_back(ParseEvent::Finished);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(not(feature="async"))]
+impl<'a, S, G> Iterator for ParseStream<'a, S, G>
+    where S: Set, G: IdGenerator {
+    type Item = Result<ParseEvent>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(event) = self.events.pop_front() {
+                return Some(Ok(event));
+            }
+            if self.finished {
+                return None;
+            }
+            if let Err(e) = self.advance() {
+                self.finished = true;
+                return Some(Err(e));
+            }
+        }
     }
 }
 
@@ -605,8 +1205,8 @@ impl<S, G> Publisher<Box<dyn Proc>> for ForwardParser<S, G>
 /// * GenStyle::GS_Equal_With_Value
 /// * GenStyle::GS_Argument
 /// * GenStyle::GS_Boolean
-/// * GenStyle::GS_Mutliple_Option
 /// * GenStyle::GS_Embedded_Value
+/// * GenStyle::GS_Mutliple_Option
 /// 
 /// If any option matched, the [`Context`] don't set the value, but save it to parser.
 /// Parser will call the [`Parser::check_opt`] do option check after the option processed.
@@ -622,6 +1222,8 @@ pub struct DelayParser<S, G>
 
     cached_infos: Vec<Box<dyn Info>>,
 
+    info_index: InfoIndex,
+
     noa: Vec<String>,
 
     set: Option<S>,
@@ -630,6 +1232,26 @@ pub struct DelayParser<S, G>
 
     callbacks: HashMap<Identifier, OptCallback>,
 
+    transforms: HashMap<Identifier, Vec<ValueTransform>>,
+
+    rules: Vec<GroupRule>,
+
+    exclusive_groups: Vec<Vec<Identifier>>,
+
+    requires: HashMap<Identifier, Vec<Identifier>>,
+
+    record: MatchRecorder,
+
+    max_positionals: Option<usize>,
+
+    unknown_handler: Option<UnknownHandler>,
+
+    audit: bool,
+
+    strict: bool,
+
+    interpolate: bool,
+
     value_mapper: HashMap<Identifier, Vec<OptValue>>,
 }
 
@@ -639,10 +1261,21 @@ impl<S, G> DelayParser<S, G>
         Self {
             msg_id_gen: msg_id_gen,
             cached_infos: vec![],
+            info_index: InfoIndex::new(),
             noa: vec![],
             set: None,
             argument_matched: false,
             callbacks: HashMap::new(),
+            transforms: HashMap::new(),
+            rules: vec![],
+            exclusive_groups: vec![],
+            requires: HashMap::new(),
+            record: MatchRecorder::new(),
+            max_positionals: None,
+            unknown_handler: None,
+            audit: false,
+            strict: false,
+            interpolate: false,
             value_mapper: HashMap::new(),
         }
     }
@@ -660,14 +1293,14 @@ impl<S, G> DelayParser<S, G>
     }
 
     #[cfg(not(feature="async"))]
-    pub fn invoke_callback(&mut self, id: &Identifier, callback_type: CallbackType, index: u64) -> Result<bool> {
+    pub fn invoke_callback(&mut self, id: &Identifier, callback_type: CallbackType, index: u64, raw: &str) -> Result<bool> {
         let opt = self.set.as_mut().unwrap().get_opt_mut(id.clone()).unwrap();
 
         if let Some(callback) = self.callbacks.get_mut(id) {
             debug!("!!!! Calling callback of {:?}", id);
             match callback_type {
-                CallbackType::Value => {
-                    callback.call_value(opt)?;
+                CallbackType::Value | CallbackType::Transform => {
+                    callback.call_raw_value(opt, raw)?;
                 }
                 CallbackType::Index => {
                     let length = self.noa.len();
@@ -680,7 +1313,7 @@ impl<S, G> DelayParser<S, G>
                     }
                 }
                 CallbackType::Main => {
-                    let ret = callback.call_main(self.set.as_ref().unwrap(), &self.noa)?;
+                    let ret = callback.call_main_mut(self.set.as_mut().unwrap(), &self.noa)?;
                     // can we fix this long call?
                     self.set.as_mut().unwrap().get_opt_mut(id.clone()).unwrap().set_value(OptValue::from_bool(ret));
                 }
@@ -691,14 +1324,14 @@ impl<S, G> DelayParser<S, G>
     }
 
     #[cfg(feature="async")]
-    pub async fn invoke_callback(&mut self, id: &Identifier, callback_type: CallbackType, index: u64) -> Result<bool> {
+    pub async fn invoke_callback(&mut self, id: &Identifier, callback_type: CallbackType, index: u64, raw: &str) -> Result<bool> {
         let opt = self.set.as_mut().unwrap().get_opt_mut(id.clone()).unwrap();
 
         if let Some(callback) = self.callbacks.get_mut(id) {
             debug!("!!!! Calling callback of {:?}", id);
             match callback_type {
-                CallbackType::Value => {
-                    callback.call_value(opt).await?;
+                CallbackType::Value | CallbackType::Transform => {
+                    callback.call_raw_value(opt, raw).await?;
                 }
                 CallbackType::Index => {
                     let length = self.noa.len();
@@ -711,7 +1344,7 @@ impl<S, G> DelayParser<S, G>
                     }
                 }
                 CallbackType::Main => {
-                    let ret = callback.call_main(self.set.as_ref().unwrap(), &self.noa).await?;
+                    let ret = callback.call_main_mut(self.set.as_mut().unwrap(), &self.noa).await?;
                     // can we fix this long call?
                     self.set.as_mut().unwrap().get_opt_mut(id.clone()).unwrap().set_value(OptValue::from_bool(ret));
                 }
@@ -720,74 +1353,169 @@ impl<S, G> DelayParser<S, G>
         }
         Ok(true)
     }
-}
-
-impl<S, G> Default for DelayParser<S, G>
-    where S: Set, G: IdGenerator {
-    fn default() -> Self {
-        Self::new(G::default())
-    }
-}
 
-#[async_trait(?Send)]
-impl<S, G> Parser<S, G> for DelayParser<S, G>
-    where S: Set, G: IdGenerator {
+    /// Process the current argument of `iter`, return true if it matched an option.
     #[cfg(not(feature="async"))]
-    fn parse(&mut self, iter: &mut dyn IndexIterator) -> Result<Option<bool>> {
-        if self.set.is_none() {
-            return Ok(None);
-        }
+    fn parse_arg(&mut self, iter: &mut dyn IndexIterator) -> Result<bool> {
         let opt_order = [
             GenStyle::GS_Delay_Equal_With_Value,
             GenStyle::GS_Delay_Argument,
             GenStyle::GS_Delay_Boolean,
-            GenStyle::GS_Delay_Mutliple_Option,
             GenStyle::GS_Delay_Embedded_Value,
+            GenStyle::GS_Delay_Mutliple_Option,
         ];
+        let mut matched = false;
+        let mut like_opt = false;
 
-        self.pre_check()?;
-        debug!("---- In DelayParser, start process option");
-        while ! iter.reach_end() {
-            let mut matched = false;
+        iter.fill_current_and_next();
+        self.argument_matched = false;
+        debug!("**** ArgIterator [{:?}, {:?}]", iter.current(), iter.next());
 
-            iter.fill_current_and_next();
-            self.argument_matched = false;
-            debug!("**** ArgIterator [{:?}, {:?}]", iter.current(), iter.next());
+        // the standalone `--` terminate the option processing
+        if is_terminator(iter.current()) {
+            debug!("---- In DelayParser, got terminator, left arguments are non-option");
+            self.noa.extend(take_left_arguments(iter));
+            return Ok(false);
+        }
 
-            if let Ok(arg) = iter.parse(self.get_prefix()) {
-                debug!("parse ... {:?}", arg);
-                for opt_style in &opt_order {
-                    if ! matched {
-                        let multiple_ctx = opt_style.gen_opt(&arg, iter.next());
+        if let Ok(mut arg) = iter.parse(self.get_prefix()) {
+            let group = resolve_argument(self.set.as_ref().unwrap(), &mut arg, false)?;
+            debug!("parse ... {:?}", arg);
+            like_opt = is_option_name(&arg);
+            for opt_style in &opt_order {
+                let multiple_ctx = opt_style.gen_opt_with_group(&arg, iter.next(), group);
 
-                        if multiple_ctx.len() > 0 {
-                            let mut cp = Box::new(SequenceProc::from(self.msg_id_gen.next_id()));
+                if multiple_ctx.len() > 0 {
+                    let mut cp = Box::new(SequenceProc::from(self.msg_id_gen.next_id()));
 
-                            for ctx in multiple_ctx {
-                                cp.app_ctx(ctx);
-                            }
+                    for ctx in multiple_ctx {
+                        cp.app_ctx(ctx);
+                    }
 
-                            matched = self.publish(cp)?;
-                        }
+                    self.record.gen_style = Some(format!("{:?}", opt_style));
+                    self.record.gen_tokens = iter.current().iter().chain(iter.next().iter()).cloned().collect();
+                    matched = catch_missing_argument(self.publish(cp), iter.next().is_none(), &mut self.record.missing_argument)?;
+
+                    // the first matched style wins, skip the other styles
+                    if matched {
+                        break;
                     }
                 }
             }
+        }
 
-            // If next argument matched, skip it
-            if matched && self.argument_matched {
-                iter.skip();
-            }
-
-            if !matched {
-                if let Some(arg) = iter.current() {
+        // If next argument matched, skip it
+        if matched && self.argument_matched {
+            iter.skip();
+        }
+
+        if !matched {
+            if let Some(arg) = iter.current() {
+                let suppressed = parser_default_unknown_check(self.unknown_handler.as_mut(), self.strict, like_opt, arg)?;
+
+                if ! suppressed {
                     self.noa.push(arg.clone());
                 }
             }
+        }
+
+        iter.skip();
+        Ok(matched)
+    }
+
+    /// Process the current argument of `iter`, return true if it matched an option.
+    #[cfg(feature="async")]
+    async fn parse_arg(&mut self, iter: &mut dyn IndexIterator) -> Result<bool> {
+        let opt_order = [
+            GenStyle::GS_Delay_Equal_With_Value,
+            GenStyle::GS_Delay_Argument,
+            GenStyle::GS_Delay_Boolean,
+            GenStyle::GS_Delay_Embedded_Value,
+            GenStyle::GS_Delay_Mutliple_Option,
+        ];
+        let mut matched = false;
+        let mut like_opt = false;
+
+        iter.fill_current_and_next();
+        self.argument_matched = false;
+        debug!("**** ArgIterator [{:?}, {:?}]", iter.current(), iter.next());
+
+        // the standalone `--` terminate the option processing
+        if is_terminator(iter.current()) {
+            debug!("---- In DelayParser, got terminator, left arguments are non-option");
+            self.noa.extend(take_left_arguments(iter));
+            return Ok(false);
+        }
+
+        if let Ok(mut arg) = iter.parse(self.get_prefix()).await {
+            let group = resolve_argument(self.set.as_ref().unwrap(), &mut arg, false)?;
+            debug!("parse ... {:?}", arg);
+            like_opt = is_option_name(&arg);
+            for opt_style in &opt_order {
+                let multiple_ctx = opt_style.gen_opt_with_group(&arg, iter.next(), group);
+
+                if multiple_ctx.len() > 0 {
+                    let mut cp = Box::new(SequenceProc::from(self.msg_id_gen.next_id()));
+
+                    for ctx in multiple_ctx {
+                        cp.app_ctx(ctx);
+                    }
+
+                    self.record.gen_style = Some(format!("{:?}", opt_style));
+                    self.record.gen_tokens = iter.current().iter().chain(iter.next().iter()).cloned().collect();
+                    matched = catch_missing_argument(self.publish(cp).await, iter.next().is_none(), &mut self.record.missing_argument)?;
+
+                    // the first matched style wins, skip the other styles
+                    if matched {
+                        break;
+                    }
+                }
+            }
+        }
 
+        // If next argument matched, skip it
+        if matched && self.argument_matched {
             iter.skip();
         }
 
+        if !matched {
+            if let Some(arg) = iter.current() {
+                let suppressed = parser_default_unknown_check(self.unknown_handler.as_mut(), self.strict, like_opt, arg)?;
+
+                if ! suppressed {
+                    self.noa.push(arg.clone());
+                }
+            }
+        }
+
+        iter.skip();
+        Ok(matched)
+    }
+}
+
+impl<S, G> Default for DelayParser<S, G>
+    where S: Set, G: IdGenerator {
+    fn default() -> Self {
+        Self::new(G::default())
+    }
+}
+
+#[async_trait(?Send)]
+impl<S, G> Parser<S, G> for DelayParser<S, G>
+    where S: Set, G: IdGenerator {
+    #[cfg(not(feature="async"))]
+    fn parse(&mut self, iter: &mut dyn IndexIterator) -> Result<Option<bool>> {
+        if self.set.is_none() {
+            return Ok(None);
+        }
+        self.pre_check()?;
+        debug!("---- In DelayParser, start process option");
+        while ! iter.reach_end() {
+            self.parse_arg(iter)?;
+        }
+
         self.check_opt()?;
+        check_max_positionals(self.max_positionals, self.noa.len())?;
 
         let noa_total = self.noa().len();
 
@@ -826,18 +1554,23 @@ impl<S, G> Parser<S, G> for DelayParser<S, G>
 
             for id in ids {
                 if let Some(x) = self.value_mapper.remove_entry(&id) {
+                    let raws = self.record.raw_values.get(&id).cloned().unwrap_or_default();
                     let opt = self.set.as_mut().unwrap().get_opt_mut(id.clone()).unwrap();
-
-                    for value in x.1 {
-                        opt.set_value(value);
-                    }
                     let callback_type = opt.callback_type();
 
                     opt.set_need_invoke(false);
-                    self.invoke_callback(&id, callback_type, 0)?;
+                    // invoke the callback for each occurrence with the raw argument of it
+                    for (occurrence, value) in x.1.into_iter().enumerate() {
+                        self.set.as_mut().unwrap().get_opt_mut(id.clone()).unwrap().set_value(value);
+                        self.invoke_callback(&id, callback_type.clone(), 0, raws.get(occurrence).map(|v| v.as_str()).unwrap_or_default())?;
+                    }
                 }
             }
         }
+        apply_env_values(self.set.as_mut().unwrap())?;
+        if self.interpolate {
+            interpolate_values(self.set.as_mut().unwrap())?;
+        }
 
         self.check_nonopt()?;
 
@@ -863,64 +1596,21 @@ impl<S, G> Parser<S, G> for DelayParser<S, G>
         if self.set.is_none() {
             return Ok(None);
         }
-        let opt_order = [
-            GenStyle::GS_Delay_Equal_With_Value,
-            GenStyle::GS_Delay_Argument,
-            GenStyle::GS_Delay_Boolean,
-            GenStyle::GS_Delay_Mutliple_Option,
-            GenStyle::GS_Delay_Embedded_Value,
-        ];
-
-        self.pre_check().await?;
+        self.pre_check()?;
         debug!("---- In DelayParser, start process option");
         while ! iter.reach_end() {
-            let mut matched = false;
-
-            iter.fill_current_and_next();
-            self.argument_matched = false;
-            debug!("**** ArgIterator [{:?}, {:?}]", iter.current(), iter.next());
-
-            if let Ok(arg) = iter.parse(self.get_prefix()).await {
-                debug!("parse ... {:?}", arg);
-                for opt_style in &opt_order {
-                    if ! matched {
-                        let multiple_ctx = opt_style.gen_opt(&arg, iter.next());
-
-                        if multiple_ctx.len() > 0 {
-                            let mut cp = Box::new(SequenceProc::from(self.msg_id_gen.next_id()));
-
-                            for ctx in multiple_ctx {
-                                cp.app_ctx(ctx);
-                            }
-
-                            matched = self.publish(cp).await?;
-                        }
-                    }
-                }
-            }
-
-            // If next argument matched, skip it
-            if matched && self.argument_matched {
-                iter.skip();
-            }
-
-            if !matched {
-                if let Some(arg) = iter.current() {
-                    self.noa.push(arg.clone());
-                }
-            }
-
-            iter.skip();
+            self.parse_arg(iter).await?;
         }
 
         self.check_opt()?;
+        check_max_positionals(self.max_positionals, self.noa.len())?;
 
         let noa_total = self.noa().len();
 
         // process cmd and pos
         if noa_total > 0 {
             debug!("---- In DelayParser, start process {:?}", GenStyle::GS_Non_Cmd);
-            let non_opt_cmd = GenStyle::GS_Non_Cmd.gen_nonopt(&self.noa()[0], noa_total as i64, 1);
+            let non_opt_cmd = GenStyle::GS_Non_Cmd.gen_nonopt(&self.noa()[0], noa_total as u64, 1);
 
             if non_opt_cmd.len() > 0 {
                 let mut cp = Box::new(SingleCtxProc::from(self.msg_id_gen.next_id()));
@@ -933,7 +1623,7 @@ impl<S, G> Parser<S, G> for DelayParser<S, G>
 
             debug!("---- In DelayParser, start process {:?}", GenStyle::GS_Non_Pos);
             for index in 1 ..= noa_total {
-                let non_opt_pos = GenStyle::GS_Non_Pos.gen_nonopt(&self.noa()[index - 1], noa_total as i64, index as i64);
+                let non_opt_pos = GenStyle::GS_Non_Pos.gen_nonopt(&self.noa()[index - 1], noa_total as u64, index as u64);
 
                 if non_opt_pos.len() > 0  {
                     let mut cp = Box::new(SingleCtxProc::from(self.msg_id_gen.next_id()));
@@ -952,18 +1642,23 @@ impl<S, G> Parser<S, G> for DelayParser<S, G>
 
             for id in ids {
                 if let Some(x) = self.value_mapper.remove_entry(&id) {
+                    let raws = self.record.raw_values.get(&id).cloned().unwrap_or_default();
                     let opt = self.set.as_mut().unwrap().get_opt_mut(id.clone()).unwrap();
-
-                    for value in x.1 {
-                        opt.set_value(value);
-                    }
                     let callback_type = opt.callback_type();
 
                     opt.set_need_invoke(false);
-                    self.invoke_callback(&id, callback_type).await?;
+                    // invoke the callback for each occurrence with the raw argument of it
+                    for (occurrence, value) in x.1.into_iter().enumerate() {
+                        self.set.as_mut().unwrap().get_opt_mut(id.clone()).unwrap().set_value(value);
+                        self.invoke_callback(&id, callback_type.clone(), 0, raws.get(occurrence).map(|v| v.as_str()).unwrap_or_default()).await?;
+                    }
                 }
             }
         }
+        apply_env_values(self.set.as_mut().unwrap())?;
+        if self.interpolate {
+            interpolate_values(self.set.as_mut().unwrap())?;
+        }
 
         self.check_nonopt()?;
 
@@ -992,6 +1687,70 @@ impl<S, G> Parser<S, G> for DelayParser<S, G>
         self.callbacks.insert(id, callback);
     }
 
+    fn add_transform(&mut self, id: Identifier, transform: ValueTransform) {
+        self.transforms.entry(id).or_insert(vec![]).push(transform);
+    }
+
+    fn add_rule(&mut self, rule: GroupRule) {
+        self.rules.push(rule);
+    }
+
+    fn add_exclusive_group(&mut self, ids: &[Identifier]) {
+        self.exclusive_groups.push(ids.to_vec());
+    }
+
+    fn add_requires(&mut self, id: Identifier, dependency: Identifier) {
+        self.requires.entry(id).or_insert(vec![]).push(dependency);
+    }
+
+    fn validation_report(&self) -> ValidationReport {
+        match self.set.as_ref() {
+            Some(_) => ValidationReport::from_rules(&self.record.matched_styles, &self.rules),
+            None => ValidationReport::new(),
+        }
+    }
+
+    fn trace(&self) -> ParseTrace {
+        ParseTrace::from_set(self.set.as_ref().unwrap(), &self.record.matched_styles)
+    }
+
+    fn occurrence(&self, id: Identifier) -> u64 {
+        self.record.matched_styles.get(&id).map(|v| v.len() as u64).unwrap_or(0)
+    }
+
+    fn last_matched_option(&self) -> Option<Identifier> {
+        self.record.last_matched
+    }
+
+    fn set_max_positionals(&mut self, max: Option<usize>) {
+        self.max_positionals = max;
+    }
+
+    fn set_unknown_handler(&mut self, handler: Box<UnknownHandlerFn>) {
+        self.unknown_handler = Some(UnknownHandler::new(handler));
+    }
+
+    fn set_audit(&mut self, audit: bool) {
+        self.audit = audit;
+    }
+
+    fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+
+    fn set_interpolate(&mut self, interpolate: bool) {
+        self.interpolate = interpolate;
+    }
+
+    fn audit(&self) -> Vec<String> {
+        parser_default_audit(self.set.as_ref().unwrap(), &self.callbacks, &self.record.matched_styles)
+    }
+
+    #[cfg(feature="profile")]
+    fn profile_report(&self) -> ProfileReport {
+        self.record.profile.clone()
+    }
+
     fn publish_to(&mut self, set: S) {
         self.set = Some(set);
     }
@@ -1017,6 +1776,9 @@ impl<S, G> Parser<S, G> for DelayParser<S, G>
     }
 
     fn check_opt(&self) -> Result<bool> {
+        if let Some(name) = &self.record.missing_argument {
+            return Err(Error::MissingArgument(name.clone()));
+        }
         Ok(true)    
     }
 
@@ -1026,13 +1788,31 @@ impl<S, G> Parser<S, G> for DelayParser<S, G>
     }
 
     fn check_other(&self) -> Result<bool> {
-        Ok(true)
+        if self.audit {
+            self.audit();
+        }
+        parser_default_exclusive_check(self.set.as_ref().unwrap(), &self.exclusive_groups, &self.record.matched_styles)?;
+        parser_default_requires_check(self.set.as_ref().unwrap(), &self.requires, &self.record.matched_styles)
     }
 
-    fn reset(&mut self) {
+    fn reset_values(&mut self) {
         self.noa.clear();
-        self.set.as_mut().unwrap().reset();
+        self.record.clear();
+        self.value_mapper.clear();
         self.argument_matched = false;
+        if let Some(set) = self.set.as_mut() {
+            set.reset();
+        }
+    }
+
+    fn rearm_callbacks(&mut self) {
+        if let Some(set) = self.set.as_mut() {
+            for id in self.callbacks.keys() {
+                if let Some(opt) = set.get_opt_mut(*id) {
+                    opt.set_need_invoke(true);
+                }
+            }
+        }
     }
 }
 
@@ -1046,29 +1826,38 @@ impl<S, G> Publisher<Box<dyn Proc>> for DelayParser<S, G>
         let mut process_id: Vec<Identifier> = vec![];
 
         debug!("Receive msg<{:?}> => {:?}", &proc.id(), &proc);
-        
-        for index in 0 .. self.cached_infos.len() {
+
+        self.record.begin();
+        for index in self.info_index.candidates(proc.as_ref()) {
             let info = self.cached_infos.get_mut(index).unwrap();
             let opt = self.set.as_mut().unwrap().get_opt_mut(info.id()).unwrap(); // id always exist, so just unwrap
             let res = proc.process(opt)?;
-            let mut need_invoke = opt.is_need_invoke();
-            let callback_type = opt.callback_type();
             let id = info.id();
 
+            self.record.record(proc.as_ref(), opt, res.is_some());
             if let Some(index) = res {
+                let mut delayed = false;
+
                 for ctx_i in 0 .. proc.len() {
                     let ctx = proc.get_ctx(ctx_i);
                     if let Some(ctx) = ctx {
                         if ctx.is_matched() && !process_id.contains(&id) {
                             let default_string = String::default();
                             let v = ctx.get_next_argument().as_ref().unwrap_or(&default_string);
-                            let value = opt.parse_value(v.as_str())?;
+                            let value = if ctx.get_style() == Style::Pos && ctx.get_next_argument().is_none() {
+                                // the value of positional is already parsed by non-option context
+                                opt.value().clone()
+                            }
+                            else {
+                                opt.parse_value(v.as_str())?
+                            };
+                            let value = apply_transform(&mut self.transforms, &id, value)?;
 
                             match ctx.get_style()  {
                                 Style::Argument | Style::Boolean | Style::Multiple => {
                                     // we may have multiple value for one option
                                     value_keeper.entry(id).or_insert(vec![]).push(value);
-                                    need_invoke = false;
+                                    delayed = true;
                                 }
                                 _ => {
                                     // Set the option value if we using a delay context
@@ -1080,9 +1869,13 @@ impl<S, G> Publisher<Box<dyn Proc>> for DelayParser<S, G>
                         }
                     }
                 }
-                if need_invoke {
-                    opt.set_need_invoke(false);
-                    self.invoke_callback(&id, callback_type, index)?;
+                // the callback of delayed value is invoked after all the arguments processed
+                if ! delayed {
+                    if let Some(callback_type) = take_callback(opt) {
+                        let raw = self.record.raw_values.get(&id).and_then(|raws| raws.last()).cloned().unwrap_or_default();
+
+                        self.invoke_callback(&id, callback_type, index, &raw)?;
+                    }
                 }
             }
 
@@ -1092,6 +1885,7 @@ impl<S, G> Publisher<Box<dyn Proc>> for DelayParser<S, G>
             }
         }
 
+        self.record.finish(self.set.as_mut().unwrap(), proc.as_ref());
         if proc.is_matched() && proc.is_need_argument() {
             self.set_argument_matched();
         }
@@ -1109,29 +1903,38 @@ impl<S, G> Publisher<Box<dyn Proc>> for DelayParser<S, G>
         let mut process_id: Vec<Identifier> = vec![];
 
         debug!("Receive msg<{:?}> => {:?}", &proc.id(), &proc);
-        
-        for index in 0 .. self.cached_infos.len() {
+
+        self.record.begin();
+        for index in self.info_index.candidates(proc.as_ref()) {
             let info = self.cached_infos.get_mut(index).unwrap();
             let opt = self.set.as_mut().unwrap().get_opt_mut(info.id()).unwrap(); // id always exist, so just unwrap
             let res = proc.process(opt).await?;
-            let mut need_invoke = opt.is_need_invoke();
-            let callback_type = opt.callback_type();
             let id = info.id();
 
+            self.record.record(proc.as_ref(), opt, res.is_some());
             if let Some(index) = res {
+                let mut delayed = false;
+
                 for ctx_i in 0 .. proc.len() {
                     let ctx = proc.get_ctx(ctx_i);
                     if let Some(ctx) = ctx {
                         if ctx.is_matched() && !process_id.contains(&id) {
                             let default_string = String::default();
                             let v = ctx.get_next_argument().as_ref().unwrap_or(&default_string);
-                            let value = opt.parse_value(v.as_str())?;
+                            let value = if ctx.get_style() == Style::Pos && ctx.get_next_argument().is_none() {
+                                // the value of positional is already parsed by non-option context
+                                opt.value().clone()
+                            }
+                            else {
+                                opt.parse_value(v.as_str())?
+                            };
+                            let value = apply_transform(&mut self.transforms, &id, value)?;
 
                             match ctx.get_style()  {
                                 Style::Argument | Style::Boolean | Style::Multiple => {
                                     // we may have multiple value for one option
                                     value_keeper.entry(id).or_insert(vec![]).push(value);
-                                    need_invoke = false;
+                                    delayed = true;
                                 }
                                 _ => {
                                     // Set the option value if we using a delay context
@@ -1143,9 +1946,13 @@ impl<S, G> Publisher<Box<dyn Proc>> for DelayParser<S, G>
                         }
                     }
                 }
-                if need_invoke {
-                    opt.set_need_invoke(false);
-                    self.invoke_callback(&id, callback_type, index).await?;
+                // the callback of delayed value is invoked after all the arguments processed
+                if ! delayed {
+                    if let Some(callback_type) = take_callback(opt) {
+                        let raw = self.record.raw_values.get(&id).and_then(|raws| raws.last()).cloned().unwrap_or_default();
+
+                        self.invoke_callback(&id, callback_type, index, &raw).await?;
+                    }
                 }
             }
 
@@ -1155,10 +1962,10 @@ impl<S, G> Publisher<Box<dyn Proc>> for DelayParser<S, G>
             }
         }
 
+        self.record.finish(self.set.as_mut().unwrap(), proc.as_ref());
         if proc.is_matched() && proc.is_need_argument() {
             self.set_argument_matched();
         }
-
         for (id, value) in value_keeper {
             self.add_delay_value(id, value);
         }
@@ -1167,11 +1974,13 @@ impl<S, G> Publisher<Box<dyn Proc>> for DelayParser<S, G>
     }
 
     fn reg_subscriber(&mut self, info: Box<dyn Info>) {
+        self.info_index.insert(self.cached_infos.len(), info.as_ref());
         self.cached_infos.push(info);
     }
 
     fn clean(&mut self) {
         self.cached_infos.clear();
+        self.info_index.clear();
     }
 }
 
@@ -1180,8 +1989,8 @@ impl<S, G> Publisher<Box<dyn Proc>> for DelayParser<S, G>
 /// * GenStyle::GS_Equal_With_Value
 /// * GenStyle::GS_Argument
 /// * GenStyle::GS_Boolean
-/// * GenStyle::GS_Mutliple_Option
 /// * GenStyle::GS_Embedded_Value
+/// * GenStyle::GS_Mutliple_Option
 /// 
 /// The [`Context`] will set the value if any option matched.
 /// Parser will call the [`Parser::check_opt`] do option check after the option processed.
@@ -1197,13 +2006,37 @@ pub struct PreParser<S, G>
 
     cached_infos: Vec<Box<dyn Info>>,
 
+    info_index: InfoIndex,
+
     noa: Vec<String>,
 
+    unknown_opts: Vec<String>,
+
     set: Option<S>,
 
     argument_matched: bool,
 
     callbacks: HashMap<Identifier, OptCallback>,
+
+    transforms: HashMap<Identifier, Vec<ValueTransform>>,
+
+    rules: Vec<GroupRule>,
+
+    exclusive_groups: Vec<Vec<Identifier>>,
+
+    requires: HashMap<Identifier, Vec<Identifier>>,
+
+    record: MatchRecorder,
+
+    max_positionals: Option<usize>,
+
+    unknown_handler: Option<UnknownHandler>,
+
+    audit: bool,
+
+    strict: bool,
+
+    interpolate: bool,
 }
 
 impl<S, G> PreParser<S, G>
@@ -1212,10 +2045,22 @@ impl<S, G> PreParser<S, G>
         Self {
             msg_id_gen: msg_id_gen,
             cached_infos: vec![],
+            info_index: InfoIndex::new(),
             noa: vec![],
+            unknown_opts: vec![],
             set: None,
             argument_matched: false,
             callbacks: HashMap::new(),
+            transforms: HashMap::new(),
+            rules: vec![],
+            exclusive_groups: vec![],
+            requires: HashMap::new(),
+            record: MatchRecorder::new(),
+            max_positionals: None,
+            unknown_handler: None,
+            audit: false,
+            strict: false,
+            interpolate: false,
         }
     }
 
@@ -1223,6 +2068,11 @@ impl<S, G> PreParser<S, G>
         self.argument_matched = true;
     }
 
+    /// Return the arguments look like an option but not matched any option.
+    pub fn unknown_opts(&self) -> &Vec<String> {
+        &self.unknown_opts
+    }
+
     pub fn get_prefix(&self) -> &Vec<String> {
         self.set.as_ref().unwrap().get_prefix()
     }
@@ -1234,8 +2084,8 @@ impl<S, G> PreParser<S, G>
         if let Some(callback) = self.callbacks.get_mut(id) {
             debug!("!!!! Calling callback of {:?}", id);
             match callback_type {
-                CallbackType::Value => {
-                    callback.call_value(opt)?;
+                CallbackType::Value | CallbackType::Transform => {
+                    callback.call_raw_value(opt, self.record.raw_values.get(id).and_then(|raws| raws.last()).map(|v| v.as_str()).unwrap_or_default())?;
                 }
                 CallbackType::Index => {
                     let length = self.noa.len();
@@ -1248,7 +2098,7 @@ impl<S, G> PreParser<S, G>
                     }
                 }
                 CallbackType::Main => {
-                    let ret = callback.call_main(self.set.as_ref().unwrap(), &self.noa)?;
+                    let ret = callback.call_main_mut(self.set.as_mut().unwrap(), &self.noa)?;
                     // can we fix this long call?
                     self.set.as_mut().unwrap().get_opt_mut(id.clone()).unwrap().set_value(OptValue::from_bool(ret));
                 }
@@ -1265,8 +2115,8 @@ impl<S, G> PreParser<S, G>
         if let Some(callback) = self.callbacks.get_mut(id) {
             debug!("!!!! Calling callback of {:?}", id);
             match callback_type {
-                CallbackType::Value => {
-                    callback.call_value(opt).await?;
+                CallbackType::Value | CallbackType::Transform => {
+                    callback.call_raw_value(opt, self.record.raw_values.get(id).and_then(|raws| raws.last()).map(|v| v.as_str()).unwrap_or_default()).await?;
                 }
                 CallbackType::Index => {
                     let length = self.noa.len();
@@ -1279,7 +2129,7 @@ impl<S, G> PreParser<S, G>
                     }
                 }
                 CallbackType::Main => {
-                    let ret = callback.call_main(self.set.as_ref().unwrap(), &self.noa).await?;
+                    let ret = callback.call_main_mut(self.set.as_mut().unwrap(), &self.noa).await?;
                     // can we fix this long call?
                     self.set.as_mut().unwrap().get_opt_mut(id.clone()).unwrap().set_value(OptValue::from_bool(ret));
                 }
@@ -1288,186 +2138,258 @@ impl<S, G> PreParser<S, G>
         }
         Ok(true)
     }
-}
-
-impl<S, G> Default for PreParser<S, G>
-    where S: Set, G: IdGenerator {
-    fn default() -> Self {
-        Self::new(G::default())
-    }
-}
 
-#[async_trait(?Send)]
-impl<S, G> Parser<S, G> for PreParser<S, G>
-    where S: Set, G: IdGenerator {
+    /// Process the current argument of `iter`, return true if it matched an option.
     #[cfg(not(feature="async"))]
-    fn parse(&mut self, iter: &mut dyn IndexIterator) -> Result<Option<bool>> {
-        if self.set.is_none() {
-            return Ok(None);
-        }
+    fn parse_arg(&mut self, iter: &mut dyn IndexIterator) -> Result<bool> {
         let opt_order = [
             GenStyle::GS_Equal_With_Value,
             GenStyle::GS_Argument,
             GenStyle::GS_Boolean,
-            GenStyle::GS_Mutliple_Option,
             GenStyle::GS_Embedded_Value,
+            GenStyle::GS_Mutliple_Option,
         ];
+        let mut matched = false;
+        let mut like_opt = false;
 
-        self.pre_check()?;
-        debug!("---- In PreParser, start process option");
-        while ! iter.reach_end() {
-            let mut matched = false;
+        iter.fill_current_and_next();
+        self.argument_matched = false;
+        debug!("**** ArgIterator [{:?}, {:?}]", iter.current(), iter.next());
 
-            iter.fill_current_and_next();
-            self.argument_matched = false;
-            debug!("**** ArgIterator [{:?}, {:?}]", iter.current(), iter.next());
+        // the standalone `--` terminate the option processing
+        if is_terminator(iter.current()) {
+            debug!("---- In PreParser, got terminator, left arguments are non-option");
+            self.noa.extend(take_left_arguments(iter));
+            return Ok(false);
+        }
 
-            if let Ok(arg) = iter.parse(self.get_prefix()) {
-                debug!("parse ... {:?}", arg);
-                for opt_style in &opt_order {
-                    if ! matched {
-                        let multiple_ctx = opt_style.gen_opt(&arg, iter.next());
+        if let Ok(mut arg) = iter.parse(self.get_prefix()) {
+            let group = resolve_argument(self.set.as_ref().unwrap(), &mut arg, true)?;
+            debug!("parse ... {:?}", arg);
+            like_opt = is_option_name(&arg);
+            for opt_style in &opt_order {
+                let multiple_ctx = opt_style.gen_opt_with_group(&arg, iter.next(), group);
 
-                        if multiple_ctx.len() > 0 {
-                            let mut cp = Box::new(SequenceProc::from(self.msg_id_gen.next_id()));
+                if multiple_ctx.len() > 0 {
+                    let mut cp = Box::new(SequenceProc::from(self.msg_id_gen.next_id()));
 
-                            for ctx in multiple_ctx {
-                                cp.app_ctx(ctx);
-                            }
+                    for ctx in multiple_ctx {
+                        cp.app_ctx(ctx);
+                    }
 
-                            matched = self.publish(cp)?;
-                        }
+                    self.record.gen_style = Some(format!("{:?}", opt_style));
+                    self.record.gen_tokens = iter.current().iter().chain(iter.next().iter()).cloned().collect();
+                    matched = self.publish(cp)?;
+
+                    // the first matched style wins, skip the other styles
+                    if matched {
+                        break;
                     }
                 }
             }
+        }
 
-            // If next argument matched, skip it
-            if matched && self.argument_matched {
-                iter.skip();
-            }
-            if !matched {
-                if let Some(arg) = iter.current() {
+        // If next argument matched, skip it
+        if matched && self.argument_matched {
+            iter.skip();
+        }
+        if !matched {
+            if let Some(arg) = iter.current() {
+                let suppressed = parser_default_unknown_check(self.unknown_handler.as_mut(), self.strict, like_opt, arg)?;
+
+                if suppressed {
+                    debug!("---- In PreParser, unknown option {} suppressed", arg);
+                }
+                else if like_opt {
+                    self.unknown_opts.push(arg.clone());
+                }
+                else {
                     self.noa.push(arg.clone());
                 }
             }
-
-            iter.skip();
         }
 
-        self.check_opt()?;
+        iter.skip();
+        Ok(matched)
+    }
 
-        let noa_total = self.noa().len();
+    /// Process the current argument of `iter`, return true if it matched an option.
+    #[cfg(feature="async")]
+    async fn parse_arg(&mut self, iter: &mut dyn IndexIterator) -> Result<bool> {
+        let opt_order = [
+            GenStyle::GS_Equal_With_Value,
+            GenStyle::GS_Argument,
+            GenStyle::GS_Boolean,
+            GenStyle::GS_Embedded_Value,
+            GenStyle::GS_Mutliple_Option,
+        ];
+        let mut matched = false;
+        let mut like_opt = false;
 
-        // process cmd and pos
-        if noa_total > 0 {
-            debug!("---- In PreParser, start process {:?}", GenStyle::GS_Non_Cmd);
-            let non_opt_cmd = GenStyle::GS_Non_Cmd.gen_nonopt(&self.noa()[0], noa_total as u64, 1);
+        iter.fill_current_and_next();
+        self.argument_matched = false;
+        debug!("**** ArgIterator [{:?}, {:?}]", iter.current(), iter.next());
 
-            if non_opt_cmd.len() > 0 {
-                let mut cp = Box::new(SingleCtxProc::from(self.msg_id_gen.next_id()));
+        // the standalone `--` terminate the option processing
+        if is_terminator(iter.current()) {
+            debug!("---- In PreParser, got terminator, left arguments are non-option");
+            self.noa.extend(take_left_arguments(iter));
+            return Ok(false);
+        }
 
-                for non_opt in non_opt_cmd {
-                    cp.app_ctx(non_opt);
-                }
-                self.publish(cp)?;
-            }
+        if let Ok(mut arg) = iter.parse(self.get_prefix()).await {
+            let group = resolve_argument(self.set.as_ref().unwrap(), &mut arg, true)?;
+            debug!("parse ... {:?}", arg);
+            like_opt = is_option_name(&arg);
+            for opt_style in &opt_order {
+                let multiple_ctx = opt_style.gen_opt_with_group(&arg, iter.next(), group);
 
-            debug!("---- In PreParser, start process {:?}", GenStyle::GS_Non_Pos);
-            for index in 1 ..= noa_total {
-                let non_opt_pos = GenStyle::GS_Non_Pos.gen_nonopt(&self.noa()[index - 1], noa_total as u64, index as u64);
+                if multiple_ctx.len() > 0 {
+                    let mut cp = Box::new(SequenceProc::from(self.msg_id_gen.next_id()));
 
-                if non_opt_pos.len() > 0  {
-                    let mut cp = Box::new(SingleCtxProc::from(self.msg_id_gen.next_id()));
+                    for ctx in multiple_ctx {
+                        cp.app_ctx(ctx);
+                    }
 
-                    for non_opt in non_opt_pos {
-                        cp.app_ctx(non_opt);
+                    self.record.gen_style = Some(format!("{:?}", opt_style));
+                    self.record.gen_tokens = iter.current().iter().chain(iter.next().iter()).cloned().collect();
+                    matched = self.publish(cp).await?;
+
+                    // the first matched style wins, skip the other styles
+                    if matched {
+                        break;
                     }
-                    self.publish(cp)?;
                 }
             }
         }
 
-        self.check_nonopt()?;
-
-        debug!("---- In PreParser, start process {:?}", GenStyle::GS_Non_Main);
-        let non_opt_main = GenStyle::GS_Non_Main.gen_nonopt(&String::new(), 0, 0);
-
-        if non_opt_main.len() > 0 {
-            let mut cp = Box::new(SingleCtxProc::from(self.msg_id_gen.next_id()));
-
-            for main in non_opt_main {
-                cp.app_ctx(main);
-            }
-            self.publish(cp)?;
+        // If next argument matched, skip it
+        if matched && self.argument_matched {
+            iter.skip();
         }
+        if !matched {
+            if let Some(arg) = iter.current() {
+                let suppressed = parser_default_unknown_check(self.unknown_handler.as_mut(), self.strict, like_opt, arg)?;
 
-        self.check_other()?;
+                if suppressed {
+                    debug!("---- In PreParser, unknown option {} suppressed", arg);
+                }
+                else if like_opt {
+                    self.unknown_opts.push(arg.clone());
+                }
+                else {
+                    self.noa.push(arg.clone());
+                }
+            }
+        }
 
-        Ok(Some(true))
+        iter.skip();
+        Ok(matched)
     }
+}
 
-    #[cfg(feature="async")]
-    async fn parse(&mut self, iter: &mut dyn IndexIterator) -> Result<Option<bool>> {
+impl<S, G> Default for PreParser<S, G>
+    where S: Set, G: IdGenerator {
+    fn default() -> Self {
+        Self::new(G::default())
+    }
+}
+
+#[async_trait(?Send)]
+impl<S, G> Parser<S, G> for PreParser<S, G>
+    where S: Set, G: IdGenerator {
+    #[cfg(not(feature="async"))]
+    fn parse(&mut self, iter: &mut dyn IndexIterator) -> Result<Option<bool>> {
         if self.set.is_none() {
             return Ok(None);
         }
-        let opt_order = [
-            GenStyle::GS_Equal_With_Value,
-            GenStyle::GS_Argument,
-            GenStyle::GS_Boolean,
-            GenStyle::GS_Mutliple_Option,
-            GenStyle::GS_Embedded_Value,
-        ];
-
-        self.pre_check().await?;
+        self.pre_check()?;
         debug!("---- In PreParser, start process option");
         while ! iter.reach_end() {
-            let mut matched = false;
+            self.parse_arg(iter)?;
+        }
 
-            iter.fill_current_and_next();
-            self.argument_matched = false;
-            debug!("**** ArgIterator [{:?}, {:?}]", iter.current(), iter.next());
+        apply_env_values(self.set.as_mut().unwrap())?;
+        if self.interpolate {
+            interpolate_values(self.set.as_mut().unwrap())?;
+        }
+        self.check_opt()?;
+        check_max_positionals(self.max_positionals, self.noa.len())?;
 
-            if let Ok(arg) = iter.parse(self.get_prefix()).await {
-                debug!("parse ... {:?}", arg);
-                for opt_style in &opt_order {
-                    if ! matched {
-                        let multiple_ctx = opt_style.gen_opt(&arg, iter.next());
+        let noa_total = self.noa().len();
 
-                        if multiple_ctx.len() > 0 {
-                            let mut cp = Box::new(SequenceProc::from(self.msg_id_gen.next_id()));
+        // process cmd and pos
+        if noa_total > 0 {
+            debug!("---- In PreParser, start process {:?}", GenStyle::GS_Non_Cmd);
+            let non_opt_cmd = GenStyle::GS_Non_Cmd.gen_nonopt(&self.noa()[0], noa_total as u64, 1);
 
-                            for ctx in multiple_ctx {
-                                cp.app_ctx(ctx);
-                            }
+            if non_opt_cmd.len() > 0 {
+                let mut cp = Box::new(SingleCtxProc::from(self.msg_id_gen.next_id()));
 
-                            matched = self.publish(cp).await?;
-                        }
-                    }
+                for non_opt in non_opt_cmd {
+                    cp.app_ctx(non_opt);
                 }
+                self.publish(cp)?;
             }
 
-            // If next argument matched, skip it
-            if matched && self.argument_matched {
-                iter.skip();
-            }
-            if !matched {
-                if let Some(arg) = iter.current() {
-                    self.noa.push(arg.clone());
+            debug!("---- In PreParser, start process {:?}", GenStyle::GS_Non_Pos);
+            for index in 1 ..= noa_total {
+                let non_opt_pos = GenStyle::GS_Non_Pos.gen_nonopt(&self.noa()[index - 1], noa_total as u64, index as u64);
+
+                if non_opt_pos.len() > 0  {
+                    let mut cp = Box::new(SingleCtxProc::from(self.msg_id_gen.next_id()));
+
+                    for non_opt in non_opt_pos {
+                        cp.app_ctx(non_opt);
+                    }
+                    self.publish(cp)?;
                 }
             }
+        }
 
-            iter.skip();
+        self.check_nonopt()?;
+
+        debug!("---- In PreParser, start process {:?}", GenStyle::GS_Non_Main);
+        let non_opt_main = GenStyle::GS_Non_Main.gen_nonopt(&String::new(), 0, 0);
+
+        if non_opt_main.len() > 0 {
+            let mut cp = Box::new(SingleCtxProc::from(self.msg_id_gen.next_id()));
+
+            for main in non_opt_main {
+                cp.app_ctx(main);
+            }
+            self.publish(cp)?;
+        }
+
+        self.check_other()?;
+
+        Ok(Some(true))
+    }
+
+    #[cfg(feature="async")]
+    async fn parse(&mut self, iter: &mut dyn IndexIterator) -> Result<Option<bool>> {
+        if self.set.is_none() {
+            return Ok(None);
+        }
+        self.pre_check()?;
+        debug!("---- In PreParser, start process option");
+        while ! iter.reach_end() {
+            self.parse_arg(iter).await?;
         }
 
+        apply_env_values(self.set.as_mut().unwrap())?;
+        if self.interpolate {
+            interpolate_values(self.set.as_mut().unwrap())?;
+        }
         self.check_opt()?;
+        check_max_positionals(self.max_positionals, self.noa.len())?;
 
         let noa_total = self.noa().len();
 
         // process cmd and pos
         if noa_total > 0 {
             debug!("---- In PreParser, start process {:?}", GenStyle::GS_Non_Cmd);
-            let non_opt_cmd = GenStyle::GS_Non_Cmd.gen_nonopt(&self.noa()[0], noa_total as i64, 1);
+            let non_opt_cmd = GenStyle::GS_Non_Cmd.gen_nonopt(&self.noa()[0], noa_total as u64, 1);
 
             if non_opt_cmd.len() > 0 {
                 let mut cp = Box::new(SingleCtxProc::from(self.msg_id_gen.next_id()));
@@ -1480,7 +2402,7 @@ impl<S, G> Parser<S, G> for PreParser<S, G>
 
             debug!("---- In PreParser, start process {:?}", GenStyle::GS_Non_Pos);
             for index in 1 ..= noa_total {
-                let non_opt_pos = GenStyle::GS_Non_Pos.gen_nonopt(&self.noa()[index - 1], noa_total as i64, index as i64);
+                let non_opt_pos = GenStyle::GS_Non_Pos.gen_nonopt(&self.noa()[index - 1], noa_total as u64, index as u64);
 
                 if non_opt_pos.len() > 0  {
                     let mut cp = Box::new(SingleCtxProc::from(self.msg_id_gen.next_id()));
@@ -1520,6 +2442,70 @@ impl<S, G> Parser<S, G> for PreParser<S, G>
         self.callbacks.insert(id, callback);
     }
 
+    fn add_transform(&mut self, id: Identifier, transform: ValueTransform) {
+        self.transforms.entry(id).or_insert(vec![]).push(transform);
+    }
+
+    fn add_rule(&mut self, rule: GroupRule) {
+        self.rules.push(rule);
+    }
+
+    fn add_exclusive_group(&mut self, ids: &[Identifier]) {
+        self.exclusive_groups.push(ids.to_vec());
+    }
+
+    fn add_requires(&mut self, id: Identifier, dependency: Identifier) {
+        self.requires.entry(id).or_insert(vec![]).push(dependency);
+    }
+
+    fn validation_report(&self) -> ValidationReport {
+        match self.set.as_ref() {
+            Some(_) => ValidationReport::from_rules(&self.record.matched_styles, &self.rules),
+            None => ValidationReport::new(),
+        }
+    }
+
+    fn trace(&self) -> ParseTrace {
+        ParseTrace::from_set(self.set.as_ref().unwrap(), &self.record.matched_styles)
+    }
+
+    fn occurrence(&self, id: Identifier) -> u64 {
+        self.record.matched_styles.get(&id).map(|v| v.len() as u64).unwrap_or(0)
+    }
+
+    fn last_matched_option(&self) -> Option<Identifier> {
+        self.record.last_matched
+    }
+
+    fn set_max_positionals(&mut self, max: Option<usize>) {
+        self.max_positionals = max;
+    }
+
+    fn set_unknown_handler(&mut self, handler: Box<UnknownHandlerFn>) {
+        self.unknown_handler = Some(UnknownHandler::new(handler));
+    }
+
+    fn set_audit(&mut self, audit: bool) {
+        self.audit = audit;
+    }
+
+    fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+
+    fn set_interpolate(&mut self, interpolate: bool) {
+        self.interpolate = interpolate;
+    }
+
+    fn audit(&self) -> Vec<String> {
+        parser_default_audit(self.set.as_ref().unwrap(), &self.callbacks, &self.record.matched_styles)
+    }
+
+    #[cfg(feature="profile")]
+    fn profile_report(&self) -> ProfileReport {
+        self.record.profile.clone()
+    }
+
     fn publish_to(&mut self, set: S) {
         self.set = Some(set);
     }
@@ -1553,13 +2539,31 @@ impl<S, G> Parser<S, G> for PreParser<S, G>
     }
 
     fn check_other(&self) -> Result<bool> {
-        Ok(true)
+        if self.audit {
+            self.audit();
+        }
+        parser_default_exclusive_check(self.set.as_ref().unwrap(), &self.exclusive_groups, &self.record.matched_styles)?;
+        parser_default_requires_check(self.set.as_ref().unwrap(), &self.requires, &self.record.matched_styles)
     }
 
-    fn reset(&mut self) {
+    fn reset_values(&mut self) {
         self.noa.clear();
-        self.set.as_mut().unwrap().reset();
+        self.record.clear();
+        self.unknown_opts.clear();
         self.argument_matched = false;
+        if let Some(set) = self.set.as_mut() {
+            set.reset();
+        }
+    }
+
+    fn rearm_callbacks(&mut self) {
+        if let Some(set) = self.set.as_mut() {
+            for id in self.callbacks.keys() {
+                if let Some(opt) = set.get_opt_mut(*id) {
+                    opt.set_need_invoke(true);
+                }
+            }
+        }
     }
 }
 
@@ -1572,18 +2576,16 @@ impl<S, G> Publisher<Box<dyn Proc>> for PreParser<S, G>
 
         debug!("Receive msg<{:?}> => {:?}", &proc.id(), &proc);
 
-        for index in 0 .. self.cached_infos.len() {
+        self.record.begin();
+        for index in self.info_index.candidates(proc.as_ref()) {
             let info = self.cached_infos.get_mut(index).unwrap();
             let opt = self.set.as_mut().unwrap().get_opt_mut(info.id()).unwrap(); // id always exist, so just unwrap
-            let res = proc.process(opt).unwrap_or(None); // ignore error
-            let need_invoke = opt.is_need_invoke();
-            let callback_type = opt.callback_type();
-
-            if let Some(index)  = res {
-                if need_invoke {
-                    let id = info.id();
-                    
-                    opt.set_need_invoke(false);
+            let (id, transforms) = (info.id(), &mut self.transforms);
+            let res = proc.process_with(opt, &mut |value| apply_transform(transforms, &id, value)).unwrap_or(None); // ignore error;
+
+            self.record.record(proc.as_ref(), opt, res.is_some());
+            if let Some(index) = res {
+                if let Some(callback_type) = take_callback(opt) {
                     self.invoke_callback(&id, callback_type, index)?;
                 }
             }
@@ -1592,6 +2594,7 @@ impl<S, G> Publisher<Box<dyn Proc>> for PreParser<S, G>
                 break;
             }
         }
+        self.record.finish(self.set.as_mut().unwrap(), proc.as_ref());
         if proc.is_matched() && proc.is_need_argument() {
             self.set_argument_matched();
         }
@@ -1605,18 +2608,16 @@ impl<S, G> Publisher<Box<dyn Proc>> for PreParser<S, G>
 
         debug!("Receive msg<{:?}> => {:?}", &proc.id(), &proc);
 
-        for index in 0 .. self.cached_infos.len() {
+        self.record.begin();
+        for index in self.info_index.candidates(proc.as_ref()) {
             let info = self.cached_infos.get_mut(index).unwrap();
             let opt = self.set.as_mut().unwrap().get_opt_mut(info.id()).unwrap(); // id always exist, so just unwrap
-            let res = proc.process(opt).await.unwrap_or(false);
-            let need_invoke = opt.is_need_invoke();
-            let callback_type = opt.callback_type();
-
-            if let Some(index)  = res {
-                if need_invoke {
-                    let id = info.id();
-                    
-                    opt.set_need_invoke(false);
+            let (id, transforms) = (info.id(), &mut self.transforms);
+            let res = proc.process_with(opt, &mut |value| apply_transform(transforms, &id, value)).await.unwrap_or(None); // ignore error;
+
+            self.record.record(proc.as_ref(), opt, res.is_some());
+            if let Some(index) = res {
+                if let Some(callback_type) = take_callback(opt) {
                     self.invoke_callback(&id, callback_type, index).await?;
                 }
             }
@@ -1625,6 +2626,7 @@ impl<S, G> Publisher<Box<dyn Proc>> for PreParser<S, G>
                 break;
             }
         }
+        self.record.finish(self.set.as_mut().unwrap(), proc.as_ref());
         if proc.is_matched() && proc.is_need_argument() {
             self.set_argument_matched();
         }
@@ -1633,11 +2635,131 @@ impl<S, G> Publisher<Box<dyn Proc>> for PreParser<S, G>
     }
 
     fn reg_subscriber(&mut self, info: Box<dyn Info>) {
+        self.info_index.insert(self.cached_infos.len(), info.as_ref());
         self.cached_infos.push(info);
     }
 
     fn clean(&mut self) {
         self.cached_infos.clear();
+        self.info_index.clear();
+    }
+}
+
+/// ParserBuilder hold the set and parser, it can register the option and callback at same time.
+/// ```no_run
+/// use getopt_rs::prelude::*;
+/// use getopt_rs::parser::ParserBuilder;
+/// use getopt_rs::tools::simple_value_callback;
+///
+/// let mut set = DefaultSet::new();
+///
+/// set.initialize_utils().unwrap();
+/// set.initialize_prefixs();
+///
+/// let mut builder = ParserBuilder::new(set, ForwardParser::<DefaultSet, DefaultIdGen>::default());
+///
+/// builder.add_opt("-c=int").unwrap()
+///        .set_callback(simple_value_callback(|opt| Ok(opt.value().as_int().is_some())))
+///        .commit()
+///        .unwrap();
+///
+/// let mut parser = builder.build();
+/// ```
+#[derive(Debug)]
+pub struct ParserBuilder<S, G, P>
+    where S: Set, G: IdGenerator, P: Parser<S, G> {
+    set: S,
+
+    parser: P,
+
+    marker: std::marker::PhantomData<G>,
+}
+
+impl<S, G, P> ParserBuilder<S, G, P>
+    where S: Set, G: IdGenerator, P: Parser<S, G> {
+    pub fn new(set: S, parser: P) -> Self {
+        Self {
+            set,
+            parser,
+            marker: std::marker::PhantomData,
+        }
+    }
+
+    pub fn set(&self) -> &S {
+        &self.set
+    }
+
+    pub fn set_mut(&mut self) -> &mut S {
+        &mut self.set
+    }
+
+    pub fn parser_mut(&mut self) -> &mut P {
+        &mut self.parser
+    }
+
+    /// Add the option to set, the callback of [`BuilderCommit`] will be registered to parser when committed.
+    pub fn add_opt(&mut self, opt: &str) -> Result<BuilderCommit<'_, S, G>> {
+        let commit = self.set.add_opt(opt)?;
+
+        Ok(BuilderCommit {
+            commit,
+            parser: &mut self.parser,
+            callback: None,
+        })
+    }
+
+    /// Subscribe the set to parser and return the parser.
+    pub fn build(self) -> P {
+        let mut parser = self.parser;
+
+        self.set.subscribe_from(&mut parser);
+        parser.publish_to(self.set);
+        parser
+    }
+}
+
+/// The [`Commit`](crate::set::Commit) created by [`ParserBuilder`], it can access all the method of `Commit`.
+#[derive(Debug)]
+pub struct BuilderCommit<'a, S, G>
+    where S: Set, G: IdGenerator {
+    commit: crate::set::Commit<'a>,
+
+    parser: &'a mut dyn Parser<S, G>,
+
+    callback: Option<OptCallback>,
+}
+
+impl<'a, S, G> BuilderCommit<'a, S, G>
+    where S: Set, G: IdGenerator {
+    pub fn set_callback(&mut self, callback: OptCallback) -> &mut Self {
+        self.callback = Some(callback);
+        self
+    }
+
+    /// Commit the option, and register the callback to parser if the option added.
+    pub fn commit(&mut self) -> Result<Identifier> {
+        let id = self.commit.commit()?;
+
+        if let Some(callback) = self.callback.take() {
+            self.parser.set_callback(id, callback);
+        }
+        Ok(id)
+    }
+}
+
+impl<'a, S, G> std::ops::Deref for BuilderCommit<'a, S, G>
+    where S: Set, G: IdGenerator {
+    type Target = crate::set::Commit<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.commit
+    }
+}
+
+impl<'a, S, G> std::ops::DerefMut for BuilderCommit<'a, S, G>
+    where S: Set, G: IdGenerator {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.commit
     }
 }
 
@@ -1657,9 +2779,47 @@ enum GenStyle {
     GS_Non_Main,
     GS_Non_Pos,
     GS_Non_Cmd,
+    GS_Type,
 }
 
+/// The constructor of [`Context`] used by [`GenStyle::gen_opt_with_group`].
+type ContextCtor = fn(String, String, Option<String>, Style, bool) -> Box<dyn Context>;
+
 impl GenStyle {
+    /// Same as [`gen_opt`](GenStyle::gen_opt), but the multiple style split the option group at `group`,
+    /// the option at `group` using the rest of argument or next argument as value, such as `-xvf archive`.
+    pub fn gen_opt_with_group(&self, arg: &Argument, next_argument: &Option<String>, group: Option<usize>) -> Vec<Box<dyn Context>> {
+        let ctor: ContextCtor = match self {
+            Self::GS_Mutliple_Option => |prefix, name, value, style, skip| Box::new(OptContext::new(prefix, name, value, style, skip)),
+            Self::GS_Delay_Mutliple_Option => |prefix, name, value, style, skip| Box::new(DelayContext::new(prefix, name, value, style, skip)),
+            _ => { return self.gen_opt(arg, next_argument); }
+        };
+
+        match (group, arg.get_prefix(), arg.get_name()) {
+            (Some(group), Some(prefix), Some(name)) => {
+                let (flags, rest) = name.split_at(group);
+                let mut rest = rest.chars();
+                let mut ret = vec![];
+
+                for char in flags.chars() {
+                    ret.push(ctor(prefix.clone(), String::from(char), None, Style::Multiple, false));
+                }
+                if let Some(char) = rest.next() {
+                    let value = rest.as_str();
+
+                    if value.is_empty() {
+                        ret.push(ctor(prefix.clone(), String::from(char), next_argument.clone(), Style::Argument, true));
+                    }
+                    else {
+                        ret.push(ctor(prefix.clone(), String::from(char), Some(value.to_owned()), Style::Argument, false));
+                    }
+                }
+                ret
+            }
+            _ => self.gen_opt(arg, next_argument),
+        }
+    }
+
     pub fn gen_opt(&self, arg: &Argument, next_argument: &Option<String>) -> Vec<Box<dyn Context>> {
         let mut ret: Vec<Box<dyn Context>> = vec![];
         let default_value = String::default();
@@ -1718,15 +2878,14 @@ impl GenStyle {
                 }
             }
             Self::GS_Boolean => {
-                if arg.get_value().is_none() {
-                    ret.push(Box::new(OptContext::new(
-                        arg.get_prefix().unwrap().clone(),
-                        arg.get_name().unwrap().clone(),
-                        None,
-                        Style::Boolean,
-                        false,
-                    )));
-                }
+                // the attached value such as `--debug=false` is parsed by option
+                ret.push(Box::new(OptContext::new(
+                    arg.get_prefix().unwrap().clone(),
+                    arg.get_name().unwrap().clone(),
+                    arg.get_value().cloned(),
+                    Style::Boolean,
+                    false,
+                )));
             }
             Self::GS_Delay_Equal_With_Value => {
                 if let Some(value) = arg.get_value() {
@@ -1781,21 +2940,29 @@ impl GenStyle {
                 }
             }
             Self::GS_Delay_Boolean => {
-                if arg.get_value().is_none() {
-                    ret.push(Box::new(DelayContext::new(
-                        arg.get_prefix().unwrap().clone(),
-                        arg.get_name().unwrap().clone(),
-                        None,
-                        Style::Boolean,
-                        false,
-                    )));
-                }
+                // the attached value such as `--debug=false` is parsed by option
+                ret.push(Box::new(DelayContext::new(
+                    arg.get_prefix().unwrap().clone(),
+                    arg.get_name().unwrap().clone(),
+                    arg.get_value().cloned(),
+                    Style::Boolean,
+                    false,
+                )));
             }
             _ => { }
         }
         ret
     }
 
+    pub fn gen_type(&self, type_name: &str) -> Vec<Box<dyn Context>> {
+        let mut ret: Vec<Box<dyn Context>> = vec![];
+
+        if let Self::GS_Type = self {
+            ret.push(Box::new(TypeContext::new(type_name)));
+        }
+        ret
+    }
+
     pub fn gen_nonopt(&self, noa: &String, total: u64, current: u64)-> Vec<Box<dyn Context>> {
         let mut ret: Vec<Box<dyn Context>> = vec![];
 
@@ -1815,37 +2982,478 @@ impl GenStyle {
     }
 }
 
-pub fn parse_default_pre_check(set: &dyn Set, callback_holder: &HashMap<Identifier, OptCallback>) -> Result<bool> {
-    for (id, callback) in callback_holder.iter() {
-        if let Some(opt) = set.get_opt(id.clone()) {
-            if opt.accept_callback_type(callback.to_callback_type()) {
-                return Ok(true)
+/// Generate the canonical command line from options of `set` and the non-option arguments `noa`.
+/// 
+/// The options are in the order of the [`Set`], and the value are joined with `=`,
+/// such as `--name=value`.
+/// The boolean option is written only when it is not equal to default, and it is true,
+/// or it is false for the deactivate style option.
+/// The non-option arguments are following the options,
+/// a terminator `--` will be insert if any of them look like an option.
+pub fn canonical_command_line(set: &dyn Set, noa: &[String]) -> Vec<String> {
+    let mut ret = vec![];
+
+    for opt in set.iter() {
+        if ! (opt.is_style(Style::Boolean) || opt.is_style(Style::Argument) || opt.is_style(Style::Multiple)) {
+            continue;
+        }
+        if ! opt.has_value() {
+            continue;
+        }
+        let name = format!("{}{}", opt.prefix(), opt.name());
+
+        match opt.value() {
+            OptValue::Int(v) => ret.push(format!("{}={}", name, v)),
+            OptValue::Uint(v) => {
+                if opt.is_style(Style::Argument) {
+                    ret.push(format!("{}={}", name, v));
+                }
+                else {
+                    // such as count option
+                    for _ in 0 .. *v {
+                        ret.push(name.clone());
+                    }
+                }
             }
-            else {
-                return Err(Error::InvalidCallbackType(format!("{:?}", id), format!("{:?}", callback.to_callback_type())))
+            OptValue::Flt(v) => ret.push(format!("{}={}", name, v)),
+            OptValue::Str(v) => ret.push(format!("{}={}", name, v)),
+            OptValue::Bool(v) => {
+                // the false value can only be written as a deactivate style option
+                if *v != opt.is_deactivate_style() && opt.value() != opt.default_value() {
+                    ret.push(name);
+                }
             }
-        }
-        else {
-            return Err(Error::InvaldOptionId(format!("{:?}", id)))
+            OptValue::Array(v) => {
+                for item in v {
+                    ret.push(format!("{}={}", name, item));
+                }
+            }
+            OptValue::Map(v) => {
+                for (key, value) in v {
+                    ret.push(format!("{}={}={}", name, key, value));
+                }
+            }
+            _ => { }
         }
     }
-    Ok(true)
+    if noa.iter().any(|v| parse_argument_impl(&Some(v.clone()), set.get_prefix()).is_ok()) {
+        ret.push(String::from(TERMINATOR));
+    }
+    ret.extend(noa.iter().cloned());
+    ret
 }
 
-/// This function will call function [`check`](crate::opt::Type::check)
-/// of options which type is [`Style::Boolean`], [`Style::Argument`]
-/// or [`Style::Multiple`]
-pub fn parser_default_opt_check(set: &dyn Set) -> Result<bool> {
-        for opt in set.iter() {
-            if opt.as_ref().is_style(Style::Boolean) 
-            || opt.as_ref().is_style(Style::Argument) 
-            || opt.as_ref().is_style(Style::Multiple) {
-                opt.check()?;
-            }
-        }
-        Ok(true)
+/// Convert the `value` to JSON, the [`OptValue::Any`] is convert to `null`.
+#[cfg(feature="serde")]
+fn opt_value_to_json(value: &OptValue) -> serde_json::Value {
+    match value {
+        OptValue::Int(v) => serde_json::json!(v),
+        OptValue::Uint(v) => serde_json::json!(v),
+        OptValue::Flt(v) => serde_json::json!(v),
+        OptValue::Str(v) => serde_json::json!(v),
+        OptValue::Bool(v) => serde_json::json!(v),
+        OptValue::Array(v) => serde_json::json!(v),
+        OptValue::Map(v) => serde_json::json!(v),
+        _ => serde_json::Value::Null,
+    }
 }
 
+/// Set the value of options from their environment variable, see [`Set::set_env`].
+/// The value is parsed by the option, and only used if the option has no value.
+pub fn apply_env_values(set: &mut dyn Set) -> Result<bool> {
+    let mut envs = vec![];
+
+    for opt in set.iter() {
+        if let Some(env) = set.get_env(opt.id()) {
+            if ! opt.has_value() {
+                if let Ok(value) = std::env::var(env) {
+                    envs.push((opt.id(), value));
+                }
+            }
+        }
+    }
+    for (id, value) in envs {
+        if let Some(opt) = set.get_opt_mut(id) {
+            let value = opt.parse_value(&value)?;
+
+            opt.replace_value(value);
+        }
+    }
+    Ok(true)
+}
+
+/// Resolve the `${name}` placeholder in the string value of options, the `name` is the name of other option,
+/// or the prefix and name such as `${--name}`.
+/// The placeholder reference an unknown option will be kept.
+/// Return [`Error::AmbiguousOption`] if the `name` reference more than one option with different prefix.
+/// Return [`Error::InterpolationCycle`] if the options reference each other.
+pub fn interpolate_values(set: &mut dyn Set) -> Result<bool> {
+    let mut raws: HashMap<String, String> = HashMap::new();
+    let mut names: HashMap<String, Vec<String>> = HashMap::new();
+    let mut resolved: HashMap<String, String> = HashMap::new();
+
+    for opt in set.iter() {
+        if let Some(value) = opt.value().as_str() {
+            let key = format!("{}{}", opt.prefix(), opt.name());
+
+            names.entry(opt.name().to_owned()).or_insert(vec![]).push(key.clone());
+            raws.insert(key, value.clone());
+        }
+    }
+    for key in raws.keys() {
+        interpolate_value(key, &raws, &names, &mut resolved, &mut vec![])?;
+    }
+    for opt in set.iter_mut() {
+        if let Some(value) = resolved.get(&format!("{}{}", opt.prefix(), opt.name())) {
+            if opt.value().as_str() != Some(value) {
+                opt.set_value(OptValue::from_str(value.clone()));
+            }
+        }
+    }
+    Ok(true)
+}
+
+/// Return the prefix and name of option referenced by placeholder `ref_name`.
+fn interpolate_key<'a>(ref_name: &'a str, raws: &HashMap<String, String>, names: &'a HashMap<String, Vec<String>>) -> Result<Option<&'a str>> {
+    if raws.contains_key(ref_name) {
+        return Ok(Some(ref_name));
+    }
+    match names.get(ref_name) {
+        Some(keys) if keys.len() > 1 => {
+            let mut keys = keys.clone();
+
+            keys.sort();
+            Err(Error::AmbiguousOption(ref_name.to_owned(), keys))
+        }
+        Some(keys) => Ok(keys.first().map(|v| v.as_str())),
+        None => Ok(None),
+    }
+}
+
+fn interpolate_value(name: &str, raws: &HashMap<String, String>, names: &HashMap<String, Vec<String>>, resolved: &mut HashMap<String, String>, stack: &mut Vec<String>) -> Result<String> {
+    if let Some(value) = resolved.get(name) {
+        return Ok(value.clone());
+    }
+    if stack.iter().any(|v| v == name) {
+        stack.push(name.to_owned());
+        return Err(Error::InterpolationCycle(stack.join(" -> ")));
+    }
+    let mut ret = String::new();
+    let mut left = raws[name].as_str();
+
+    stack.push(name.to_owned());
+    while let Some(begin) = left.find("${") {
+        ret.push_str(&left[.. begin]);
+        left = &left[begin ..];
+        match left.find('}') {
+            Some(end) => {
+                match interpolate_key(&left[2 .. end], raws, names)? {
+                    Some(key) => {
+                        ret.push_str(&interpolate_value(key, raws, names, resolved, stack)?);
+                    }
+                    None => {
+                        ret.push_str(&left[.. end + 1]);
+                    }
+                }
+                left = &left[end + 1 ..];
+            }
+            None => {
+                break;
+            }
+        }
+    }
+    ret.push_str(left);
+    stack.pop();
+    resolved.insert(name.to_owned(), ret.clone());
+    Ok(ret)
+}
+
+/// Resolve the abbreviation and `no-` prefix of `arg` before it matched with the options,
+/// return the position of option need an argument in the option group, see [`find_group_argument`].
+/// The ambiguous abbreviation error is ignored if `ignore_error` is true.
+pub fn resolve_argument(set: &dyn Set, arg: &mut Argument, ignore_error: bool) -> Result<Option<usize>> {
+    if let Err(e) = resolve_abbrev(set, arg) {
+        if ! ignore_error {
+            return Err(e);
+        }
+    }
+    resolve_no_prefix(set, arg);
+    Ok(find_group_argument(set, arg))
+}
+
+/// Return the position of first option which need an argument in the option group, such as `f` of `-xvf`.
+/// The options before it must support the multiple style, such as boolean option.
+/// Return None if the argument has a value, or it is not a valid option group.
+pub fn find_group_argument(set: &dyn Set, arg: &Argument) -> Option<usize> {
+    let (prefix, name) = (arg.get_prefix()?, arg.get_name()?);
+
+    if arg.get_value().is_some() {
+        return None;
+    }
+    for (count, (pos, char)) in name.char_indices().enumerate() {
+        let opt = set.get_opt_by_name(prefix, &String::from(char))?;
+
+        if opt.is_style(Style::Multiple) {
+            continue;
+        }
+        if count > 0 && opt.is_style(Style::Argument) {
+            return Some(pos);
+        }
+        return None;
+    }
+    None
+}
+
+/// Replace the name of `arg` with the option name it is an unambiguous prefix of,
+/// if the set [`allow abbreviation`](Set::set_allow_abbrev).
+/// Only the multi-character name of option with multi-character prefix can be abbreviated.
+/// Return [`Error::AmbiguousOption`] if the name is prefix of more than one option.
+pub fn resolve_abbrev(set: &dyn Set, arg: &mut Argument) -> Result<bool> {
+    if ! set.is_allow_abbrev() {
+        return Ok(false);
+    }
+
+    let (prefix, name) = match (arg.get_prefix(), arg.get_name()) {
+        (Some(prefix), Some(name)) if prefix.len() > 1 && ! name.is_empty() => (prefix.clone(), name.clone()),
+        _ => { return Ok(false); }
+    };
+
+    if set.get_opt_by_name(&prefix, &name).is_some() {
+        return Ok(false);
+    }
+
+    let mut candidates: Vec<String> = vec![];
+
+    for opt in set.iter() {
+        if opt.is_style(Style::Boolean) || opt.is_style(Style::Argument) {
+            let mut names = vec![(opt.prefix(), opt.name())];
+
+            if let Some(alias) = opt.alias() {
+                names.extend(alias.iter().map(|(p, n)| (p.as_str(), n.as_str())));
+            }
+            for (opt_prefix, opt_name) in names {
+                if opt_prefix == prefix
+                    && opt_name.len() > 1
+                    && opt_name.starts_with(name.as_str())
+                    && ! candidates.iter().any(|v| v == opt_name) {
+                    candidates.push(opt_name.to_owned());
+                }
+            }
+        }
+    }
+    match candidates.len() {
+        0 => Ok(false),
+        1 => {
+            arg.set_name(candidates.pop());
+            Ok(true)
+        }
+        _ => Err(Error::AmbiguousOption(
+            format!("{}{}", prefix, name),
+            candidates.iter().map(|v| format!("{}{}", prefix, v)).collect(),
+        ))
+    }
+}
+
+/// Rewrite the negation form `--no-name` to `--name=false` if `name` is a boolean option,
+/// return true if the argument is rewritten.
+/// 
+/// It does nothing if [`Set::is_enable_no_prefix`] is false,
+/// or the argument already has a value, or any option named `no-name`.
+pub fn resolve_no_prefix(set: &dyn Set, arg: &mut Argument) -> bool {
+    if ! set.is_enable_no_prefix() || arg.get_value().is_some() {
+        return false;
+    }
+
+    let (prefix, name) = match (arg.get_prefix(), arg.get_name()) {
+        (Some(prefix), Some(name)) => (prefix.clone(), name.clone()),
+        _ => { return false; }
+    };
+
+    if set.get_opt_by_name(&prefix, &name).is_some() {
+        return false;
+    }
+    if let Some(name) = name.strip_prefix("no-") {
+        if let Some(opt) = set.get_opt_by_name(&prefix, name) {
+            if opt.type_name() == "bool" {
+                arg.set_name(Some(name.to_owned()));
+                arg.set_value(Some(String::from("false")));
+                return true;
+            }
+        }
+    }
+    false
+}
+
+/// Return the id of options which type is `type_name` in `set`, the options are matched by [`TypeContext`].
+/// 
+/// It can be used for bulk operations on options of same type, such as all the `array` options.
+pub fn find_by_type(set: &mut dyn Set, type_name: &str) -> Result<Vec<Identifier>> {
+    let mut ret = vec![];
+
+    for opt in set.iter_mut() {
+        for mut ctx in GenStyle::GS_Type.gen_type(type_name) {
+            if ctx.match_opt(opt.as_ref()) && ctx.process(opt.as_mut())? {
+                ret.push(opt.id());
+            }
+        }
+    }
+    Ok(ret)
+}
+
+/// Return [`Error::TooManyPositionals`] if the count of non-option arguments exceeds the `max`.
+pub fn check_max_positionals(max: Option<usize>, got: usize) -> Result<bool> {
+    match max {
+        Some(max) if got > max => Err(Error::TooManyPositionals { max, got }),
+        _ => Ok(true),
+    }
+}
+
+/// Return the callback type of `opt` if the callback need be invoked,
+/// the invoke flag of option is cleared.
+fn take_callback(opt: &mut dyn Opt) -> Option<CallbackType> {
+    if opt.is_need_invoke() {
+        let callback_type = opt.callback_type();
+
+        opt.set_need_invoke(false);
+        Some(callback_type)
+    }
+    else {
+        None
+    }
+}
+
+/// Return the argument of the context matched `opt` in `proc`, such as `007` of `--count=007`,
+/// or empty string if it has no argument.
+pub fn matched_argument(proc: &dyn Proc, opt: &dyn Opt) -> String {
+    for index in 0 .. proc.len() {
+        if let Some(ctx) = proc.get_ctx(index) {
+            if ctx.is_matched() && ctx.match_opt(opt) {
+                return ctx.get_next_argument().clone().unwrap_or_default();
+            }
+        }
+    }
+    String::default()
+}
+
+/// Call the transforms of option `id` on the `value` in registration order.
+pub fn apply_transform(transforms: &mut HashMap<Identifier, Vec<ValueTransform>>, id: &Identifier, value: OptValue) -> Result<OptValue> {
+    let mut value = value;
+
+    if let Some(transforms) = transforms.get_mut(id) {
+        for transform in transforms.iter_mut() {
+            value = transform.transform(value)?;
+        }
+    }
+    Ok(value)
+}
+
+pub fn parse_default_pre_check(set: &dyn Set, callback_holder: &HashMap<Identifier, OptCallback>) -> Result<bool> {
+    for (id, callback) in callback_holder.iter() {
+        if let Some(opt) = set.get_opt(id.clone()) {
+            if callback.is_null() {
+                continue;
+            }
+            if ! opt.accept_callback_type(callback.to_callback_type()) {
+                return Err(Error::InvalidCallbackType(format!("{:?}", id), format!("{:?}", callback.to_callback_type())))
+            }
+        }
+        else {
+            return Err(Error::InvaldOptionId(format!("{:?}", id)))
+        }
+    }
+    Ok(true)
+}
+
+/// Keep the option name in `missing` if `ret` is an [`Error::ArgumentRequired`] and the option is the last argument,
+/// the option is treated as matched, and the [`Error::MissingArgument`] is raised by [`Parser::check_opt`].
+fn catch_missing_argument(ret: Result<bool>, is_last: bool, missing: &mut Option<String>) -> Result<bool> {
+    match ret {
+        Err(Error::ArgumentRequired(name)) if is_last => {
+            *missing = Some(name);
+            Ok(true)
+        }
+        ret => ret,
+    }
+}
+
+/// Call the `handler` with the argument look like an option but not matched any option,
+/// return true if the argument is suppressed by the `handler`.
+/// Return [`Error::UnknownOption`] in `strict` mode if the argument is not suppressed.
+pub fn parser_default_unknown_check(handler: Option<&mut UnknownHandler>, strict: bool, like_opt: bool, arg: &str) -> Result<bool> {
+    if ! like_opt {
+        return Ok(false);
+    }
+    let suppressed = handler.map_or(Ok(false), |handler| handler.handle(arg))?;
+
+    if ! suppressed && strict {
+        return Err(Error::UnknownOption(String::from(arg)));
+    }
+    Ok(suppressed)
+}
+
+/// This function will call function [`check`](crate::opt::Type::check)
+/// of options which type is [`Style::Boolean`], [`Style::Argument`]
+/// or [`Style::Multiple`]
+pub fn parser_default_opt_check(set: &dyn Set) -> Result<bool> {
+        for opt in set.iter() {
+            if opt.as_ref().is_style(Style::Boolean) 
+            || opt.as_ref().is_style(Style::Argument) 
+            || opt.as_ref().is_style(Style::Multiple) {
+                opt.check()?;
+            }
+        }
+        Ok(true)
+}
+
+/// Return [`Error::ConflictOption`] if more than one option of any group matched in `matched_styles`.
+pub fn parser_default_exclusive_check(set: &dyn Set, groups: &[Vec<Identifier>], matched_styles: &HashMap<Identifier, Vec<String>>) -> Result<bool> {
+    for group in groups.iter() {
+        if ! GroupRule::exclusive(group).check(matched_styles) {
+            let names: Vec<String> = group.iter()
+                                          .filter(|id| matched_styles.contains_key(id))
+                                          .filter_map(|id| set.get_opt(*id))
+                                          .map(|opt| format!("{}{}", opt.prefix(), opt.name()))
+                                          .collect();
+
+            return Err(Error::ConflictOption(names.join(", ")));
+        }
+    }
+    Ok(true)
+}
+
+/// Return [`Error::MissingDependency`] if an option matched in `matched_styles` but any of its dependencies not.
+pub fn parser_default_requires_check(set: &dyn Set, requires: &HashMap<Identifier, Vec<Identifier>>, matched_styles: &HashMap<Identifier, Vec<String>>) -> Result<bool> {
+    let name_of = |id: &Identifier| {
+        set.get_opt(*id).map_or_else(|| format!("{:?}", id), |opt| format!("{}{}", opt.prefix(), opt.name()))
+    };
+
+    for (id, dependencies) in requires.iter() {
+        for dependency in dependencies.iter() {
+            if ! GroupRule::requires(*id, *dependency).check(matched_styles) {
+                return Err(Error::MissingDependency(name_of(id), name_of(dependency)));
+            }
+        }
+    }
+    Ok(true)
+}
+
+/// Return the name of options has no callback in `callbacks` and not matched in `matched_styles`,
+/// and log them at `warn` level.
+pub fn parser_default_audit(set: &dyn Set, callbacks: &HashMap<Identifier, OptCallback>, matched_styles: &HashMap<Identifier, Vec<String>>) -> Vec<String> {
+    let mut ret = vec![];
+
+    for opt in set.iter() {
+        if opt.is_style(Style::Boolean) || opt.is_style(Style::Argument) || opt.is_style(Style::Multiple) {
+            if ! callbacks.contains_key(&opt.id()) && ! matched_styles.contains_key(&opt.id()) {
+                let name = format!("{}{}", opt.prefix(), opt.name());
+
+                warn!("Option `{}` has no callback and not matched, may be a typo of option name", name);
+                ret.push(name);
+            }
+        }
+    }
+    ret
+}
 
 pub fn parser_default_nonopt_check(set: &dyn Set) -> Result<bool> {
     const LEN: u64 = u64::MAX;
@@ -1862,9 +3470,14 @@ pub fn parser_default_nonopt_check(set: &dyn Set) -> Result<bool> {
             entry.push(opt.as_ref().id());
         } 
     }
-    let mut force_names= vec![];
-
-    for item in index_map.iter() {
+    let mut force_opts: Vec<&dyn Opt> = vec![];
+    let names = |opts: &Vec<&dyn Opt>| opts.iter().map(|opt| format!("{}{}", opt.prefix(), opt.name())).collect::<Vec<String>>();
+    let describes = |opts: &Vec<&dyn Opt>| opts.iter().map(|opt| opt.describe()).collect::<Vec<String>>();
+    let mut items: Vec<(&u64, &Vec<Identifier>)> = index_map.iter().collect();
+
+    // report the missing position in order
+    items.sort_by_key(|item| item.0);
+    for item in items {
         let mut valid = false;
 
         // first pos is a special position
@@ -1889,7 +3502,7 @@ pub fn parser_default_nonopt_check(set: &dyn Set) -> Result<bool> {
                         force_valid = force_valid || opt.has_value();
                     }
                 }
-                force_names.push(format!("`{}{}`", opt.prefix(), opt.name()));
+                force_opts.push(opt);
             }
 
             if cmd_count > 0 {
@@ -1905,7 +3518,10 @@ pub fn parser_default_nonopt_check(set: &dyn Set) -> Result<bool> {
             }
 
             if !valid {
-                return Err(Error::NonOptionForceRequired(force_names.join(" or ")));
+                if cmd_count == 0 && item.0 == &1 {
+                    return Err(Error::PositionalMissing { index: *item.0 as i64, name: names(&force_opts).join(" or ") });
+                }
+                return Err(Error::NonOptionForceRequired(describes(&force_opts).join("` or `")));
             }
         }
         else {
@@ -1913,13 +3529,16 @@ pub fn parser_default_nonopt_check(set: &dyn Set) -> Result<bool> {
                 let opt = set.get_opt(*id).unwrap();
                 
                 valid = valid || opt.check().unwrap_or(false);
-                force_names.push(format!("`{}{}`", opt.prefix(), opt.name()));
+                force_opts.push(opt);
             }
             if !valid {
-                return Err(Error::NonOptionForceRequired(force_names.join(" or ")));
+                if item.0 != &LEN {
+                    return Err(Error::PositionalMissing { index: *item.0 as i64, name: names(&force_opts).join(" or ") });
+                }
+                return Err(Error::NonOptionForceRequired(describes(&force_opts).join("` or `")));
             }
         }
-        force_names.clear();
+        force_opts.clear();
     }        
     Ok(true)
 }
@@ -2442,7 +4061,7 @@ mod tests {
                        .value().as_bool().unwrap());
             assert!(set.filter("debug").unwrap().find().unwrap()
                        .value().as_bool().unwrap());
-            assert_eq!(noa[1], String::from("download/sources"));
+            assert_eq!(noa[0], String::from("download/sources"));
             Ok(true)
         }
 
@@ -2487,8 +4106,7 @@ mod tests {
             parser.set_callback(id, 
                 OptCallback::from_main(Box::new(SimpleMainCallback::new(
                     move |set, noa| {
-                        assert_eq!(noa[0], String::from("-f"));
-                        assert_eq!(noa[1], String::from("download/sources"));
+                        assert_eq!(noa[0], String::from("download/sources"));
                         directory(set, noa)?;
                         Ok(true)
                     }
@@ -2519,8 +4137,10 @@ mod tests {
         parser.publish_to(set);
         let _ret = parser.parse(&mut ai).unwrap();
 
-        assert_eq!(parser.noa(), &vec![
+        assert_eq!(parser.unknown_opts(), &vec![
             String::from("-f"),
+        ]);
+        assert_eq!(parser.noa(), &vec![
             String::from("download/sources"),
             String::from("picture/pngs"),
             String::from("picture/jpgs"),
@@ -2548,4 +4168,2471 @@ mod tests {
                        .value().as_bool_or_null().unwrap_or(&false));
         
     }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_forwardparser_cmd_skip_unmatched_opt() {
+        let id = DefaultIdGen::default();
+        let mut set = DefaultSet::new();
+        let mut parser = ForwardParser::new(id);
+
+        set.initialize_utils().unwrap();
+        set.initialize_prefixs();
+
+        if let Ok(mut commit) = set.add_opt("-v=bool") {
+            commit.commit().unwrap();
+        }
+        let add_id = set.add_opt("add=cmd").unwrap().commit().unwrap();
+
+        let mut ai = ArgIterator::new();
+
+        ai.set_args(&mut ["-x", "add", "file"].iter().map(|&v|String::from(v)));
+
+        set.subscribe_from(&mut parser);
+        parser.publish_to(set);
+        parser.parse(&mut ai).unwrap();
+
+        assert_eq!(parser.noa(), &vec![String::from("-x"), String::from("add"), String::from("file")]);
+        assert_eq!(parser.get_opt(add_id).unwrap().value().as_bool(), Some(&true));
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_forwardparser_count_work() {
+        let test_cases = [
+            (vec!["-vvv"], 3),
+            (vec!["--verbose", "--verbose"], 2),
+            (vec!["-v"], 1),
+            (vec!["-v", "-vv", "--verbose"], 4),
+        ];
+
+        for (args, count) in test_cases.iter() {
+            let mut set = DefaultSet::new();
+            let mut parser = ForwardParser::new(DefaultIdGen::default());
+
+            set.initialize_utils().unwrap();
+            set.initialize_prefixs();
+
+            let mut commit = set.add_opt("-v=count").unwrap();
+
+            commit.add_alias("--", "verbose");
+            let id = commit.commit().unwrap();
+            let mut ai = ArgIterator::new();
+
+            ai.set_args(&mut args.iter().map(|&v|String::from(v)));
+            set.subscribe_from(&mut parser);
+            parser.publish_to(set);
+            parser.parse(&mut ai).unwrap();
+
+            assert_eq!(parser.get_opt(id).unwrap().value().as_uint(), Some(count));
+            assert!(parser.noa().is_empty());
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_terminator_work() {
+        let mut set = DefaultSet::new();
+        let mut parser = ForwardParser::new(DefaultIdGen::default());
+
+        set.initialize_utils().unwrap();
+        // `--` is registered as a prefix
+        set.initialize_prefixs();
+
+        let foo_id = set.add_opt("--foo=bool").unwrap().commit().unwrap();
+        let bar_id = set.add_opt("--bar=bool").unwrap().commit().unwrap();
+        let mut ai = ArgIterator::new();
+
+        ai.set_args(&mut ["--foo", "a", "--", "--bar", "b"].iter().map(|&v|String::from(v)));
+        set.subscribe_from(&mut parser);
+        parser.publish_to(set);
+        parser.parse(&mut ai).unwrap();
+
+        assert_eq!(parser.get_opt(foo_id).unwrap().value().as_bool(), Some(&true));
+        assert_eq!(parser.get_opt(bar_id).unwrap().value().as_bool(), Some(&false));
+        assert_eq!(parser.noa(), &vec![String::from("a"), String::from("--bar"), String::from("b")]);
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_canonical_command_line_work() {
+        fn parse(args: &[String]) -> Vec<String> {
+            let mut set = DefaultSet::new();
+            let mut parser = ForwardParser::new(DefaultIdGen::default());
+
+            set.initialize_utils().unwrap();
+            set.initialize_prefixs();
+            for opt in ["-c=int", "--name=str", "-d=bool", "-a=array", "-v=count", "-q=bool", "-n=bool/"].iter() {
+                set.add_opt(opt).unwrap().commit().unwrap();
+            }
+
+            let mut ai = ArgIterator::new();
+
+            ai.set_args(&mut args.iter().cloned());
+            set.subscribe_from(&mut parser);
+            parser.publish_to(set);
+            parser.parse(&mut ai).unwrap();
+            parser.canonical_command_line()
+        }
+
+        let args: Vec<String> = [
+            "file1", "-a", "x", "-vv", "-d", "-n", "--name", "foo", "-c=1", "-a=y", "--", "-file2",
+        ].iter().map(|&v|String::from(v)).collect();
+        let canonical = parse(&args);
+
+        assert_eq!(canonical, [
+            "-c=1", "--name=foo", "-d", "-a=x", "-a=y", "-v", "-v", "-n", "--", "file1", "-file2",
+        ].iter().map(|&v|String::from(v)).collect::<Vec<String>>());
+        assert_eq!(parse(&canonical), canonical);
+        assert_eq!(parse(&[]), Vec::<String>::new());
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_transform_work() {
+        fn parse(parser: &mut dyn Parser<DefaultSet, DefaultIdGen>, args: &[&str]) -> Result<Option<bool>> {
+            let mut ai = ArgIterator::new();
+
+            ai.set_args(&mut args.iter().map(|&v|String::from(v)));
+            parser.reset();
+            parser.parse(&mut ai)
+        }
+
+        let mut parsers: Vec<(Box<dyn Parser<DefaultSet, DefaultIdGen>>, bool)> = vec![
+            (Box::new(ForwardParser::default()), true),
+            (Box::new(DelayParser::default()), true),
+            // PreParser ignore the error
+            (Box::new(PreParser::default()), false),
+        ];
+
+        for (parser, raise_error) in parsers.iter_mut() {
+            let mut set = DefaultSet::new();
+
+            set.initialize_utils().unwrap();
+            set.initialize_prefixs();
+
+            let id = set.add_opt("--name=str").unwrap().commit().unwrap();
+
+            parser.add_transform(id, ValueTransform::new(|v| {
+                Ok(OptValue::from_str(v.as_str().unwrap().trim()))
+            }));
+            parser.add_transform(id, ValueTransform::new(|v| {
+                if v.as_str().unwrap().is_empty() {
+                    Err(Error::InvaldOptionValue(String::from("--name"), String::from("empty")))
+                }
+                else {
+                    Ok(OptValue::from_str(v.as_str().unwrap().to_lowercase()))
+                }
+            }));
+            set.subscribe_from(parser.as_mut());
+            parser.publish_to(set);
+
+            assert!(parse(parser.as_mut(), &["--name", "  HeLLo "]).is_ok());
+            assert_eq!(parser.get_opt(id).unwrap().value().as_str(), Some(&String::from("hello")));
+            assert_eq!(parse(parser.as_mut(), &["--name", "   "]).is_err(), *raise_error);
+        }
+
+        let parsers: Vec<Box<dyn Parser<DefaultSet, DefaultIdGen>>> = vec![
+            Box::new(ForwardParser::default()),
+            Box::new(DelayParser::default()),
+            Box::new(PreParser::default()),
+        ];
+
+        // the transform is called on the value of each occurrence before it stored
+        for mut parser in parsers {
+            let mut set = DefaultSet::new();
+
+            set.initialize_utils().unwrap();
+            set.initialize_prefixs();
+
+            let id = set.add_opt("--tag=array").unwrap().commit().unwrap();
+
+            parser.add_transform(id, ValueTransform::new(|v| {
+                Ok(OptValue::from_vec(v.as_vec().unwrap().iter().map(|v| format!("{}!", v)).collect::<Vec<String>>()))
+            }));
+            set.subscribe_from(parser.as_mut());
+            parser.publish_to(set);
+
+            assert!(parse(parser.as_mut(), &["--tag", "a", "--tag", "b"]).is_ok());
+            assert_eq!(parser.get_opt(id).unwrap().value().as_vec(), Some(&vec![String::from("a!"), String::from("b!")]));
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_validation_report_work() {
+        let mut set = DefaultSet::new();
+        let mut parser = ForwardParser::new(DefaultIdGen::default());
+
+        set.initialize_utils().unwrap();
+        set.initialize_prefixs();
+
+        let json = set.add_opt("--json=bool").unwrap().commit().unwrap();
+        let yaml = set.add_opt("--yaml=bool").unwrap().commit().unwrap();
+        let cert = set.add_opt("--cert=str").unwrap().commit().unwrap();
+        let key = set.add_opt("--key=str").unwrap().commit().unwrap();
+        let user = set.add_opt("--user=str").unwrap().commit().unwrap();
+        let token = set.add_opt("--token=str").unwrap().commit().unwrap();
+
+        parser.add_rule(GroupRule::exclusive(&[json, yaml]));
+        parser.add_rule(GroupRule::requires(cert, key));
+        parser.add_rule(GroupRule::required_one_of(&[user, token]));
+        parser.add_rule(GroupRule::requires(key, cert));
+
+        let mut ai = ArgIterator::new();
+
+        ai.set_args(&mut ["--json", "--yaml", "--cert", "a.pem", "--token", "xyz"].iter().map(|&v|String::from(v)));
+        set.subscribe_from(&mut parser);
+        parser.publish_to(set);
+        parser.parse(&mut ai).unwrap();
+
+        let report = parser.validation_report();
+        let passed: Vec<bool> = report.results().iter().map(|v| v.passed).collect();
+
+        assert_eq!(passed, vec![false, false, true, true]);
+        assert!(! report.is_passed());
+        assert_eq!(report.failed().len(), 2);
+        assert_eq!(report.failed()[0].rule, GroupRule::exclusive(&[json, yaml]));
+        assert_eq!(report.failed()[1].rule, GroupRule::requires(cert, key));
+
+        let mut parser: ForwardParser<DefaultSet, DefaultIdGen> = ForwardParser::new(DefaultIdGen::default());
+
+        parser.add_rule(GroupRule::requires(cert, key));
+        assert!(parser.validation_report().results().is_empty());
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_group_rule_ignore_default_value_work() {
+        let parsers: Vec<Box<dyn Parser<DefaultSet, DefaultIdGen>>> = vec![
+            Box::new(ForwardParser::new(DefaultIdGen::default())),
+            Box::new(DelayParser::new(DefaultIdGen::default())),
+            Box::new(PreParser::new(DefaultIdGen::default())),
+        ];
+
+        for mut parser in parsers {
+            let mut set = DefaultSet::new();
+
+            set.initialize_utils().unwrap();
+            set.initialize_prefixs();
+
+            let json = set.add_opt("--json=bool").unwrap().commit().unwrap();
+            let yaml = set.add_opt("--yaml=str{x}").unwrap().commit().unwrap();
+            let cert = set.add_opt("--cert=str").unwrap().commit().unwrap();
+            let key = set.add_opt("--key=str{a.key}").unwrap().commit().unwrap();
+            let mut ai = ArgIterator::new();
+
+            ai.set_args(&mut ["--json", "--key", "b.key"].iter().map(|&v|String::from(v)));
+            set.subscribe_from(parser.as_mut());
+            parser.publish_to(set);
+            parser.add_exclusive_group(&[json, yaml]);
+            parser.add_requires(key, cert);
+            parser.add_rule(GroupRule::exclusive(&[json, yaml]));
+            parser.add_rule(GroupRule::requires(cert, key));
+            parser.add_rule(GroupRule::requires(key, cert));
+
+            assert!(matches!(
+                parser.parse(&mut ai),
+                Err(Error::MissingDependency(name, dep)) if name == "--key" && dep == "--cert"
+            ));
+            assert_eq!(parser.get_opt(yaml).unwrap().value().as_str(), Some(&String::from("x")));
+
+            let passed: Vec<bool> = parser.validation_report().results().iter().map(|v| v.passed).collect();
+
+            assert_eq!(passed, vec![true, true, false]);
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_validator_work() {
+        for (args, valid) in [
+            (["--threads", "32", "--ratio", "0.5"], true),
+            (["--threads", "0", "--ratio", "0.5"], false),
+            (["--threads", "100", "--ratio", "0.5"], false),
+            (["--threads", "32", "--ratio", "1.5"], false),
+        ].iter() {
+            let mut set = DefaultSet::new();
+            let mut parser = ForwardParser::new(DefaultIdGen::default());
+
+            set.initialize_utils().unwrap();
+            set.initialize_prefixs();
+
+            let mut commit = set.add_opt("--threads=int").unwrap();
+
+            commit.set_validator(Box::new(|v: &OptValue| -> Result<()> {
+                match v.as_int() {
+                    Some(threads) if (1 ..= 64).contains(threads) => Ok(()),
+                    _ => Err(Error::InvaldOptionValue(format!("{:?}", v), String::from("out of range 1..=64"))),
+                }
+            }));
+            let threads = commit.commit().unwrap();
+
+            let mut commit = set.add_opt("--ratio=flt").unwrap();
+
+            commit.set_validator(Box::new(|v: &OptValue| -> Result<()> {
+                match v.as_flt() {
+                    Some(ratio) if (0.0 ..= 1.0).contains(ratio) => Ok(()),
+                    _ => Err(Error::InvaldOptionValue(format!("{:?}", v), String::from("out of range 0..=1"))),
+                }
+            }));
+            commit.commit().unwrap();
+
+            let mut ai = ArgIterator::new();
+
+            ai.set_args(&mut args.iter().map(|&v|String::from(v)));
+            set.subscribe_from(&mut parser);
+            parser.publish_to(set);
+
+            let ret = parser.parse(&mut ai);
+
+            if *valid {
+                assert!(ret.is_ok());
+                assert_eq!(parser.set().as_ref().unwrap().get_opt(threads).unwrap().value().as_int(), Some(&32));
+            }
+            else {
+                assert!(matches!(ret, Err(Error::InvaldOptionValue(_, _))));
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_preparser_unknown_opts_work() {
+        let mut set = DefaultSet::new();
+        let mut parser = PreParser::new(DefaultIdGen::default());
+
+        set.initialize_utils().unwrap();
+        set.initialize_prefixs();
+
+        set.add_opt("-c=array").unwrap().commit().unwrap();
+        set.add_opt("-i=bool").unwrap().commit().unwrap();
+
+        let mut ai = ArgIterator::new();
+
+        ai.set_args(&mut ["-c", "c", "-f", "plugin", "--load=foo", "-i", "others"].iter().map(|&v|String::from(v)));
+        set.subscribe_from(&mut parser);
+        parser.publish_to(set);
+        parser.parse(&mut ai).unwrap();
+
+        assert_eq!(parser.unknown_opts(), &vec![String::from("-f"), String::from("--load=foo")]);
+        assert_eq!(parser.noa(), &vec![String::from("plugin"), String::from("others")]);
+
+        parser.reset();
+        assert!(parser.unknown_opts().is_empty());
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_reset_values_work() {
+        let mut set = DefaultSet::new();
+        let mut parser = ForwardParser::new(DefaultIdGen::default());
+
+        set.initialize_utils().unwrap();
+        set.initialize_prefixs();
+
+        let array = set.add_opt("-a=array").unwrap().commit().unwrap();
+        let id = set.add_opt("-d=bool").unwrap().commit().unwrap();
+
+        set.set_record_raw(true);
+        parser.set_callback(id, OptCallback::from_value(Box::new(SimpleValueCallback::new(|_| Ok(true)))));
+        set.subscribe_from(&mut parser);
+        parser.publish_to(set);
+
+        let mut ai = ArgIterator::new();
+
+        ai.set_args(&mut ["-a", "foo", "-d", "bar"].iter().map(|&v|String::from(v)));
+        parser.parse(&mut ai).unwrap();
+        assert_eq!(parser.get_opt(array).unwrap().value().as_vec(), Some(&vec![String::from("foo")]));
+        assert_eq!(parser.noa(), &vec![String::from("bar")]);
+        assert_eq!(parser.set().as_ref().unwrap().raw_tokens(array), &[String::from("-a"), String::from("foo")]);
+
+        parser.reset_values();
+        assert!(parser.noa().is_empty());
+        assert!(parser.get_opt(array).unwrap().value().is_null());
+        assert!(parser.set().as_ref().unwrap().raw_tokens(array).is_empty());
+
+        ai.reset();
+        ai.set_args(&mut ["-a", "baz"].iter().map(|&v|String::from(v)));
+        parser.parse(&mut ai).unwrap();
+        assert_eq!(parser.get_opt(array).unwrap().value().as_vec(), Some(&vec![String::from("baz")]));
+        assert!(parser.callbacks.contains_key(&id));
+
+        let mut set = DefaultSet::new();
+        let mut parser = DelayParser::new(DefaultIdGen::default());
+
+        set.initialize_utils().unwrap();
+        set.initialize_prefixs();
+
+        let array = set.add_opt("-a=array").unwrap().commit().unwrap();
+
+        set.subscribe_from(&mut parser);
+        parser.publish_to(set);
+
+        ai.reset();
+        ai.set_args(&mut ["-a", "foo", "bar"].iter().map(|&v|String::from(v)));
+        parser.parse(&mut ai).unwrap();
+        assert_eq!(parser.get_opt(array).unwrap().value().as_vec(), Some(&vec![String::from("foo")]));
+
+        parser.reset_values();
+        ai.reset();
+        ai.set_args(&mut ["-a", "baz"].iter().map(|&v|String::from(v)));
+        parser.parse(&mut ai).unwrap();
+        assert_eq!(parser.get_opt(array).unwrap().value().as_vec(), Some(&vec![String::from("baz")]));
+        assert!(parser.noa().is_empty());
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_typed_pos_work() {
+        for (arg, ret) in [("42", Some(42)), ("abc", None)].iter() {
+            let parsers: Vec<(Box<dyn Parser<DefaultSet, DefaultIdGen>>, bool)> = vec![
+                (Box::new(ForwardParser::default()), true),
+                (Box::new(DelayParser::default()), true),
+                // PreParser ignore the error
+                (Box::new(PreParser::default()), false),
+            ];
+
+            for (mut parser, raise_error) in parsers {
+                let mut set = DefaultSet::new();
+
+                set.initialize_utils().unwrap();
+                set.initialize_prefixs();
+
+                let id = set.add_opt("directory=int@1").unwrap().commit().unwrap();
+
+                set.add_opt("-v=bool").unwrap().commit().unwrap();
+                assert!(set.get_opt(id).unwrap().is_style(Style::Pos));
+
+                let mut ai = ArgIterator::new();
+
+                ai.set_args(&mut ["-v", arg].iter().map(|&v|String::from(v)));
+                set.subscribe_from(parser.as_mut());
+                parser.publish_to(set);
+
+                match ret {
+                    Some(value) => {
+                        assert!(parser.parse(&mut ai).unwrap().unwrap());
+                        assert_eq!(parser.get_opt(id).unwrap().value().as_int(), Some(value));
+                    }
+                    None => {
+                        if raise_error {
+                            assert!(matches!(parser.parse(&mut ai), Err(Error::InvaldOptionValue(v, _)) if v == "abc"));
+                        }
+                        else {
+                            assert!(parser.parse(&mut ai).unwrap().unwrap());
+                            assert!(parser.get_opt(id).unwrap().value().is_null());
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_unknown_handler_work() {
+        use std::rc::Rc;
+        use std::cell::RefCell;
+
+        let parsers: Vec<Box<dyn Parser<DefaultSet, DefaultIdGen>>> = vec![
+            Box::new(ForwardParser::default()),
+            Box::new(DelayParser::default()),
+            Box::new(PreParser::default()),
+        ];
+
+        for mut parser in parsers {
+            let mut set = DefaultSet::new();
+            let unknowns = Rc::new(RefCell::new(vec![]));
+            let unknowns_ref = unknowns.clone();
+
+            set.initialize_utils().unwrap();
+            set.initialize_prefixs();
+            set.add_opt("-v=bool").unwrap().commit().unwrap();
+            parser.set_unknown_handler(Box::new(move |arg: &str| {
+                unknowns_ref.borrow_mut().push(arg.to_owned());
+                Ok(arg == "-z")
+            }));
+            set.subscribe_from(parser.as_mut());
+            parser.publish_to(set);
+
+            let mut ai = ArgIterator::new();
+
+            ai.set_args(&mut ["-z", "foo", "-v", "--zz", "bar"].iter().map(|&v|String::from(v)));
+            parser.parse(&mut ai).unwrap();
+            assert_eq!(unknowns.borrow().as_slice(), &[String::from("-z"), String::from("--zz")]);
+            assert!(! parser.noa().contains(&String::from("-z")));
+            assert!(parser.noa().contains(&String::from("foo")));
+            assert!(parser.noa().contains(&String::from("bar")));
+
+            parser.set_unknown_handler(Box::new(|arg: &str| Err(Error::RaisedError(format!("did you mean `-v`? got `{}`", arg)))));
+            parser.reset();
+            ai.reset();
+            ai.set_args(&mut ["foo", "-x"].iter().map(|&v|String::from(v)));
+            assert!(matches!(parser.parse(&mut ai), Err(Error::RaisedError(_))));
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_strict_mode_work() {
+        for strict in [true, false].iter() {
+            let parsers: Vec<Box<dyn Parser<DefaultSet, DefaultIdGen>>> = vec![
+                Box::new(ForwardParser::default()),
+                Box::new(DelayParser::default()),
+                Box::new(PreParser::default()),
+            ];
+
+            for mut parser in parsers {
+                let mut set = DefaultSet::new();
+
+                set.initialize_utils().unwrap();
+                set.initialize_prefixs();
+                set.add_opt("-v=bool").unwrap().commit().unwrap();
+                parser.set_strict(*strict);
+                set.subscribe_from(parser.as_mut());
+                parser.publish_to(set);
+
+                let mut ai = ArgIterator::new();
+
+                ai.set_args(&mut ["-v", "file.txt"].iter().map(|&v|String::from(v)));
+                parser.parse(&mut ai).unwrap();
+                assert_eq!(parser.noa(), &vec![String::from("file.txt")]);
+
+                let mut ai = ArgIterator::new();
+
+                parser.reset();
+                ai.set_args(&mut ["-v", "-z", "file.txt"].iter().map(|&v|String::from(v)));
+
+                let ret = parser.parse(&mut ai);
+
+                if *strict {
+                    assert!(matches!(ret, Err(Error::UnknownOption(name)) if name == "-z"));
+                }
+                else {
+                    assert!(ret.is_ok());
+                    assert!(parser.noa().contains(&String::from("file.txt")));
+                }
+
+                let mut ai = ArgIterator::new();
+
+                // `/` is a prefix, but `etc/passwd` is not an option name
+                parser.reset();
+                ai.set_args(&mut ["-v", "/etc/passwd"].iter().map(|&v|String::from(v)));
+                parser.parse(&mut ai).unwrap();
+                assert_eq!(parser.noa(), &vec![String::from("/etc/passwd")]);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_bool_attached_value_work() {
+        for (args, debug, quiet) in [
+            (vec!["--debug=false", "--quiet"], false, false),
+            (vec!["--debug=true"], true, true),
+            (vec!["--debug=yes", "--quiet"], true, false),
+            (vec!["--debug=0"], false, true),
+        ].iter() {
+            let parsers: Vec<Box<dyn Parser<DefaultSet, DefaultIdGen>>> = vec![
+                Box::new(ForwardParser::default()),
+                Box::new(DelayParser::default()),
+                Box::new(PreParser::default()),
+            ];
+
+            for mut parser in parsers {
+                let mut set = DefaultSet::new();
+
+                set.initialize_utils().unwrap();
+                set.initialize_prefixs();
+
+                let debug_id = set.add_opt("--debug=bool").unwrap().commit().unwrap();
+                let quiet_id = set.add_opt("--quiet=bool/").unwrap().commit().unwrap();
+
+                set.subscribe_from(parser.as_mut());
+                parser.publish_to(set);
+
+                let mut ai = ArgIterator::new();
+
+                ai.set_args(&mut args.iter().map(|&v|String::from(v)));
+                parser.parse(&mut ai).unwrap();
+                assert_eq!(parser.value_of_bool(debug_id).unwrap(), *debug);
+                assert_eq!(parser.value_of_bool(quiet_id).unwrap(), *quiet);
+                assert!(parser.noa().is_empty());
+            }
+        }
+
+        let mut set = DefaultSet::new();
+        let mut parser = ForwardParser::new(DefaultIdGen::default());
+
+        set.initialize_utils().unwrap();
+        set.initialize_prefixs();
+        set.add_opt("--debug=bool").unwrap().commit().unwrap();
+        set.add_opt("--quiet=bool/").unwrap().commit().unwrap();
+        set.subscribe_from(&mut parser);
+        parser.publish_to(set);
+
+        let mut ai = ArgIterator::new();
+
+        ai.set_args(&mut ["--debug=maybe"].iter().map(|&v|String::from(v)));
+        assert!(matches!(parser.parse(&mut ai), Err(Error::InvaldOptionValue(v, _)) if v == "maybe"));
+
+        let mut ai = ArgIterator::new();
+
+        // the deactivate style option not accept value
+        parser.reset();
+        ai.set_args(&mut ["--quiet=true"].iter().map(|&v|String::from(v)));
+        assert!(matches!(parser.parse(&mut ai), Err(Error::InvaldOptionValue(v, _)) if v == "true"));
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_into_stream_work() {
+        let mut set = DefaultSet::new();
+        let mut parser = ForwardParser::new(DefaultIdGen::default());
+
+        set.initialize_utils().unwrap();
+        set.initialize_prefixs();
+
+        let id = set.add_opt("-c=str").unwrap().commit().unwrap();
+
+        set.subscribe_from(&mut parser);
+        parser.publish_to(set);
+
+        let mut ai = ArgIterator::new();
+
+        ai.set_args(&mut ["-c", "c", "file1", "file2"].iter().map(|&v|String::from(v)));
+
+        let mut stream = parser.into_stream(&mut ai);
+
+        assert!(matches!(
+            stream.next(),
+            Some(Ok(ParseEvent::OptionMatched { id: matched_id, value: OptValue::Str(v) })) if matched_id == id && v == "c"
+        ));
+        // the left arguments are not processed yet
+        assert!(stream.parser().noa().is_empty());
+        assert!(matches!(stream.next(), Some(Ok(ParseEvent::NonOption(v))) if v == "file1"));
+        assert!(matches!(stream.next(), Some(Ok(ParseEvent::NonOption(v))) if v == "file2"));
+        assert!(matches!(stream.next(), Some(Ok(ParseEvent::Finished))));
+        assert!(stream.next().is_none());
+
+        let parser = stream.into_parser();
+
+        assert_eq!(parser.noa(), &vec![String::from("file1"), String::from("file2")]);
+
+        let mut set = DefaultSet::new();
+        let mut parser = ForwardParser::new(DefaultIdGen::default());
+
+        set.initialize_utils().unwrap();
+        set.initialize_prefixs();
+        set.add_opt("-c=int").unwrap().commit().unwrap();
+        set.subscribe_from(&mut parser);
+        parser.publish_to(set);
+        ai.reset();
+        ai.set_args(&mut ["-c", "c", "file1"].iter().map(|&v|String::from(v)));
+
+        let events: Vec<_> = parser.into_stream(&mut ai).collect();
+
+        assert_eq!(events.len(), 1);
+        assert!(matches!(events[0], Err(Error::InvaldOptionValue(_, _))));
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_positional_missing_work() {
+        let parsers: Vec<Box<dyn Parser<DefaultSet, DefaultIdGen>>> = vec![
+            Box::new(ForwardParser::default()),
+            Box::new(DelayParser::default()),
+        ];
+
+        for mut parser in parsers {
+            let mut set = DefaultSet::new();
+
+            set.initialize_utils().unwrap();
+            set.initialize_prefixs();
+            set.add_opt("source=pos!@1").unwrap().commit().unwrap();
+            set.add_opt("dest=pos!@2").unwrap().commit().unwrap();
+            set.add_opt("-v=bool").unwrap().commit().unwrap();
+            set.subscribe_from(parser.as_mut());
+            parser.publish_to(set);
+
+            let mut ai = ArgIterator::new();
+
+            ai.set_args(&mut ["-v", "foo"].iter().map(|&v|String::from(v)));
+
+            let error = parser.parse(&mut ai).unwrap_err();
+
+            assert!(matches!(&error, Error::PositionalMissing { index: 2, name } if name == "dest"));
+            assert_eq!(error.to_string(), "positional argument `dest` at index 2 is missing");
+
+            parser.reset();
+            ai.reset();
+            ai.set_args(&mut ["-v"].iter().map(|&v|String::from(v)));
+            assert!(matches!(parser.parse(&mut ai), Err(Error::PositionalMissing { index: 1, .. })));
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_env_fallback_work() {
+        std::env::set_var("GETOPT_RS_TEST_TOKEN", "secret");
+        std::env::set_var("GETOPT_RS_TEST_RETRY", "3");
+        std::env::set_var("GETOPT_RS_TEST_VERBOSE", "");
+
+        for (args, token, retry) in [
+            (vec![], "secret", 3),
+            (vec!["--token", "cli"], "cli", 3),
+            (vec!["--retry=5"], "secret", 5),
+        ].iter() {
+            let parsers: Vec<Box<dyn Parser<DefaultSet, DefaultIdGen>>> = vec![
+                Box::new(ForwardParser::default()),
+                Box::new(DelayParser::default()),
+                Box::new(PreParser::default()),
+            ];
+
+            for mut parser in parsers {
+                let mut set = DefaultSet::new();
+
+                set.initialize_utils().unwrap();
+                set.initialize_prefixs();
+
+                let mut commit = set.add_opt("--token=str").unwrap();
+
+                commit.set_env("GETOPT_RS_TEST_TOKEN");
+
+                let token_id = commit.commit().unwrap();
+                let mut commit = set.add_opt("--retry=int").unwrap();
+
+                commit.set_env("GETOPT_RS_TEST_RETRY");
+
+                let retry_id = commit.commit().unwrap();
+                let mut commit = set.add_opt("--user=str").unwrap();
+
+                commit.set_env("GETOPT_RS_TEST_NOT_EXIST");
+
+                let user_id = commit.commit().unwrap();
+                let mut commit = set.add_opt("--verbose=count").unwrap();
+
+                commit.set_deafult_value(OptValue::from_uint(2u64));
+                commit.set_env("GETOPT_RS_TEST_VERBOSE");
+
+                let verbose_id = commit.commit().unwrap();
+
+                assert_eq!(set.get_env(token_id), Some("GETOPT_RS_TEST_TOKEN"));
+                set.subscribe_from(parser.as_mut());
+                parser.publish_to(set);
+
+                let mut ai = ArgIterator::new();
+
+                ai.set_args(&mut args.iter().map(|&v|String::from(v)));
+                parser.parse(&mut ai).unwrap();
+                assert_eq!(parser.value_of_str(token_id).unwrap(), token);
+                assert_eq!(parser.value_of_int(retry_id).unwrap(), *retry);
+                assert!(parser.get_opt(user_id).unwrap().value().is_null());
+                assert_eq!(parser.get_opt(verbose_id).unwrap().value().as_uint(), Some(&1));
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_no_prefix_work() {
+        for enable_no_prefix in [true, false].iter() {
+            // PreParser keep the unknown options in `unknown_opts`
+            let parsers: Vec<(Box<dyn Parser<DefaultSet, DefaultIdGen>>, bool)> = vec![
+                (Box::new(ForwardParser::default()), true),
+                (Box::new(DelayParser::default()), true),
+                (Box::new(PreParser::default()), false),
+            ];
+
+            for (mut parser, unknown_in_noa) in parsers {
+                let mut set = DefaultSet::new();
+
+                set.initialize_utils().unwrap();
+                set.initialize_prefixs();
+                set.set_enable_no_prefix(*enable_no_prefix);
+
+                let force_id = set.add_opt("--force=bool").unwrap().commit().unwrap();
+                let count_id = set.add_opt("--count=count").unwrap().commit().unwrap();
+
+                set.subscribe_from(parser.as_mut());
+                parser.publish_to(set);
+
+                let mut ai = ArgIterator::new();
+
+                ai.set_args(&mut ["--force", "--count", "--no-force", "--no-count"].iter().map(|&v|String::from(v)));
+                parser.parse(&mut ai).unwrap();
+                assert_eq!(parser.value_of_bool(force_id).unwrap(), ! enable_no_prefix);
+                assert_eq!(parser.get_opt(count_id).unwrap().value().as_uint(), Some(&1));
+                if ! unknown_in_noa {
+                    assert!(parser.noa().is_empty());
+                }
+                else if *enable_no_prefix {
+                    assert_eq!(parser.noa(), &vec![String::from("--no-count")]);
+                }
+                else {
+                    assert_eq!(parser.noa(), &vec![String::from("--no-force"), String::from("--no-count")]);
+                }
+            }
+        }
+    }
+
+    #[cfg(feature="profile")]
+    #[test]
+    fn make_sure_profile_report_work() {
+        let mut parser = ForwardParser::new(DefaultIdGen::default());
+        let mut set = DefaultSet::new();
+
+        set.initialize_utils().unwrap();
+        set.initialize_prefixs();
+        set.add_opt("--name=str").unwrap().commit().unwrap();
+        set.add_opt("--count=int").unwrap().commit().unwrap();
+        set.add_opt("--debug=bool").unwrap().commit().unwrap();
+        set.subscribe_from(&mut parser);
+        parser.publish_to(set);
+
+        let mut ai = ArgIterator::new();
+
+        // no option matched
+        ai.set_args(&mut ["-a", "-b", "-c"].iter().map(|&v|String::from(v)));
+        parser.parse(&mut ai).unwrap();
+
+        let report = parser.profile_report();
+
+        // every token publish GS_Argument and GS_Boolean proc,
+        // then the non-option proc: one cmd, three pos and one main
+        assert_eq!(report.procs().len(), 3 * 2 + 5);
+        // the option proc only check the options has same name,
+        // the non-option proc check all the options
+        assert_eq!(report.checked(), 5 * 3);
+        assert!(report.procs()[.. 6].iter().all(|v| v.checked == 0));
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_info_index_work() {
+        let args = [
+            "--o1=1", "--o250", "250", "-abc", "-n", "42", "--o499=499", "-c", "--unknown", "pos", "--number=7"
+        ];
+
+        for index in 0 .. 3 {
+            let mut results = vec![];
+
+            // the parser with unnamed info check every option like before
+            for indexed in [true, false].iter() {
+                let mut parser: Box<dyn Parser<DefaultSet, DefaultIdGen>> = match index {
+                    0 => Box::new(ForwardParser::default()),
+                    1 => Box::new(DelayParser::default()),
+                    _ => Box::new(PreParser::default()),
+                };
+                let mut set = DefaultSet::new();
+
+                set.initialize_utils().unwrap();
+                set.initialize_prefixs();
+                for i in 0 .. 495 {
+                    set.add_opt(&format!("--o{}=int", i)).unwrap().commit().unwrap();
+                }
+                set.add_opt("-a=bool").unwrap().commit().unwrap();
+                set.add_opt("-b=bool").unwrap().commit().unwrap();
+                set.add_opt("-c=count").unwrap().commit().unwrap();
+
+                let mut commit = set.add_opt("-n=int").unwrap();
+
+                commit.add_alias("--", "number");
+                commit.commit().unwrap();
+                set.add_opt("pos=pos@1").unwrap().commit().unwrap();
+                assert_eq!(set.len(), 500);
+                if *indexed {
+                    set.subscribe_from(parser.as_mut());
+                }
+                else {
+                    for opt in set.iter() {
+                        parser.reg_subscriber(Box::new(OptionInfo::new(opt.id())));
+                    }
+                }
+                parser.publish_to(set);
+
+                let mut ai = ArgIterator::new();
+
+                ai.set_args(&mut args.iter().map(|&v|String::from(v)));
+                assert!(parser.parse(&mut ai).unwrap().is_some());
+                results.push((
+                    parser.set().as_ref().unwrap().iter().map(|opt| opt.value().clone()).collect::<Vec<OptValue>>(),
+                    parser.noa().clone(),
+                ));
+            }
+            assert_eq!(results[0], results[1]);
+
+            let (values, _) = &results[0];
+
+            assert_eq!(values[1].as_int(), Some(&1));
+            assert_eq!(values[250].as_int(), Some(&250));
+            assert_eq!(values[2].as_int(), None);
+            assert_eq!(values[495].as_bool(), Some(&true));
+            assert_eq!(values[497].as_uint(), Some(&2));
+            assert_eq!(values[498].as_int(), Some(&7));
+        }
+    }
+
+    #[test]
+    fn make_sure_type_context_work() {
+        let mut set = DefaultSet::new();
+
+        set.initialize_utils().unwrap();
+        set.initialize_prefixs();
+
+        let debug_id = set.add_opt("--debug=bool").unwrap().commit().unwrap();
+        let name_id = set.add_opt("--name=str").unwrap().commit().unwrap();
+        let quiet_id = set.add_opt("-q=bool").unwrap().commit().unwrap();
+
+        let value = set[debug_id].value().clone();
+        let mut ctx = TypeContext::new("bool");
+
+        assert!(ctx.match_opt(&set[debug_id]));
+        assert!(ctx.match_opt(&set[quiet_id]));
+        assert!(! ctx.match_opt(&set[name_id]));
+        assert!(! ctx.is_matched());
+        assert!(ctx.process(&mut set[debug_id]).unwrap());
+        assert!(ctx.is_matched());
+        assert_eq!(set[debug_id].value(), &value);
+
+        assert_eq!(find_by_type(&mut set, "bool").unwrap(), vec![debug_id, quiet_id]);
+        assert_eq!(find_by_type(&mut set, "str").unwrap(), vec![name_id]);
+        assert!(find_by_type(&mut set, "array").unwrap().is_empty());
+    }
+
+    struct AuditLogger;
+
+    static AUDIT_LOG: std::sync::Mutex<Vec<String>> = std::sync::Mutex::new(vec![]);
+
+    impl log::Log for AuditLogger {
+        fn enabled(&self, metadata: &log::Metadata) -> bool {
+            metadata.level() <= log::Level::Warn
+        }
+
+        fn log(&self, record: &log::Record) {
+            if self.enabled(record.metadata()) {
+                AUDIT_LOG.lock().unwrap().push(record.args().to_string());
+            }
+        }
+
+        fn flush(&self) { }
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_audit_work() {
+        static LOGGER: AuditLogger = AuditLogger;
+
+        if log::set_logger(&LOGGER).is_ok() {
+            log::set_max_level(log::LevelFilter::Warn);
+        }
+
+        let parsers: Vec<Box<dyn Parser<DefaultSet, DefaultIdGen>>> = vec![
+            Box::new(ForwardParser::default()),
+            Box::new(DelayParser::default()),
+            Box::new(PreParser::default()),
+        ];
+
+        for (index, mut parser) in parsers.into_iter().enumerate() {
+            let mut set = DefaultSet::new();
+
+            set.initialize_utils().unwrap();
+            set.initialize_prefixs();
+            set.add_opt("--audit-matched=bool").unwrap().commit().unwrap();
+
+            let callback_id = set.add_opt("--audit-callback=bool").unwrap().commit().unwrap();
+
+            set.add_opt(&format!("--audit-unused{}=str", index)).unwrap().commit().unwrap();
+            set.add_opt("pos=pos@0").unwrap().commit().unwrap();
+            parser.set_callback(callback_id, OptCallback::from_value(Box::new(SimpleValueCallback::new(|_| Ok(true)))));
+            set.subscribe_from(parser.as_mut());
+            parser.publish_to(set);
+
+            let mut ai = ArgIterator::new();
+
+            ai.set_args(&mut ["--audit-matched"].iter().map(|&v|String::from(v)));
+            parser.parse(&mut ai).unwrap();
+
+            let message = format!("Option `--audit-unused{}` has no callback and not matched, may be a typo of option name", index);
+
+            // the audit is opt-in
+            assert!(! AUDIT_LOG.lock().unwrap().contains(&message));
+            assert_eq!(parser.audit(), vec![format!("--audit-unused{}", index)]);
+            AUDIT_LOG.lock().unwrap().clear();
+
+            parser.set_audit(true);
+            parser.reset();
+            ai.reset();
+            parser.parse(&mut ai).unwrap();
+            assert!(AUDIT_LOG.lock().unwrap().contains(&message));
+        }
+    }
+
+    #[test]
+    fn make_sure_complete_work() {
+        let parsers: Vec<Box<dyn Parser<DefaultSet, DefaultIdGen>>> = vec![
+            Box::new(ForwardParser::default()),
+            Box::new(DelayParser::default()),
+            Box::new(PreParser::default()),
+        ];
+
+        for mut parser in parsers {
+            let mut set = DefaultSet::new();
+
+            set.initialize_utils().unwrap();
+            set.initialize_prefixs();
+            set.add_opt("--color=bool").unwrap().commit().unwrap();
+
+            let mut commit = set.add_opt("--count=int").unwrap();
+
+            commit.add_alias("-", "c");
+            commit.add_alias("--", "counter");
+            commit.commit().unwrap();
+            set.add_opt("--force=bool").unwrap().commit().unwrap();
+            set.add_opt("copy=cmd").unwrap().commit().unwrap();
+            set.add_opt("--config=pos@1").unwrap().commit().unwrap();
+            set.subscribe_from(parser.as_mut());
+            parser.publish_to(set);
+
+            assert_eq!(parser.complete("--co"), vec!["--color", "--count", "--counter"]);
+            assert_eq!(parser.complete("--f"), vec!["--force"]);
+            assert_eq!(parser.complete("-c"), vec!["-c"]);
+            assert!(parser.complete("co").is_empty());
+            assert_eq!(parser.complete("").len(), 5);
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_first_matched_style_win_work() {
+        let parsers: Vec<Box<dyn Parser<DefaultSet, DefaultIdGen>>> = vec![
+            Box::new(ForwardParser::new(DefaultIdGen::default())),
+            Box::new(DelayParser::new(DefaultIdGen::default())),
+            Box::new(PreParser::new(DefaultIdGen::default())),
+        ];
+
+        for mut parser in parsers {
+            let mut set = DefaultSet::new();
+
+            set.initialize_utils().unwrap();
+            set.initialize_prefixs();
+
+            let d = set.add_opt("-d=bool").unwrap().commit().unwrap();
+            let e = set.add_opt("-e=str").unwrap().commit().unwrap();
+            let mut ai = ArgIterator::new();
+
+            ai.set_args(&mut ["-d", "-eval"].iter().map(|&v|String::from(v)));
+            set.subscribe_from(parser.as_mut());
+            parser.publish_to(set);
+            parser.parse(&mut ai).unwrap();
+
+            let trace = parser.trace();
+            let d_styles = &trace.get("-d").unwrap().styles;
+            let e_styles = &trace.get("-e").unwrap().styles;
+
+            assert_eq!(parser.occurrence(d), 1);
+            assert_eq!(d_styles.len(), 1);
+            assert!(d_styles[0].ends_with("_Boolean"));
+            assert_eq!(parser.occurrence(e), 1);
+            assert_eq!(e_styles.len(), 1);
+            assert!(e_styles[0].ends_with("_Embedded_Value"));
+            assert_eq!(parser.get_opt(d).unwrap().value().as_bool(), Some(&true));
+            assert_eq!(parser.get_opt(e).unwrap().value().as_str(), Some(&String::from("val")));
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_main_mut_callback_work() {
+        let parsers: Vec<Box<dyn Parser<DefaultSet, DefaultIdGen>>> = vec![
+            Box::new(ForwardParser::new(DefaultIdGen::default())),
+            Box::new(DelayParser::new(DefaultIdGen::default())),
+            Box::new(PreParser::new(DefaultIdGen::default())),
+        ];
+
+        for mut parser in parsers {
+            let mut set = DefaultSet::new();
+
+            set.initialize_utils().unwrap();
+            set.initialize_prefixs();
+
+            let width = set.add_opt("--width=int").unwrap().commit().unwrap();
+            let height = set.add_opt("--height=int").unwrap().commit().unwrap();
+            let area = set.add_opt("--area=int").unwrap().commit().unwrap();
+            let main = set.add_opt("main=main").unwrap().commit().unwrap();
+
+            parser.set_callback(main,
+                OptCallback::from_main_mut(Box::new(SimpleMainMutCallback::new(
+                    move |set, _| {
+                        let value = set[width].value().as_int().unwrap() * set[height].value().as_int().unwrap();
+
+                        set.get_opt_mut(area).unwrap().set_value(OptValue::from_int(value));
+                        Ok(true)
+                    }
+                )))
+            );
+
+            let mut ai = ArgIterator::new();
+
+            ai.set_args(&mut ["--width", "3", "--height", "4"].iter().map(|&v|String::from(v)));
+            set.subscribe_from(parser.as_mut());
+            parser.publish_to(set);
+            parser.parse(&mut ai).unwrap();
+
+            assert_eq!(parser.get_opt(area).unwrap().value().as_int(), Some(&12));
+            assert_eq!(parser.get_opt(main).unwrap().value().as_bool(), Some(&true));
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_parser_builder_work() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        let called = Rc::new(RefCell::new(vec![]));
+        let mut set = DefaultSet::new();
+
+        set.initialize_utils().unwrap();
+        set.initialize_prefixs();
+
+        let mut builder = ParserBuilder::new(set, ForwardParser::<DefaultSet, DefaultIdGen>::default());
+        let called_c = called.clone();
+        let c = builder.add_opt("-c=int").unwrap()
+                       .set_callback(OptCallback::from_value(Box::new(SimpleValueCallback::new(move |opt| {
+                           called_c.borrow_mut().push(format!("c={}", opt.value().as_int().unwrap()));
+                           Ok(true)
+                       }))))
+                       .commit()
+                       .unwrap();
+        let called_d = called.clone();
+        let d = {
+            let mut commit = builder.add_opt("-d=bool").unwrap();
+
+            commit.set_help("debug mode");
+            commit.set_callback(OptCallback::from_value(Box::new(SimpleValueCallback::new(move |opt| {
+                called_d.borrow_mut().push(format!("d={}", opt.value().as_bool().unwrap()));
+                Ok(true)
+            }))));
+            commit.commit().unwrap()
+        };
+
+        assert!(builder.add_opt("-e=unknown").unwrap().set_callback(OptCallback::Null).commit().is_err());
+        assert_eq!(builder.set().len(), 2);
+
+        let mut parser = builder.build();
+        let mut ai = ArgIterator::new();
+
+        ai.set_args(&mut ["-c", "42", "-d"].iter().map(|&v|String::from(v)));
+        assert_eq!(parser.parse(&mut ai).unwrap(), Some(true));
+        assert_eq!(parser.get_opt(c).unwrap().value().as_int(), Some(&42));
+        assert_eq!(parser.get_opt(d).unwrap().help_info().help, "debug mode");
+        assert_eq!(&*called.borrow(), &vec![String::from("c=42"), String::from("d=true")]);
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_array_delimiter_work() {
+        let parsers: Vec<Box<dyn Parser<DefaultSet, DefaultIdGen>>> = vec![
+            Box::new(ForwardParser::new(DefaultIdGen::default())),
+            Box::new(DelayParser::new(DefaultIdGen::default())),
+            Box::new(PreParser::new(DefaultIdGen::default())),
+        ];
+
+        for mut parser in parsers {
+            let mut set = DefaultSet::new();
+
+            set.initialize_utils().unwrap();
+            set.initialize_prefixs();
+
+            let tags = {
+                let mut commit = set.add_opt("--tags=array").unwrap();
+
+                commit.set_delimiter(',');
+                commit.commit().unwrap()
+            };
+            let files = set.add_opt("--files=array").unwrap().commit().unwrap();
+            let mut ai = ArgIterator::new();
+
+            ai.set_args(&mut ["--tags", "a,b,c", "--tags=a\\,b", "--files", "x,y", "--tags", "d,,e\\f"].iter().map(|&v|String::from(v)));
+            set.subscribe_from(parser.as_mut());
+            parser.publish_to(set);
+            parser.parse(&mut ai).unwrap();
+
+            let values: Vec<&str> = vec!["a", "b", "c", "a,b", "d", "", "e\\f"];
+
+            assert_eq!(parser.get_opt(tags).unwrap().value().as_vec().unwrap(), &values);
+            assert_eq!(parser.get_opt(files).unwrap().value().as_vec().unwrap(), &vec![String::from("x,y")]);
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_last_matched_option_work() {
+        let parsers: Vec<(Box<dyn Parser<DefaultSet, DefaultIdGen>>, bool)> = vec![
+            (Box::new(ForwardParser::new(DefaultIdGen::default())), true),
+            (Box::new(DelayParser::new(DefaultIdGen::default())), true),
+            (Box::new(PreParser::new(DefaultIdGen::default())), false),
+        ];
+
+        for (mut parser, strict) in parsers {
+            let mut set = DefaultSet::new();
+
+            set.initialize_utils().unwrap();
+            set.initialize_prefixs();
+
+            let c = set.add_opt("-c=int").unwrap().commit().unwrap();
+            let d = set.add_opt("-d=bool").unwrap().commit().unwrap();
+            let mut ai = ArgIterator::new();
+
+            set.add_opt("file=pos@1").unwrap().commit().unwrap();
+            ai.set_args(&mut ["-d", "-c", "1", "foo"].iter().map(|&v|String::from(v)));
+            set.subscribe_from(parser.as_mut());
+            parser.publish_to(set);
+            parser.parse(&mut ai).unwrap();
+            assert_eq!(parser.last_matched_option(), Some(c));
+
+            parser.reset_values();
+            assert_eq!(parser.last_matched_option(), None);
+
+            // the value of argument style option is missing
+            ai.reset();
+            ai.set_args(&mut ["-d", "-c"].iter().map(|&v|String::from(v)));
+            if strict {
+                assert!(matches!(parser.parse(&mut ai), Err(Error::MissingArgument(name)) if name == "-c"));
+            }
+            else {
+                parser.parse(&mut ai).unwrap();
+            }
+            assert_eq!(parser.last_matched_option(), Some(d));
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_group_argument_work() {
+        for args in [vec!["-xvf", "archive", "foo"], vec!["-xvfarchive", "foo"]].iter() {
+            let parsers: Vec<Box<dyn Parser<DefaultSet, DefaultIdGen>>> = vec![
+                Box::new(ForwardParser::new(DefaultIdGen::default())),
+                Box::new(DelayParser::new(DefaultIdGen::default())),
+                Box::new(PreParser::new(DefaultIdGen::default())),
+            ];
+
+            for mut parser in parsers {
+                let mut set = DefaultSet::new();
+
+                set.initialize_utils().unwrap();
+                set.initialize_prefixs();
+
+                let f = set.add_opt("-f=str").unwrap().commit().unwrap();
+                let x = set.add_opt("-x=bool").unwrap().commit().unwrap();
+                let v = set.add_opt("-v=bool").unwrap().commit().unwrap();
+                let mut ai = ArgIterator::new();
+
+                ai.set_args(&mut args.iter().map(|&v|String::from(v)));
+                set.subscribe_from(parser.as_mut());
+                parser.publish_to(set);
+                parser.parse(&mut ai).unwrap();
+
+                assert_eq!(parser.get_opt(f).unwrap().value().as_str(), Some(&String::from("archive")));
+                assert_eq!(parser.get_opt(x).unwrap().value().as_bool(), Some(&true));
+                assert_eq!(parser.get_opt(v).unwrap().value().as_bool(), Some(&true));
+                assert_eq!(parser.noa(), &vec![String::from("foo")]);
+            }
+        }
+
+        let mut set = DefaultSet::new();
+
+        set.initialize_utils().unwrap();
+        set.initialize_prefixs();
+        set.add_opt("-f=str").unwrap().commit().unwrap();
+        set.add_opt("-x=bool").unwrap().commit().unwrap();
+        set.add_opt("-v=bool").unwrap().commit().unwrap();
+
+        let group = |s: &str| find_group_argument(&set, &parse_argument_impl(&Some(String::from(s)), &vec![String::from("-")]).unwrap());
+
+        assert_eq!(group("-xvf"), Some(2));
+        assert_eq!(group("-xfv"), Some(1));
+        assert_eq!(group("-fxv"), None);
+        assert_eq!(group("-xv"), None);
+        assert_eq!(group("-xyf"), None);
+        assert_eq!(group("-xvf=a"), None);
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_transform_callback_work() {
+        let parsers: Vec<Box<dyn Parser<DefaultSet, DefaultIdGen>>> = vec![
+            Box::new(ForwardParser::new(DefaultIdGen::default())),
+            Box::new(DelayParser::new(DefaultIdGen::default())),
+            Box::new(PreParser::new(DefaultIdGen::default())),
+        ];
+
+        for mut parser in parsers {
+            let mut set = DefaultSet::new();
+
+            set.initialize_utils().unwrap();
+            set.initialize_prefixs();
+
+            let name = set.add_opt("--name=str").unwrap().commit().unwrap();
+            let other = set.add_opt("--other=str").unwrap().commit().unwrap();
+            let mut ai = ArgIterator::new();
+
+            parser.set_callback(name, OptCallback::from_transform(Box::new(SimpleTransformCallback::new(|opt| {
+                let value = opt.value().as_str().unwrap().to_uppercase();
+
+                opt.set_value(OptValue::from_str(value));
+                Ok(true)
+            }))));
+            ai.set_args(&mut ["--name", "foo", "--other", "bar"].iter().map(|&v|String::from(v)));
+            set.subscribe_from(parser.as_mut());
+            parser.publish_to(set);
+            parser.parse(&mut ai).unwrap();
+
+            assert_eq!(parser.get_opt(name).unwrap().value().as_str(), Some(&String::from("FOO")));
+            assert_eq!(parser.get_opt(other).unwrap().value().as_str(), Some(&String::from("bar")));
+        }
+
+        let mut set = DefaultSet::new();
+        let mut parser = ForwardParser::<DefaultSet, DefaultIdGen>::default();
+
+        set.initialize_utils().unwrap();
+        set.initialize_prefixs();
+
+        let file = set.add_opt("file=pos@1").unwrap().commit().unwrap();
+
+        parser.set_callback(file, OptCallback::from_transform(Box::new(SimpleTransformCallback::new(|_| Ok(true)))));
+        set.subscribe_from(&mut parser);
+        parser.publish_to(set);
+        assert!(matches!(parser.parse(&mut ArgIterator::new()), Err(Error::InvalidCallbackType(_, name)) if name == "Transform"));
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_backward_index_callback_work() {
+        use std::rc::Rc;
+        use std::cell::RefCell;
+
+        let parsers: Vec<Box<dyn Parser<DefaultSet, DefaultIdGen>>> = vec![
+            Box::new(ForwardParser::new(DefaultIdGen::default())),
+            Box::new(DelayParser::new(DefaultIdGen::default())),
+            Box::new(PreParser::new(DefaultIdGen::default())),
+        ];
+
+        for mut parser in parsers {
+            let mut set = DefaultSet::new();
+            let called = Rc::new(RefCell::new(vec![]));
+
+            set.initialize_utils().unwrap();
+            set.initialize_prefixs();
+
+            let last = set.add_opt("last=pos@-1").unwrap().commit().unwrap();
+            let called_clone = called.clone();
+            let mut ai = ArgIterator::new();
+
+            parser.set_callback(last, OptCallback::from_index(Box::new(SimpleIndexCallback::new(move |_, noa| {
+                called_clone.borrow_mut().push(noa.clone());
+                Ok(true)
+            }))));
+            ai.set_args(&mut ["foo", "bar", "baz"].iter().map(|&v|String::from(v)));
+            set.subscribe_from(parser.as_mut());
+            parser.publish_to(set);
+            parser.parse(&mut ai).unwrap();
+
+            assert_eq!(called.borrow().as_slice(), &[String::from("baz")]);
+            assert_eq!(parser.get_opt(last).unwrap().value().as_bool(), Some(&true));
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_merge_results_into_work() {
+        let parsers: Vec<Box<dyn Parser<DefaultSet, DefaultIdGen>>> = vec![
+            Box::new(ForwardParser::new(DefaultIdGen::default())),
+            Box::new(DelayParser::new(DefaultIdGen::default())),
+            Box::new(PreParser::new(DefaultIdGen::default())),
+        ];
+
+        for mut parser in parsers {
+            let mut set = DefaultSet::new();
+
+            set.initialize_utils().unwrap();
+            set.initialize_prefixs();
+            set.add_opt("--verbose=bool").unwrap().commit().unwrap();
+            set.add_opt("--jobs=int").unwrap().commit().unwrap();
+            set.add_opt("--color=str").unwrap().commit().unwrap();
+
+            let mut ai = ArgIterator::new();
+
+            ai.set_args(&mut ["--verbose", "--jobs", "4", "build"].iter().map(|&v|String::from(v)));
+            set.subscribe_from(parser.as_mut());
+            parser.publish_to(set);
+            parser.parse(&mut ai).unwrap();
+
+            let mut sub = DefaultSet::new();
+
+            sub.initialize_utils().unwrap();
+            sub.initialize_prefixs();
+
+            let verbose = sub.add_opt("--verbose=bool").unwrap().commit().unwrap();
+            let release = sub.add_opt("--release=bool").unwrap().commit().unwrap();
+            let mut commit = sub.add_opt("--color=str").unwrap();
+
+            commit.set_deafult_value(OptValue::from_str("auto"));
+            let color = commit.commit().unwrap();
+
+            // the `--color` not matched and `--jobs` not exists in subcommand set
+            assert_eq!(parser.merge_results_into(&mut sub), 1);
+            assert_eq!(sub[verbose].value().as_bool(), Some(&true));
+            assert_eq!(sub[release].value().as_bool(), Some(&false));
+            assert_eq!(sub[color].value().as_str(), Some(&String::from("auto")));
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_array_element_type_work() {
+        let parsers: Vec<Box<dyn Parser<DefaultSet, DefaultIdGen>>> = vec![
+            Box::new(ForwardParser::new(DefaultIdGen::default())),
+            Box::new(DelayParser::new(DefaultIdGen::default())),
+        ];
+
+        for mut parser in parsers {
+            let mut set = DefaultSet::new();
+
+            set.initialize_utils().unwrap();
+            set.initialize_prefixs();
+
+            let ports = set.add_opt("--ports=array:uint").unwrap().commit().unwrap();
+            let mut ai = ArgIterator::new();
+
+            assert_eq!(set[ports].type_name(), "array");
+            assert!(matches!(set.add_opt("--flags=array:bool").unwrap().commit(), Err(Error::UtilsNotSupportTypeName(_, name)) if name == "array:bool"));
+            ai.set_args(&mut ["--ports", "80", "--ports=443"].iter().map(|&v|String::from(v)));
+            set.subscribe_from(parser.as_mut());
+            parser.publish_to(set);
+            parser.parse(&mut ai).unwrap();
+
+            assert_eq!(parser.get_opt(ports).unwrap().value().as_vec().unwrap(), &vec![String::from("80"), String::from("443")]);
+
+            let mut ai = ArgIterator::new();
+
+            parser.reset();
+            ai.set_args(&mut ["--ports", "80", "--ports", "abc"].iter().map(|&v|String::from(v)));
+
+            let ret = parser.parse(&mut ai);
+
+            assert!(ret.is_err());
+            assert!(ret.unwrap_err().to_string().contains("invalid option value `abc`"));
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_custom_index_iterator_work() {
+        use crate::arg::{Argument, parse_argument};
+        use std::sync::mpsc::channel;
+
+        // an iterator receiving the arguments from a channel
+        #[derive(Debug)]
+        struct ChannelIterator {
+            args: Vec<String>,
+
+            index: usize,
+
+            arg: Option<String>,
+
+            next_arg: Option<String>,
+        }
+
+        #[async_trait::async_trait]
+        impl IndexIterator for ChannelIterator {
+            fn set_args(&mut self, args: &mut dyn Iterator<Item = String>) {
+                self.args = args.collect();
+            }
+
+            fn reach_end(&self) -> bool {
+                self.index >= self.args.len()
+            }
+
+            fn fill_current_and_next(&mut self) {
+                self.arg = self.args.get(self.index).cloned();
+                self.next_arg = self.args.get(self.index + 1).cloned();
+            }
+
+            fn current(&self) -> &Option<String> {
+                &self.arg
+            }
+
+            fn current_index(&self) -> usize {
+                self.index
+            }
+
+            fn count(&self) -> usize {
+                self.args.len()
+            }
+
+            fn next(&self) -> &Option<String> {
+                &self.next_arg
+            }
+
+            fn skip(&mut self) {
+                self.index += 1;
+            }
+
+            #[cfg(not(feature="async"))]
+            fn parse(&self, prefixs: &Vec<String>) -> Result<Argument> {
+                parse_argument(self.current(), prefixs)
+            }
+
+            #[cfg(feature="async")]
+            async fn parse(&self, prefixs: &Vec<String>) -> Result<Argument> {
+                parse_argument(self.current(), prefixs).await
+            }
+
+            fn reset(&mut self) {
+                self.index = 0;
+                self.arg = None;
+                self.next_arg = None;
+            }
+        }
+
+        let (tx, rx) = channel();
+
+        for arg in ["--count", "42", "-d", "foo"].iter() {
+            tx.send(String::from(*arg)).unwrap();
+        }
+        drop(tx);
+
+        let args: Vec<String> = rx.iter().collect();
+        let mut iters: Vec<Box<dyn IndexIterator>> = vec![
+            Box::new(ChannelIterator { args: args.clone(), index: 0, arg: None, next_arg: None }),
+            Box::new(ArgIterator::from_iter(args.into_iter())),
+        ];
+
+        for iter in iters.iter_mut() {
+            let mut set = DefaultSet::new();
+            let mut parser = ForwardParser::new(DefaultIdGen::default());
+
+            set.initialize_utils().unwrap();
+            set.initialize_prefixs();
+
+            let count = set.add_opt("--count=int").unwrap().commit().unwrap();
+            let debug = set.add_opt("-d=bool").unwrap().commit().unwrap();
+
+            set.subscribe_from(&mut parser);
+            parser.publish_to(set);
+            parser.parse(iter.as_mut()).unwrap();
+
+            assert_eq!(parser.get_opt(count).unwrap().value().as_int(), Some(&42));
+            assert_eq!(parser.get_opt(debug).unwrap().value().as_bool(), Some(&true));
+            assert_eq!(parser.noa(), &vec![String::from("foo")]);
+        }
+
+        let args = ["a", "b", "c"].iter().map(|&v|String::from(v)).collect();
+        let mut iter = ChannelIterator { args, index: 0, arg: None, next_arg: None };
+
+        iter.restore(1);
+        assert_eq!(iter.checkpoint(), 1);
+        assert_eq!(iter.current(), &Some(String::from("b")));
+        assert_eq!(iter.peek_nth(1), Some(&String::from("c")));
+        assert_eq!(iter.peek_nth(2), None);
+        iter.restore(5);
+        assert!(iter.reach_end());
+        assert_eq!(iter.checkpoint(), 3);
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_raw_value_callback_work() {
+        use std::rc::Rc;
+        use std::cell::RefCell;
+
+        let parsers: Vec<Box<dyn Parser<DefaultSet, DefaultIdGen>>> = vec![
+            Box::new(ForwardParser::new(DefaultIdGen::default())),
+            Box::new(DelayParser::new(DefaultIdGen::default())),
+            Box::new(PreParser::new(DefaultIdGen::default())),
+        ];
+
+        for mut parser in parsers {
+            let mut set = DefaultSet::new();
+            let raws = Rc::new(RefCell::new(vec![]));
+
+            set.initialize_utils().unwrap();
+            set.initialize_prefixs();
+
+            let count = set.add_opt("--count=int").unwrap().commit().unwrap();
+            let debug = set.add_opt("-d=bool").unwrap().commit().unwrap();
+            let raws_clone = raws.clone();
+            let mut ai = ArgIterator::new();
+
+            parser.set_callback(count, OptCallback::from_raw_value(Box::new(SimpleRawValueCallback::new(move |opt, raw| {
+                raws_clone.borrow_mut().push((raw.to_owned(), *opt.value().as_int().unwrap()));
+                Ok(true)
+            }))));
+            parser.set_callback(debug, OptCallback::from_value(Box::new(SimpleValueCallback::new(|opt| {
+                assert_eq!(opt.value().as_bool(), Some(&true));
+                Ok(true)
+            }))));
+            ai.set_args(&mut ["--count=007", "-d", "--count", "08"].iter().map(|&v|String::from(v)));
+            set.subscribe_from(parser.as_mut());
+            parser.publish_to(set);
+            parser.parse(&mut ai).unwrap();
+
+            // the callback receive the raw argument of each occurrence
+            assert_eq!(raws.borrow().as_slice(), &[(String::from("007"), 7), (String::from("08"), 8)]);
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_anywhere_pos_without_noa_work() {
+        for optional in [true, false].iter() {
+            let parsers: Vec<Box<dyn Parser<DefaultSet, DefaultIdGen>>> = vec![
+                Box::new(ForwardParser::new(DefaultIdGen::default())),
+                Box::new(DelayParser::new(DefaultIdGen::default())),
+                Box::new(PreParser::new(DefaultIdGen::default())),
+            ];
+
+            for mut parser in parsers {
+                let mut set = DefaultSet::new();
+
+                set.initialize_utils().unwrap();
+                set.initialize_prefixs();
+
+                let any = set.add_opt(if *optional { "any=pos@0" } else { "any=pos!@0" }).unwrap().commit().unwrap();
+                let mut ai = ArgIterator::new();
+
+                parser.set_callback(any, OptCallback::from_index(Box::new(SimpleIndexCallback::new(|_, _| {
+                    panic!("the callback should not be called without non-option argument");
+                }))));
+                ai.set_args(&mut [].iter().map(|&v: &&str|String::from(v)));
+                set.subscribe_from(parser.as_mut());
+                parser.publish_to(set);
+
+                let ret = parser.parse(&mut ai);
+
+                // the anywhere positional is not matched without non-option argument
+                if *optional {
+                    assert!(ret.is_ok());
+                    assert!(! parser.get_opt(any).unwrap().has_value());
+                }
+                else {
+                    assert!(matches!(ret, Err(Error::NonOptionForceRequired(name)) if name == "any=pos! @0"));
+                }
+                assert!(parser.noa().is_empty());
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_rearm_callbacks_work() {
+        use std::rc::Rc;
+        use std::cell::RefCell;
+
+        let parsers: Vec<Box<dyn Parser<DefaultSet, DefaultIdGen>>> = vec![
+            Box::new(ForwardParser::new(DefaultIdGen::default())),
+            Box::new(DelayParser::new(DefaultIdGen::default())),
+            Box::new(PreParser::new(DefaultIdGen::default())),
+        ];
+
+        for mut parser in parsers {
+            let mut set = DefaultSet::new();
+            let count = Rc::new(RefCell::new(0));
+
+            set.initialize_utils().unwrap();
+            set.initialize_prefixs();
+
+            let debug = set.add_opt("-d=bool").unwrap().commit().unwrap();
+            let count_clone = count.clone();
+
+            parser.set_callback(debug, OptCallback::from_value(Box::new(SimpleValueCallback::new(move |_| {
+                *count_clone.borrow_mut() += 1;
+                Ok(true)
+            }))));
+            set.subscribe_from(parser.as_mut());
+            parser.publish_to(set);
+            for pass in 1 ..= 2 {
+                let mut ai = ArgIterator::new();
+
+                ai.set_args(&mut ["-d"].iter().map(|&v|String::from(v)));
+                parser.rearm_callbacks();
+                assert!(parser.get_opt(debug).unwrap().is_need_invoke());
+                parser.parse(&mut ai).unwrap();
+
+                assert_eq!(*count.borrow(), pass);
+                assert!(! parser.get_opt(debug).unwrap().is_need_invoke());
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_subparser_work() {
+        let mut parser = ForwardParser::<DefaultSet, DefaultIdGen>::default();
+        let mut build_parser = ForwardParser::<DefaultSet, DefaultIdGen>::default();
+        let mut set = DefaultSet::new();
+        let mut build_set = DefaultSet::new();
+
+        set.initialize_utils().unwrap();
+        set.initialize_prefixs();
+        build_set.initialize_utils().unwrap();
+        build_set.initialize_prefixs();
+
+        let verbose = set.add_opt("-v=bool").unwrap().commit().unwrap();
+        let build = set.add_opt("build=cmd").unwrap().commit().unwrap();
+        let release = build_set.add_opt("--release=bool").unwrap().commit().unwrap();
+
+        build_set.subscribe_from(&mut build_parser);
+        build_parser.publish_to(build_set);
+        set.subscribe_from(&mut parser);
+        parser.publish_to(set);
+        parser.add_subparser("build", Box::new(build_parser));
+
+        let mut ai = ArgIterator::new();
+
+        ai.set_args(&mut ["-v", "build", "--release"].iter().map(|&v|String::from(v)));
+        assert!(parser.parse(&mut ai).unwrap().is_some());
+        assert_eq!(parser.get_opt(verbose).unwrap().value().as_bool(), Some(&true));
+        assert_eq!(parser.get_opt(build).unwrap().value().as_bool(), Some(&true));
+        assert_eq!(parser.noa(), &vec![String::from("build")]);
+
+        let build_parser = parser.get_subparser("build").unwrap();
+
+        assert_eq!(build_parser.get_opt(release).unwrap().value().as_bool(), Some(&true));
+        assert!(build_parser.noa().is_empty());
+        assert!(parser.get_subparser("test").is_none());
+
+        // the subparser is reset too
+        parser.reset_values();
+        assert!(parser.noa().is_empty());
+        assert_eq!(parser.get_subparser("build").unwrap().get_opt(release).unwrap().value().as_bool(), Some(&false));
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_stdin_on_dash_work() {
+        for (args, value) in [
+            (vec!["--input", "-"], "piped content\n"),
+            (vec!["--input", "file"], "file"),
+            (vec!["--input=-"], "piped content\n"),
+            (vec!["--literal", "-"], "-"),
+        ].iter() {
+            let parsers: Vec<Box<dyn Parser<DefaultSet, DefaultIdGen>>> = vec![
+                Box::new(ForwardParser::default()),
+                Box::new(DelayParser::default()),
+                Box::new(PreParser::default()),
+            ];
+
+            for mut parser in parsers {
+                let mut set = DefaultSet::new();
+
+                set.initialize_utils().unwrap();
+                set.initialize_prefixs();
+
+                let mut commit = set.add_opt("--input=str").unwrap();
+
+                commit.set_stdin_on_dash(true);
+                commit.set_reader(Box::new(|| Ok(String::from("piped content\n"))));
+                let input = commit.commit().unwrap();
+                let literal = set.add_opt("--literal=str").unwrap().commit().unwrap();
+
+                set.subscribe_from(parser.as_mut());
+                parser.publish_to(set);
+
+                let mut ai = ArgIterator::new();
+
+                ai.set_args(&mut args.iter().map(|&v|String::from(v)));
+                parser.parse(&mut ai).unwrap();
+
+                let id = if args[0] == "--literal" { literal } else { input };
+
+                assert_eq!(parser.get_opt(id).unwrap().value().as_str(), Some(&String::from(*value)));
+            }
+        }
+    }
+
+    #[test]
+    fn make_sure_set_values_work() {
+        let parsers: Vec<Box<dyn Parser<DefaultSet, DefaultIdGen>>> = vec![
+            Box::new(ForwardParser::default()),
+            Box::new(DelayParser::default()),
+            Box::new(PreParser::default()),
+        ];
+
+        for mut parser in parsers {
+            let mut set = DefaultSet::new();
+
+            set.initialize_utils().unwrap();
+            set.initialize_prefixs();
+
+            let name = set.add_opt("--name=str").unwrap().commit().unwrap();
+            let count = set.add_opt("--count=int").unwrap().commit().unwrap();
+            let level = set.add_opt("--level=uint").unwrap().commit().unwrap();
+            let tag = set.add_opt("--tag=array").unwrap().commit().unwrap();
+
+            set.subscribe_from(parser.as_mut());
+            parser.publish_to(set);
+            parser.set_values(&[
+                (name, OptValue::from_str("foo")),
+                (count, OptValue::from_int(42)),
+                (level, OptValue::from_str("7")),
+                (tag, OptValue::from_str("a")),
+                (tag, OptValue::from_str("b")),
+            ]).unwrap();
+
+            assert_eq!(parser.value_of_str(name).unwrap(), "foo");
+            assert_eq!(parser.value_of_int(count).unwrap(), 42);
+            assert_eq!(parser.value_of_uint(level).unwrap(), 7);
+            assert_eq!(parser.get_opt(tag).unwrap().value().as_vec(), Some(&vec![String::from("b")]));
+            assert!(parser.set_values(&[(count, OptValue::from_str("abc"))]).is_err());
+            assert!(matches!(parser.set_values(&[(crate::id::Identifier::new(42), OptValue::from_int(1))]), Err(Error::InvaldOptionId(_))));
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_reset_keep_any_default_work() {
+        let mut set = DefaultSet::new();
+        let mut parser = ForwardParser::new(DefaultIdGen::default());
+        let default = std::env::temp_dir();
+
+        set.initialize_utils().unwrap();
+        set.initialize_prefixs();
+        set.add_utils(Box::new(example::PathUtils::new())).unwrap();
+
+        let mut commit = set.add_opt("-p=path").unwrap();
+
+        commit.set_deafult_value(OptValue::from_path(default.clone()));
+
+        let id = commit.commit().unwrap();
+
+        set.subscribe_from(&mut parser);
+        parser.publish_to(set);
+        assert_eq!(parser.get_opt(id).unwrap().value().as_path(), Some(default.as_path()));
+
+        let mut ai = ArgIterator::new();
+
+        ai.set_args(&mut ["-p", "."].iter().map(|&v|String::from(v)));
+        parser.parse(&mut ai).unwrap();
+        assert_eq!(parser.get_opt(id).unwrap().value().as_path(), Some(std::path::Path::new(".")));
+
+        parser.reset();
+        assert_eq!(parser.get_opt(id).unwrap().value().as_path(), Some(default.as_path()));
+        parser.reset();
+        assert_eq!(parser.get_opt(id).unwrap().value().as_path(), Some(default.as_path()));
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_interpolation_work() {
+        let mut set = DefaultSet::new();
+        let mut parser = ForwardParser::new(DefaultIdGen::default());
+
+        set.initialize_utils().unwrap();
+        set.initialize_prefixs();
+
+        let data_dir = set.add_opt("--data-dir=str").unwrap().commit().unwrap();
+        let mut commit = set.add_opt("--log-dir=str").unwrap();
+
+        commit.set_deafult_value(OptValue::from_str("${data-dir}/logs"));
+        let log_dir = commit.commit().unwrap();
+        let mut commit = set.add_opt("--home=str").unwrap();
+
+        commit.set_deafult_value(OptValue::from_str("${HOME}"));
+        let home = commit.commit().unwrap();
+
+        let mut ai = ArgIterator::new();
+
+        ai.set_args(&mut ["--data-dir", "/var/app"].iter().map(|&v|String::from(v)));
+        set.subscribe_from(&mut parser);
+        parser.publish_to(set);
+        parser.parse(&mut ai).unwrap();
+
+        // the interpolation is disabled by default
+        assert_eq!(parser.get_opt(log_dir).unwrap().value().as_str(), Some(&String::from("${data-dir}/logs")));
+
+        let mut ai = ArgIterator::new();
+
+        parser.reset();
+        parser.set_interpolate(true);
+        ai.set_args(&mut ["--data-dir", "/var/app"].iter().map(|&v|String::from(v)));
+        parser.parse(&mut ai).unwrap();
+
+        assert_eq!(parser.get_opt(data_dir).unwrap().value().as_str(), Some(&String::from("/var/app")));
+        assert_eq!(parser.get_opt(log_dir).unwrap().value().as_str(), Some(&String::from("/var/app/logs")));
+        assert_eq!(parser.get_opt(home).unwrap().value().as_str(), Some(&String::from("${HOME}")));
+
+        let mut set = DefaultSet::new();
+        let mut parser = DelayParser::new(DefaultIdGen::default());
+
+        set.initialize_utils().unwrap();
+        set.initialize_prefixs();
+
+        set.add_opt("--a=str").unwrap().commit().unwrap();
+        set.add_opt("--b=str").unwrap().commit().unwrap();
+
+        let mut ai = ArgIterator::new();
+
+        ai.set_args(&mut ["--a", "${b}", "--b", "x${a}"].iter().map(|&v|String::from(v)));
+        set.subscribe_from(&mut parser);
+        parser.publish_to(set);
+        parser.set_interpolate(true);
+        assert!(matches!(parser.parse(&mut ai), Err(Error::InterpolationCycle(_))));
+
+        for (value, ret) in [("${o}/x", None), ("${--o}/x", Some("b/x")), ("${-o}/x", Some("a/x"))].iter() {
+            let mut set = DefaultSet::new();
+            let mut parser = PreParser::new(DefaultIdGen::default());
+
+            set.initialize_utils().unwrap();
+            set.initialize_prefixs();
+
+            set.add_opt("-o=str").unwrap().commit().unwrap();
+            set.add_opt("--o=str").unwrap().commit().unwrap();
+
+            let out = set.add_opt("--out=str").unwrap().commit().unwrap();
+            let mut ai = ArgIterator::new();
+
+            ai.set_args(&mut ["-o", "a", "--o", "b", "--out", value].iter().map(|&v|String::from(v)));
+            set.subscribe_from(&mut parser);
+            parser.publish_to(set);
+            parser.set_interpolate(true);
+
+            match ret {
+                Some(ret) => {
+                    parser.parse(&mut ai).unwrap();
+                    assert_eq!(parser.get_opt(out).unwrap().value().as_str(), Some(&String::from(*ret)));
+                }
+                None => {
+                    assert!(matches!(
+                        parser.parse(&mut ai),
+                        Err(Error::AmbiguousOption(name, names)) if name == "o" && names == vec![String::from("--o"), String::from("-o")]
+                    ));
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_map_opt_work() {
+        for (args, ret) in [
+            (vec!["-D", "a=1", "-D", "b=2", "-D", "x=a=b", "-D", "K=a\\,b"], true),
+            (vec!["-D", "a=1", "-D", "b"], false),
+            (vec!["-D", "K=a,b"], false),
+        ].iter() {
+            let mut set = DefaultSet::new();
+            let mut parser = ForwardParser::new(DefaultIdGen::default());
+
+            set.initialize_utils().unwrap();
+            set.initialize_prefixs();
+
+            let define = set.add_opt("-D=map").unwrap().commit().unwrap();
+            let mut ai = ArgIterator::new();
+
+            ai.set_args(&mut args.iter().map(|&v|String::from(v)));
+            set.subscribe_from(&mut parser);
+            parser.publish_to(set);
+
+            if *ret {
+                parser.parse(&mut ai).unwrap();
+
+                let map = parser.get_opt(define).unwrap().value().as_map().unwrap();
+
+                assert_eq!(map.len(), 4);
+                assert_eq!(map.get("a"), Some(&String::from("1")));
+                assert_eq!(map.get("b"), Some(&String::from("2")));
+                assert_eq!(map.get("x"), Some(&String::from("a=b")));
+                assert_eq!(map.get("K"), Some(&String::from("a,b")));
+            }
+            else {
+                assert!(matches!(parser.parse(&mut ai), Err(Error::InvaldOptionValue(_, _))));
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_response_file_value_work() {
+        let path = std::env::temp_dir().join(format!("getopt-rs-response-{}.txt", std::process::id()));
+
+        std::fs::write(&path, "--message=\"hello world\"\n--name 'foo  bar'\n\"a b\"\n").unwrap();
+
+        let mut set = DefaultSet::new();
+        let mut parser = ForwardParser::new(DefaultIdGen::default());
+
+        set.initialize_utils().unwrap();
+        set.initialize_prefixs();
+
+        let message = set.add_opt("--message=str").unwrap().commit().unwrap();
+        let name = set.add_opt("--name=str").unwrap().commit().unwrap();
+
+        let mut ai = ArgIterator::from_file(&path).unwrap();
+
+        std::fs::remove_file(&path).unwrap();
+        set.subscribe_from(&mut parser);
+        parser.publish_to(set);
+        parser.parse(&mut ai).unwrap();
+
+        assert_eq!(parser.get_opt(message).unwrap().value().as_str(), Some(&String::from("hello world")));
+        assert_eq!(parser.get_opt(name).unwrap().value().as_str(), Some(&String::from("foo  bar")));
+        assert_eq!(parser.noa(), &vec![String::from("a b")]);
+        assert!(ArgIterator::from_file(&path).is_err());
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_trace_work() {
+        let mut set = DefaultSet::new();
+        let mut parser = ForwardParser::new(DefaultIdGen::default());
+
+        set.initialize_utils().unwrap();
+        set.initialize_prefixs();
+
+        set.add_opt("-c=array").unwrap().commit().unwrap();
+        set.add_opt("-i=bool").unwrap().commit().unwrap();
+        set.add_opt("-o=str").unwrap().commit().unwrap();
+
+        let mut ai = ArgIterator::new();
+
+        ai.set_args(&mut ["-c", "c", "-i"].iter().map(|&v|String::from(v)));
+        set.subscribe_from(&mut parser);
+        parser.publish_to(set);
+        parser.parse(&mut ai).unwrap();
+
+        let trace = parser.trace();
+        let c = trace.get("-c").unwrap();
+        let i = trace.get("-i").unwrap();
+        let o = trace.get("-o").unwrap();
+
+        assert_eq!(c.count, 1);
+        assert_eq!(c.styles, vec![String::from("GS_Argument")]);
+        assert_eq!(c.value, OptValue::from_vec(vec![String::from("c")]));
+        assert_eq!(i.count, 1);
+        assert_eq!(i.styles, vec![String::from("GS_Boolean")]);
+        assert_eq!(i.value, OptValue::from_bool(true));
+        assert!(! o.is_matched());
+
+        let table = format!("{}", trace);
+
+        assert!(table.starts_with("name"));
+        assert_eq!(table.lines().count(), 4);
+
+        parser.reset();
+        assert!(! parser.trace().get("-c").unwrap().is_matched());
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_max_positionals_work() {
+        for (args, ret) in [
+            (vec!["-i", "a", "b"], true),
+            (vec!["a", "-i", "b", "c"], false),
+        ].iter() {
+            let mut set = DefaultSet::new();
+            let mut parser = ForwardParser::new(DefaultIdGen::default());
+
+            set.initialize_utils().unwrap();
+            set.initialize_prefixs();
+            set.add_opt("-i=bool").unwrap().commit().unwrap();
+            parser.set_max_positionals(Some(2));
+
+            let mut ai = ArgIterator::new();
+
+            ai.set_args(&mut args.iter().map(|&v|String::from(v)));
+            set.subscribe_from(&mut parser);
+            parser.publish_to(set);
+
+            if *ret {
+                assert!(parser.parse(&mut ai).is_ok());
+            }
+            else {
+                assert!(matches!(parser.parse(&mut ai), Err(Error::TooManyPositionals { max: 2, got: 3 })));
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_raw_tokens_work() {
+        for record_raw in [true, false].iter() {
+            let parsers: Vec<Box<dyn Parser<DefaultSet, DefaultIdGen>>> = vec![
+                Box::new(ForwardParser::new(DefaultIdGen::default())),
+                Box::new(DelayParser::new(DefaultIdGen::default())),
+                Box::new(PreParser::new(DefaultIdGen::default())),
+            ];
+
+            for mut parser in parsers {
+                let mut set = DefaultSet::new();
+
+                set.initialize_utils().unwrap();
+                set.initialize_prefixs();
+                set.set_record_raw(*record_raw);
+
+                let cpp = set.add_opt("-cpp=array").unwrap().commit().unwrap();
+                let debug = set.add_opt("-d=bool").unwrap().commit().unwrap();
+                let pos = set.add_opt("file=pos@1").unwrap().commit().unwrap();
+                let mut ai = ArgIterator::new();
+
+                ai.set_args(&mut ["-cpp=cxx", "foo", "-cpp", "c++", "-d"].iter().map(|&v|String::from(v)));
+                set.subscribe_from(parser.as_mut());
+                parser.publish_to(set);
+                parser.parse(&mut ai).unwrap();
+
+                let set = parser.set().as_ref().unwrap();
+
+                assert_eq!(set.get_opt(cpp).unwrap().value().as_vec(), Some(&vec![String::from("cxx"), String::from("c++")]));
+                if *record_raw {
+                    assert_eq!(set.raw_tokens(cpp), &["-cpp=cxx", "-cpp", "c++"]);
+                    assert_eq!(set.raw_tokens(debug), &["-d"]);
+                }
+                else {
+                    assert!(set.raw_tokens(cpp).is_empty());
+                    assert!(set.raw_tokens(debug).is_empty());
+                }
+                assert!(set.raw_tokens(pos).is_empty());
+            }
+        }
+    }
+
+    #[cfg(feature="serde")]
+    #[test]
+    fn make_sure_result_json_work() {
+        let parsers: Vec<Box<dyn Parser<DefaultSet, DefaultIdGen>>> = vec![
+            Box::new(ForwardParser::new(DefaultIdGen::default())),
+            Box::new(DelayParser::new(DefaultIdGen::default())),
+            Box::new(PreParser::new(DefaultIdGen::default())),
+        ];
+
+        for mut parser in parsers {
+            let mut set = DefaultSet::new();
+
+            set.initialize_utils().unwrap();
+            set.initialize_prefixs();
+            set.add_opt("--count=int").unwrap().commit().unwrap();
+            set.add_opt("-v=bool").unwrap().commit().unwrap();
+            set.add_opt("--name=str").unwrap().commit().unwrap();
+            set.subscribe_from(parser.as_mut());
+            parser.publish_to(set);
+
+            let mut ai = ArgIterator::new();
+
+            ai.set_args(&mut ["--count", "42", "a", "-v", "b"].iter().map(|&v|String::from(v)));
+            parser.parse(&mut ai).unwrap();
+
+            let json: serde_json::Value = serde_json::from_str(&parser.result_json()).unwrap();
+            let options = json["options"].as_array().unwrap();
+
+            assert_eq!(options.len(), 2);
+            assert_eq!(options[0]["name"], "--count");
+            assert_eq!(options[0]["type"], "int");
+            assert_eq!(options[0]["value"], 42);
+            assert_eq!(options[0]["occurrence"], 1);
+            assert_eq!(options[1]["name"], "-v");
+            assert_eq!(options[1]["value"], true);
+            assert_eq!(json["positionals"], serde_json::json!(["a", "b"]));
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_occurrence_work() {
+        let parsers: Vec<Box<dyn Parser<DefaultSet, DefaultIdGen>>> = vec![
+            Box::new(ForwardParser::new(DefaultIdGen::default())),
+            Box::new(DelayParser::new(DefaultIdGen::default())),
+            Box::new(PreParser::new(DefaultIdGen::default())),
+        ];
+
+        for mut parser in parsers {
+            let mut set = DefaultSet::new();
+
+            set.initialize_utils().unwrap();
+            set.initialize_prefixs();
+
+            let c = set.add_opt("-c=str").unwrap().commit().unwrap();
+            let d = set.add_opt("-d=bool").unwrap().commit().unwrap();
+            let mut ai = ArgIterator::new();
+
+            ai.set_args(&mut ["-c", "a", "-c", "b"].iter().map(|&v|String::from(v)));
+            set.subscribe_from(parser.as_mut());
+            parser.publish_to(set);
+            parser.parse(&mut ai).unwrap();
+
+            assert_eq!(parser.occurrence(c), 2);
+            assert_eq!(parser.occurrence(d), 0);
+            assert_eq!(parser.get_opt(c).unwrap().value().as_str(), Some(&String::from("b")));
+
+            parser.reset_values();
+            assert_eq!(parser.occurrence(c), 0);
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_exclusive_group_work() {
+        for (args, ret) in [
+            (vec!["--json"], true),
+            (vec!["--yaml", "foo"], true),
+            (vec![], true),
+            (vec!["--json", "--xml"], false),
+        ].iter() {
+            let parsers: Vec<Box<dyn Parser<DefaultSet, DefaultIdGen>>> = vec![
+                Box::new(ForwardParser::new(DefaultIdGen::default())),
+                Box::new(DelayParser::new(DefaultIdGen::default())),
+            ];
+
+            for mut parser in parsers {
+                let mut set = DefaultSet::new();
+
+                set.initialize_utils().unwrap();
+                set.initialize_prefixs();
+
+                let json = set.add_opt("--json=bool").unwrap().commit().unwrap();
+                let yaml = set.add_opt("--yaml=bool").unwrap().commit().unwrap();
+                let xml = set.add_opt("--xml=bool").unwrap().commit().unwrap();
+                let mut ai = ArgIterator::new();
+
+                ai.set_args(&mut args.iter().map(|&v|String::from(v)));
+                set.subscribe_from(parser.as_mut());
+                parser.publish_to(set);
+                parser.add_exclusive_group(&[json, yaml, xml]);
+
+                if *ret {
+                    assert!(parser.parse(&mut ai).is_ok());
+                }
+                else {
+                    assert!(matches!(parser.parse(&mut ai), Err(Error::ConflictOption(names)) if names == "--json, --xml"));
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_requires_work() {
+        for (args, ret) in [
+            (vec!["--ssl-cert", "a.pem", "--ssl-key", "a.key", "--ssl-ca", "ca.pem"], None),
+            (vec![], None),
+            (vec!["--ssl-key", "a.key"], None),
+            (vec!["--ssl-cert", "a.pem"], Some("--ssl-key")),
+            (vec!["--ssl-cert", "a.pem", "--ssl-key", "a.key"], Some("--ssl-ca")),
+        ].iter() {
+            let mut set = DefaultSet::new();
+            let mut parser = ForwardParser::new(DefaultIdGen::default());
+
+            set.initialize_utils().unwrap();
+            set.initialize_prefixs();
+
+            let cert = set.add_opt("--ssl-cert=str").unwrap().commit().unwrap();
+            let key = set.add_opt("--ssl-key=str").unwrap().commit().unwrap();
+            let ca = set.add_opt("--ssl-ca=str").unwrap().commit().unwrap();
+            let mut ai = ArgIterator::new();
+
+            ai.set_args(&mut args.iter().map(|&v|String::from(v)));
+            set.subscribe_from(&mut parser);
+            parser.publish_to(set);
+            parser.add_requires(cert, key);
+            parser.add_requires(cert, ca);
+
+            match ret {
+                None => {
+                    assert!(parser.parse(&mut ai).is_ok());
+                }
+                Some(dependency) => {
+                    assert!(matches!(
+                        parser.parse(&mut ai),
+                        Err(Error::MissingDependency(name, dep)) if name == "--ssl-cert" && &dep == dependency
+                    ));
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_embedded_value_before_multiple_work() {
+        let parsers: Vec<Box<dyn Parser<DefaultSet, DefaultIdGen>>> = vec![
+            Box::new(ForwardParser::new(DefaultIdGen::default())),
+            Box::new(DelayParser::new(DefaultIdGen::default())),
+            Box::new(PreParser::new(DefaultIdGen::default())),
+        ];
+
+        for mut parser in parsers {
+            let mut set = DefaultSet::new();
+
+            set.initialize_utils().unwrap();
+            set.initialize_prefixs();
+
+            let o = set.add_opt("-O=uint").unwrap().commit().unwrap();
+            let two = set.add_opt("-2=bool").unwrap().commit().unwrap();
+            let a = set.add_opt("-a=bool").unwrap().commit().unwrap();
+            let b = set.add_opt("-b=bool").unwrap().commit().unwrap();
+            let c = set.add_opt("-c=bool").unwrap().commit().unwrap();
+            let mut ai = ArgIterator::new();
+
+            ai.set_args(&mut ["-O2", "-abc", "foo"].iter().map(|&v|String::from(v)));
+            set.subscribe_from(parser.as_mut());
+            parser.publish_to(set);
+            parser.parse(&mut ai).unwrap();
+
+            assert_eq!(parser.get_opt(o).unwrap().value().as_uint(), Some(&2));
+            assert_ne!(parser.get_opt(two).unwrap().value().as_bool(), Some(&true));
+            for id in [a, b, c].iter() {
+                assert_eq!(parser.get_opt(*id).unwrap().value().as_bool(), Some(&true));
+            }
+            assert_eq!(parser.noa(), &vec![String::from("foo")]);
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_pre_check_all_callbacks_work() {
+        for mismatch in [true, false].iter() {
+            let mut set = DefaultSet::new();
+            let mut parser = ForwardParser::new(DefaultIdGen::default());
+
+            set.initialize_utils().unwrap();
+            set.initialize_prefixs();
+
+            let debug = set.add_opt("-d=bool").unwrap().commit().unwrap();
+            let file = set.add_opt("file=pos@1").unwrap().commit().unwrap();
+            let name = set.add_opt("-n=str").unwrap().commit().unwrap();
+            let mut ai = ArgIterator::new();
+
+            ai.set_args(&mut ["-d", "foo"].iter().map(|&v|String::from(v)));
+            set.subscribe_from(&mut parser);
+            parser.publish_to(set);
+            parser.set_callback(debug, OptCallback::from_value(Box::new(SimpleValueCallback::new(|_| Ok(true)))));
+            parser.set_callback(file, OptCallback::from_index(Box::new(SimpleIndexCallback::new(|_, _| Ok(true)))));
+            if *mismatch {
+                parser.set_callback(name, OptCallback::from_index(Box::new(SimpleIndexCallback::new(|_, _| Ok(true)))));
+                assert!(matches!(parser.parse(&mut ai), Err(Error::InvalidCallbackType(id, _)) if id == format!("{:?}", name)));
+            }
+            else {
+                parser.set_callback(name, OptCallback::Null);
+                assert!(parser.parse(&mut ai).is_ok());
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_array_unique_work() {
+        for (args, unique, ret) in [
+            (vec!["-a", "x", "-a", "x"], false, vec!["x", "x"]),
+            (vec!["-a", "x", "-a", "x"], true, vec!["x"]),
+            (vec!["-a", "x", "-a", "y", "-a=x"], true, vec!["x", "y"]),
+        ].iter() {
+            let mut set = DefaultSet::new();
+            let mut parser = ForwardParser::new(DefaultIdGen::default());
+
+            set.initialize_utils().unwrap();
+            set.initialize_prefixs();
+
+            let mut commit = set.add_opt("-a=array").unwrap();
+
+            commit.set_unique(*unique);
+
+            let id = commit.commit().unwrap();
+            let mut ai = ArgIterator::new();
+
+            ai.set_args(&mut args.iter().map(|&v|String::from(v)));
+            set.subscribe_from(&mut parser);
+            parser.publish_to(set);
+            parser.parse(&mut ai).unwrap();
+
+            assert_eq!(parser.get_opt(id).unwrap().value().as_vec(), Some(&ret.iter().map(|&v|String::from(v)).collect()));
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_abbrev_work() {
+        for (args, ret) in [
+            (vec!["--ver", "--cou=1"], Ok((true, Some(1)))),
+            (vec!["--verbose", "--count", "2"], Ok((true, Some(2)))),
+            (vec!["--verb"], Ok((true, None))),
+            (vec!["-v", "-c", "3"], Ok((false, None))),
+            (vec!["--c", "1"], Err(vec!["--count", "--color"])),
+        ].iter() {
+            let mut set = DefaultSet::new();
+            let mut parser = ForwardParser::new(DefaultIdGen::default());
+
+            set.initialize_utils().unwrap();
+            set.initialize_prefixs();
+            set.set_allow_abbrev(true);
+
+            let verbose = set.add_opt("--verbose=bool").unwrap().commit().unwrap();
+            let count = set.add_opt("--count=int").unwrap().commit().unwrap();
+
+            set.add_opt("--color=bool").unwrap().commit().unwrap();
+
+            let mut ai = ArgIterator::new();
+
+            ai.set_args(&mut args.iter().map(|&v|String::from(v)));
+            set.subscribe_from(&mut parser);
+            parser.publish_to(set);
+
+            match ret {
+                Ok((verbose_value, count_value)) => {
+                    parser.parse(&mut ai).unwrap();
+                    assert_eq!(parser.get_opt(verbose).unwrap().value().as_bool() == Some(&true), *verbose_value);
+                    assert_eq!(parser.get_opt(count).unwrap().value().as_int(), count_value.as_ref());
+                }
+                Err(candidates) => {
+                    assert!(matches!(
+                        parser.parse(&mut ai),
+                        Err(Error::AmbiguousOption(name, names)) if name == "--c" && &names == candidates
+                    ));
+                }
+            }
+        }
+
+        let mut set = DefaultSet::new();
+        let mut parser = ForwardParser::new(DefaultIdGen::default());
+
+        set.initialize_utils().unwrap();
+        set.initialize_prefixs();
+        set.add_opt("--verbose=bool").unwrap().commit().unwrap();
+
+        let mut ai = ArgIterator::new();
+
+        ai.set_args(&mut ["--ver"].iter().map(|&v|String::from(v)));
+        set.subscribe_from(&mut parser);
+        parser.publish_to(set);
+        parser.parse(&mut ai).unwrap();
+        assert_eq!(parser.noa(), &vec![String::from("--ver")]);
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_value_of_work() {
+        let mut set = DefaultSet::new();
+        let mut parser = ForwardParser::new(DefaultIdGen::default());
+
+        set.initialize_utils().unwrap();
+        set.initialize_prefixs();
+
+        let count = set.add_opt("--count=int").unwrap().commit().unwrap();
+        let size = set.add_opt("--size=uint").unwrap().commit().unwrap();
+        let ratio = set.add_opt("--ratio=flt").unwrap().commit().unwrap();
+        let name = set.add_opt("--name=str").unwrap().commit().unwrap();
+        let debug = set.add_opt("--debug=bool").unwrap().commit().unwrap();
+        let mut ai = ArgIterator::new();
+
+        ai.set_args(&mut ["--count=-1", "--size=2", "--ratio=0.5", "--name=foo", "--debug"].iter().map(|&v|String::from(v)));
+        set.subscribe_from(&mut parser);
+        parser.publish_to(set);
+        parser.parse(&mut ai).unwrap();
+
+        assert_eq!(parser.value_of_int(count).unwrap(), -1);
+        assert_eq!(parser.value_of_uint(size).unwrap(), 2);
+        assert_eq!(parser.value_of_flt(ratio).unwrap(), 0.5);
+        assert_eq!(parser.value_of_str(name).unwrap(), "foo");
+        assert_eq!(parser.value_of_bool(debug).unwrap(), true);
+        assert!(matches!(parser.value_of_str(count), Err(Error::MismatchValueType(name, type_name)) if name == "--count" && type_name == "str"));
+        assert!(matches!(parser.value_of_int(crate::id::Identifier::new(42)), Err(Error::InvaldOptionId(_))));
+    }
 }
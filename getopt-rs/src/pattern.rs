@@ -0,0 +1,228 @@
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// The index part of option string, such as `@1`, `@-1`, `@0` or `@2..4`.
+/// It is converted to [`NonOptIndex`](crate::opt::NonOptIndex) when create the option.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum PatternIndex {
+    Forward(u64),
+
+    Backward(u64),
+
+    /// The range of position, the end is included, `None` means no end.
+    Range(u64, Option<u64>),
+
+    AnyWhere,
+
+    #[default]
+    Null,
+}
+
+/// The information parsed from option string, see [`parse_opt_string`].
+#[derive(Debug, Default)]
+pub struct ParseResult {
+    pub type_name: Option<String>,
+
+    pub opt_name: Option<String>,
+
+    pub opt_prefix: Option<String>,
+
+    pub deactivate: Option<bool>,
+
+    pub optional: Option<bool>,
+
+    pub opt_index: PatternIndex,
+
+    pub default_value: Option<String>,
+
+    pub element_type: Option<String>,
+}
+
+/// Parse input string `<prefix>|<name>=<type>[!][/]@<index>{<default>}`,
+/// such as `-|o=a!`, means the force required option `o`, with a prefix "-", 
+/// and option type is `a`.
+/// `!` means the option is optional or not.
+/// `/` means the option is deactivate style or not.
+/// `{<default>}` is the default value of option, such as `--count=int{10}`.
+/// `:<element type>` is the type of array element, such as `--ports=array:uint`.
+/// Return the invalid string if parse failed.
+/// 
+/// It only depend on `core` and `alloc`, so it is available without the `std` feature.
+pub fn parse_opt_string(s: &str, prefixs: &[String]) -> Result<ParseResult, String> {
+    const SPLIT: &str = "=";
+    const DEACTIVATE: &str = "/";
+    const NO_OPTIONAL: &str = "!";
+    const INDEX: &str = "@";
+    const RANGE: &str = "..";
+    const DEFAULT_BEGIN: char = '{';
+    const DEFAULT_END: char = '}';
+    const ELEMENT: char = ':';
+
+    if s.is_empty(){
+        return Err(s.to_owned());
+    }
+
+    let mut default_value = None;
+    let mut s = s;
+
+    // the trailing `{<default>}`
+    if let Some(index) = s.find(DEFAULT_BEGIN) {
+        if ! s.ends_with(DEFAULT_END) || index == 0 {
+            return Err(s.to_owned());
+        }
+        default_value = Some(s[index + 1 .. s.len() - 1].to_owned());
+        s = &s[.. index];
+    }
+
+    let mut splited_index = 0;
+    let mut deactivate = None;
+    let mut optional = None;
+    let mut opt_index = PatternIndex::Null;
+    let opt_name;
+    let mut type_name = None;
+    let mut element_type = None;
+    let right_info;
+    let left_info;
+    let mut opt_prefix  = None;
+    let mut prefix_index = 0;
+    let without_prefix;
+
+    // prefer the longest prefix, such as `--` rather than `-`
+    for prefix in prefixs.iter() {
+        if s.starts_with(prefix) && prefix.len() > prefix_index {
+            opt_prefix = Some(prefix.clone());
+            prefix_index = prefix.len();
+        }
+    }
+
+    if prefix_index == s.len() {
+        return Err(s.to_owned());
+    }
+
+    // do we have a prefix matched
+    if opt_prefix.is_none() {
+        without_prefix = s;
+    }
+    else {
+        without_prefix = s.split_at(prefix_index).1;
+    }
+
+    let splited: Vec<_> = without_prefix.split(SPLIT).collect();
+
+    // for example, s is `-o=a!/@1`, prefix is `-`
+    if splited.len() == 2 {
+        // `o`
+        left_info = splited[0];
+        // `a!/@1`
+        right_info = splited[1];
+    }
+    else {
+        // without type, right_info is `-|o!/@1`
+        right_info = without_prefix;
+        left_info = without_prefix;
+    }
+
+    // if we have a `/`
+    if let Some(index) = right_info.rfind(DEACTIVATE) {
+        deactivate = Some(true);
+        if index != 0 {
+            splited_index = index;
+        }
+    }
+    // if we have a `!`
+    if let Some(index) = right_info.rfind(NO_OPTIONAL) {
+        optional = Some(false);
+        if index != 0 && (index < splited_index || splited_index == 0) {
+            splited_index = index;
+        }
+    }
+    // if we have a `@`
+    if let Some(index) = right_info.rfind(INDEX) {
+        let index_str = right_info.split_at(index + 1).1;
+
+        if let Some((begin, end)) = index_str.split_once(RANGE) {
+            // such as `@2..4` or `@2..`
+            let begin = begin.parse::<u64>().map_err(|_| s.to_owned())?;
+            let end = if end.is_empty() {
+                None
+            }
+            else {
+                Some(end.parse::<u64>().map_err(|_| s.to_owned())?)
+            };
+
+            if begin == 0 || end.is_some_and(|end| end < begin) {
+                return Err(s.to_owned());
+            }
+            opt_index = PatternIndex::Range(begin, end);
+        }
+        else {
+            match index_str.parse::<i64>() {
+                Ok(v) => {
+                    if v > 0 {
+                        opt_index = PatternIndex::Forward(v as u64);
+                    }
+                    else if v < 0 {
+                        opt_index = PatternIndex::Backward((-v) as u64);
+                    }
+                    else {
+                        opt_index = PatternIndex::AnyWhere;
+                    }
+                }
+                Err(_) => {
+                    return Err(s.to_owned())
+                }
+            }
+        }
+        if index != 0 && (index < splited_index || splited_index == 0) {
+            splited_index = index;
+        }
+    }
+
+    if splited.len() == 2 {
+        let inner_opt_type;
+
+        if splited_index == 0 {
+            // we not have `/`, `!` or `@`, so right_info is option type
+            inner_opt_type = right_info;
+        } else {
+            // left part is option type
+            inner_opt_type = right_info.split_at(splited_index).0;
+        };
+        
+        // such as `array:uint`
+        match inner_opt_type.split_once(ELEMENT) {
+            Some((inner_opt_type, inner_element_type)) => {
+                type_name = Some(inner_opt_type.to_owned());
+                element_type = Some(inner_element_type.to_owned());
+            }
+            None => {
+                type_name = Some(inner_opt_type.to_owned());
+            }
+        }
+        opt_name = Some(left_info.to_owned());
+    }
+    else {
+        if splited_index == 0 {
+            // we not have `/`, `!` or `@`, so right_info is option name part
+            opt_name = Some(right_info.to_owned());
+        }
+        else {
+            // left part is option name part
+            opt_name  = Some(right_info.split_at(splited_index).0.to_owned());         
+        }
+    }
+
+    debug!("Parsing ==> {:?} -> {:?} {:?} {:?}", s, opt_prefix, opt_name, type_name);
+
+    return Ok(ParseResult {
+        type_name,
+        opt_name,
+        opt_prefix,
+        deactivate,
+        optional,
+        opt_index,
+        default_value,
+        element_type,
+    })
+}
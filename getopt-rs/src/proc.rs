@@ -1,9 +1,10 @@
 
 use std::fmt::Debug;
+use std::collections::HashMap;
 use async_trait::async_trait;
 
 use crate::error::Result;
-use crate::opt::Opt;
+use crate::opt::{Opt, OptValue};
 use crate::ctx::Context;
 use crate::id::Identifier;
 
@@ -13,6 +14,80 @@ pub trait Message: Debug {
 
 pub trait Info: Debug {
     fn id(&self) -> Identifier;
+
+    /// Return the prefix and name of option, include the alias.
+    /// The option has no names will be checked by every [`Proc`], see [`InfoIndex`].
+    fn names(&self) -> &[(String, String)] {
+        &[]
+    }
+}
+
+/// InfoIndex index the [`Info`] by the prefix and name of option,
+/// so the parser can find the options may matched by [`Proc`] without checking every option.
+#[derive(Debug, Default)]
+pub struct InfoIndex {
+    names: HashMap<(String, String), Vec<usize>>,
+
+    unnamed: Vec<usize>,
+
+    total: usize,
+}
+
+impl InfoIndex {
+    pub fn new() -> Self {
+        Self {
+            names: HashMap::new(),
+            unnamed: vec![],
+            total: 0,
+        }
+    }
+
+    /// Add the `info` at position `index` of parser.
+    pub fn insert(&mut self, index: usize, info: &dyn Info) {
+        let names = info.names();
+
+        if names.is_empty() {
+            self.unnamed.push(index);
+        }
+        else {
+            for (prefix, name) in names {
+                let indexs = self.names.entry((prefix.clone(), name.clone())).or_insert(vec![]);
+
+                if ! indexs.contains(&index) {
+                    indexs.push(index);
+                }
+            }
+        }
+        self.total = self.total.max(index + 1);
+    }
+
+    /// Return the position of options may matched by `proc` in order.
+    /// Return all the position if any context of `proc` not match the option by name.
+    pub fn candidates(&self, proc: &dyn Proc) -> Vec<usize> {
+        let mut ret = self.unnamed.clone();
+
+        for index in 0 .. proc.len() {
+            match proc.get_ctx(index).and_then(|ctx| ctx.get_prefix_and_name()) {
+                Some((prefix, name)) => {
+                    if let Some(indexs) = self.names.get(&(prefix.to_owned(), name.to_owned())) {
+                        ret.extend(indexs.iter());
+                    }
+                }
+                None => {
+                    return (0 .. self.total).collect();
+                }
+            }
+        }
+        ret.sort_unstable();
+        ret.dedup();
+        ret
+    }
+
+    pub fn clear(&mut self) {
+        self.names.clear();
+        self.unnamed.clear();
+        self.total = 0;
+    }
 }
 
 /// Publisher is the collection of [`Subscriber`].
@@ -46,13 +121,28 @@ pub trait Proc: Debug {
 
     /// Process the option
     #[cfg(not(feature="async"))]
-    fn process(&mut self, opt: &mut dyn Opt) -> Result<Option<u64>>;
+    fn process(&mut self, opt: &mut dyn Opt) -> Result<Option<u64>> {
+        self.process_with(opt, &mut |value| Ok(value))
+    }
 
     /// Process the option
     #[cfg(feature="async")]
-    async fn process(&mut self, opt: &mut dyn Opt) -> Result<Option<u64>>;
+    async fn process(&mut self, opt: &mut dyn Opt) -> Result<Option<u64>> {
+        self.process_with(opt, &mut |value| Ok(value)).await
+    }
 
-    /// If the matched option need argument
+    /// Process the option, the parsed value will be passed to `transform` before it stored,
+    /// see [`Context::process_with`].
+    #[cfg(not(feature="async"))]
+    fn process_with(&mut self, opt: &mut dyn Opt, transform: &mut dyn FnMut(OptValue) -> Result<OptValue>) -> Result<Option<u64>>;
+
+    /// Process the option, the parsed value will be passed to `transform` before it stored,
+    /// see [`Context::process_with`].
+    #[cfg(feature="async")]
+    async fn process_with(&mut self, opt: &mut dyn Opt, transform: &mut dyn FnMut(OptValue) -> Result<OptValue>) -> Result<Option<u64>>;
+
+    /// If any matched option need argument.
+    /// It is kept across the options processed by the proc, such as the `f` of `-xvf file`.
     fn is_need_argument(&self) -> bool;
 
     /// If all the context matched
@@ -68,7 +158,7 @@ impl Message for Box<dyn Proc> {
 }
 
 /// Default [`Proc`], it will match every [`Context`] with given [`Opt`].
-/// It will call [`Context::process`] on the [`Opt`] if matched.
+/// It will call [`Context::process_with`] on the [`Opt`] if matched.
 #[derive(Debug)]
 pub struct SequenceProc {
     id: Identifier,
@@ -112,7 +202,7 @@ impl Proc for SequenceProc {
     }
 
     #[cfg(not(feature="async"))]
-    fn process(&mut self, opt: &mut dyn Opt) -> Result<Option<u64>> {
+    fn process_with(&mut self, opt: &mut dyn Opt, transform: &mut dyn FnMut(OptValue) -> Result<OptValue>) -> Result<Option<u64>> {
         if self.is_matched() {
             debug!("Skip process {:?}, it matched", Proc::id(self));
             return Ok(self.matched_index);
@@ -120,11 +210,10 @@ impl Proc for SequenceProc {
         let mut matched = false;
 
         self.matched_index = None;
-        self.need_argument = false;
         for ctx in self.contexts.iter_mut() {
             if ! ctx.is_matched() {
                 if ctx.match_opt(opt) {
-                    ctx.process(opt)?;
+                    ctx.process_with(opt, transform)?;
                     self.need_argument = self.need_argument || ctx.is_need_argument();
                     matched = true;
                 }
@@ -138,19 +227,18 @@ impl Proc for SequenceProc {
     }
 
     #[cfg(feature="async")]
-    async fn process(&mut self, opt: &mut dyn Opt) -> Result<u64> {
+    async fn process_with(&mut self, opt: &mut dyn Opt, transform: &mut dyn FnMut(OptValue) -> Result<OptValue>) -> Result<Option<u64>> {
         if self.is_matched() {
             debug!("Skip process {:?}, it matched", Proc::id(self));
-            return Ok(self.matched_index.as_ref());
+            return Ok(self.matched_index);
         }
         let mut matched = false;
 
         self.matched_index = None;
-        self.need_argument = false;
         for ctx in self.contexts.iter_mut() {
             if ! ctx.is_matched() {
                 if ctx.match_opt(opt) {
-                    ctx.process(opt)?;
+                    ctx.process_with(opt, transform)?;
                     self.need_argument = self.need_argument || ctx.is_need_argument();
                     matched = true;
                 }
@@ -160,7 +248,7 @@ impl Proc for SequenceProc {
             // currently, SequenceProc not prcess non-option index problem
             self.matched_index = Some(0);
         }
-        Ok(self.matched_index.as_ref())
+        Ok(self.matched_index)
     }
 
     fn is_matched(&self) -> bool {
@@ -180,7 +268,7 @@ impl Proc for SequenceProc {
 
 
 /// Default [`Proc`], it will match the [`Context`] with given [`Opt`].
-/// It will call [`Context::process`] on the [`Opt`] if matched.
+/// It will call [`Context::process_with`] on the [`Opt`] if matched.
 #[derive(Debug)]
 pub struct SingleCtxProc {
     id: Identifier,
@@ -224,17 +312,16 @@ impl Proc for SingleCtxProc {
     }
 
     #[cfg(not(feature="async"))]
-    fn process(&mut self, opt: &mut dyn Opt) -> Result<Option<u64>> {
+    fn process_with(&mut self, opt: &mut dyn Opt, transform: &mut dyn FnMut(OptValue) -> Result<OptValue>) -> Result<Option<u64>> {
         if self.is_matched() {
             debug!("Skip process {:?}, it matched", Proc::id(self));
             return Ok(self.matched_index);
         }
-        self.need_argument = false;
         self.matched_index = None;
         if let Some(ctx) = &mut self.context {
             if ! ctx.is_matched() {
                 if ctx.match_opt(opt) {
-                    ctx.process(opt)?;
+                    ctx.process_with(opt, transform)?;
                     self.need_argument = self.need_argument || ctx.is_need_argument();
                     self.matched_index = Some(ctx.get_matched_index().unwrap().clone());
                 }
@@ -244,17 +331,16 @@ impl Proc for SingleCtxProc {
     }
 
     #[cfg(feature="async")]
-    async fn process(&mut self, opt: &mut dyn Opt) -> Result<Option<u64>> {
+    async fn process_with(&mut self, opt: &mut dyn Opt, transform: &mut dyn FnMut(OptValue) -> Result<OptValue>) -> Result<Option<u64>> {
         if self.is_matched() {
             debug!("Skip process {:?}, it matched", Proc::id(self));
             return Ok(self.matched_index);
         }
-        self.need_argument = false;
         self.matched_index = None;
-        if let Some(ctx) = &self.context {
+        if let Some(ctx) = &mut self.context {
             if ! ctx.is_matched() {
                 if ctx.match_opt(opt) {
-                    ctx.process(opt)?;
+                    ctx.process_with(opt, transform)?;
                     self.need_argument = self.need_argument || ctx.is_need_argument();
                     self.matched_index = Some(ctx.get_matched_index().unwrap());
                 }
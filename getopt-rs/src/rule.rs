@@ -0,0 +1,97 @@
+use std::fmt::Debug;
+use std::collections::HashMap;
+
+use crate::id::Identifier;
+
+/// The group rule between options, it will be checked after the parse finished.
+/// An option counts only if it is matched on command line, the default value is ignored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GroupRule {
+    /// At most one of the options can have value.
+    Exclusive(Vec<Identifier>),
+
+    /// If the first option has value, the second option must have value.
+    Requires(Identifier, Identifier),
+
+    /// At least one of the options must have value.
+    RequiredOneOf(Vec<Identifier>),
+}
+
+impl GroupRule {
+    pub fn exclusive(ids: &[Identifier]) -> Self {
+        Self::Exclusive(ids.to_vec())
+    }
+
+    pub fn requires(id: Identifier, dependency: Identifier) -> Self {
+        Self::Requires(id, dependency)
+    }
+
+    pub fn required_one_of(ids: &[Identifier]) -> Self {
+        Self::RequiredOneOf(ids.to_vec())
+    }
+
+    /// Return true if the rule is satisfied by the options matched in `matched_styles`.
+    pub fn check(&self, matched_styles: &HashMap<Identifier, Vec<String>>) -> bool {
+        let has_value = |id: &Identifier| {
+            matched_styles.get(id).is_some_and(|styles| ! styles.is_empty())
+        };
+
+        match self {
+            Self::Exclusive(ids) => {
+                ids.iter().filter(|id| has_value(id)).count() <= 1
+            }
+            Self::Requires(id, dependency) => {
+                ! has_value(id) || has_value(dependency)
+            }
+            Self::RequiredOneOf(ids) => {
+                ids.iter().any(|id| has_value(id))
+            }
+        }
+    }
+}
+
+/// The evaluation result of an [`GroupRule`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleResult {
+    pub rule: GroupRule,
+
+    pub passed: bool,
+}
+
+/// ValidationReport hold the evaluation result of every [`GroupRule`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationReport {
+    results: Vec<RuleResult>,
+}
+
+impl ValidationReport {
+    pub fn new() -> Self {
+        Self {
+            results: vec![],
+        }
+    }
+
+    /// Evaluate all the `rules` without short-circuiting.
+    pub fn from_rules(matched_styles: &HashMap<Identifier, Vec<String>>, rules: &[GroupRule]) -> Self {
+        Self {
+            results: rules.iter().map(|rule| RuleResult {
+                rule: rule.clone(),
+                passed: rule.check(matched_styles),
+            }).collect(),
+        }
+    }
+
+    pub fn results(&self) -> &Vec<RuleResult> {
+        &self.results
+    }
+
+    /// Return all the failed rule result.
+    pub fn failed(&self) -> Vec<&RuleResult> {
+        self.results.iter().filter(|v| ! v.passed).collect()
+    }
+
+    /// Return true if all the rule passed.
+    pub fn is_passed(&self) -> bool {
+        self.results.iter().all(|v| v.passed)
+    }
+}
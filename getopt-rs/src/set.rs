@@ -9,11 +9,13 @@ use crate::opt::str::StrUtils;
 use crate::opt::uint::UintUtils;
 use crate::opt::flt::FltUtils;
 use crate::opt::array::ArrayUtils;
+use crate::opt::map::MapUtils;
 use crate::opt::bool::BoolUtils;
-use crate::nonopt::pos::PosUtils;
+use crate::opt::count::CountUtils;
+use crate::nonopt::pos::{PosUtils, PosNonOpt};
 use crate::nonopt::cmd::CmdUtils;
 use crate::nonopt::main::MainUtils;
-use crate::opt::{Opt, OptValue, NonOptIndex};
+use crate::opt::{Opt, OptValue, NonOptIndex, ValueValidator, ValidatorFn, ValueReader, CloneHelper, Style};
 use crate::error::{Error, Result};
 use crate::proc::{Proc, Publisher, Subscriber};
 use crate::utils::{Utils, CreateInfo, FilterInfo};
@@ -76,6 +78,14 @@ pub trait Set: Debug + Subscriber + Index<Identifier, Output=dyn Opt> + IndexMut
     /// Return the option mutable reference if the index exist, otherwise return None.
     fn get_opt_mut_i(&mut self, index: usize) -> Option<&mut dyn Opt>;
 
+    /// Return the first option reference match the `prefix` and `name`, or any of its alias,
+    /// otherwise return None.
+    fn get_opt_by_name(&self, prefix: &str, name: &str) -> Option<& dyn Opt>;
+
+    /// Return the first option mutable reference match the `prefix` and `name`, or any of its alias,
+    /// otherwise return None.
+    fn get_opt_mut_by_name(&mut self, prefix: &str, name: &str) -> Option<&mut dyn Opt>;
+
     /// Return the number of option.
     fn len(&self) -> usize;
 
@@ -102,7 +112,8 @@ pub trait Set: Debug + Subscriber + Index<Identifier, Output=dyn Opt> + IndexMut
     /// Return an iterator over the Set.
     fn iter(&self) -> Iter<Box<dyn Opt>>;
 
-    /// Return an mutable iterator over the Set.
+    /// Return an mutable iterator over the Set, it can be used for modify all the options,
+    /// such as mark every option optional.
     fn iter_mut(&mut self) -> IterMut<Box<dyn Opt>>;
 
     /// Set the option prefix before add the option.
@@ -114,10 +125,73 @@ pub trait Set: Debug + Subscriber + Index<Identifier, Output=dyn Opt> + IndexMut
     /// Append an prefix to the Set.
     fn app_prefix(&mut self, prefix: String);
 
+    /// Return true if the Set support the `prefix`.
+    fn has_prefix(&self, prefix: &str) -> bool {
+        self.get_prefix().iter().any(|v| v == prefix)
+    }
+
+    /// Remove the `prefix` from the Set, return false if the prefix not exists.
+    /// The options using the prefix are not removed, but they can not be matched anymore.
+    fn rem_prefix(&mut self, prefix: &str) -> bool;
+
     /// Check if the Set's options are invalid.
     fn check(&self) -> Result<bool>;
 
+    /// Return the names with prefix, such as `-c`, which used by more than one option, including alias.
+    /// The non-option has no prefix is not checked.
+    fn find_conflicts(&self) -> Vec<String> {
+        let mut names: Vec<(String, String)> = vec![];
+        let mut conflicts = vec![];
+
+        for opt in self.iter() {
+            for name in opt_names(opt.as_ref()).into_iter().filter(|(prefix, _)| ! prefix.is_empty()) {
+                let desc = format!("{}{}", name.0, name.1);
+
+                if names.contains(&name) {
+                    if ! conflicts.contains(&desc) {
+                        conflicts.push(desc);
+                    }
+                }
+                else {
+                    names.push(name);
+                }
+            }
+        }
+        conflicts
+    }
+
+    /// Enable or disable matching the long option by unambiguous prefix of name,
+    /// such as `--ver` match `--verbose`.
+    fn set_allow_abbrev(&mut self, allow_abbrev: bool);
+
+    fn is_allow_abbrev(&self) -> bool;
+
+    /// Enable or disable matching the boolean option by negation form `no-` of name,
+    /// such as `--no-force` set `--force` to false.
+    fn set_enable_no_prefix(&mut self, enable_no_prefix: bool);
+
+    fn is_enable_no_prefix(&self) -> bool;
+
+    /// Enable or disable recording the raw tokens matched the options.
+    fn set_record_raw(&mut self, record_raw: bool);
+
+    fn is_record_raw(&self) -> bool;
+
+    /// Get the raw tokens matched the option, such as `["-cpp=cxx", "-cpp", "c++"]`.
+    fn raw_tokens(&self, id: Identifier) -> &[String];
+
+    /// Append the raw tokens matched the option.
+    fn app_raw_tokens(&mut self, id: Identifier, tokens: &[String]);
+
+    /// Set the environment variable name of option `id`, see [`apply_env_values`](crate::parser::apply_env_values).
+    fn set_env(&mut self, id: Identifier, env: &str);
+
+    fn get_env(&self, id: Identifier) -> Option<&str>;
+
     fn reset(&mut self);
+
+    /// Reset the value of option `id` to default, return false if the option not exists.
+    fn reset_value_of(&mut self, id: Identifier) -> bool;
 }
 
 #[derive(Debug)]
@@ -127,32 +201,223 @@ pub struct DefaultSet {
     utils: HashMap<String, Box<dyn Utils>>,
 
     support_prefixs: Vec<String>,
+
+    allow_abbrev: bool,
+
+    enable_no_prefix: bool,
+
+    record_raw: bool,
+
+    raw_tokens: HashMap<Identifier, Vec<String>>,
+
+    clone_helpers: HashMap<String, CloneHelper>,
+
+    envs: HashMap<Identifier, String>,
 }
 
 impl DefaultSet {
+    /// Create an empty set, the utils and prefixs are not registered,
+    /// see [`initialize_utils`](DefaultSet::initialize_utils) and [`initialize_prefixs`](DefaultSet::initialize_prefixs).
     pub fn new() -> Self {
         Self {
             opts: vec![],
             utils: HashMap::new(),
             support_prefixs: vec![],
+            allow_abbrev: false,
+            enable_no_prefix: false,
+            record_raw: false,
+            raw_tokens: HashMap::new(),
+            clone_helpers: HashMap::new(),
+            envs: HashMap::new(),
         }
     }
 
+    /// Register the utils of all builtin types, they are
+    /// `int`, `str`, `uint`, `flt`, `array`, `map`, `bool`, `count`, `pos`, `cmd` and `main`.
+    /// Return [`Error::DuplicateOptionType`] if any of them already registered.
     pub fn initialize_utils(&mut self) -> Result<bool> {
         self.add_utils(Box::new(IntUtils::new()))?;
         self.add_utils(Box::new(StrUtils::new()))?;
         self.add_utils(Box::new(UintUtils::new()))?;
         self.add_utils(Box::new(FltUtils::new()))?;
         self.add_utils(Box::new(ArrayUtils::new()))?;
+        self.add_utils(Box::new(MapUtils::new()))?;
         self.add_utils(Box::new(BoolUtils::new()))?;
+        self.add_utils(Box::new(CountUtils::new()))?;
         self.add_utils(Box::new(PosUtils::new()))?;
         self.add_utils(Box::new(CmdUtils::new()))?;
         self.add_utils(Box::new(MainUtils::new()))
     }
 
+    /// Set the prefixs to `-`, `/` and `--`.
     pub fn initialize_prefixs(&mut self) {
         self.set_prefix(vec![String::from("-"), String::from("/"), String::from("--")]);
     }
+
+    /// Set the [`CloneHelper`] of option type `type_name`, it used to clone the default value when reset.
+    pub fn set_clone_helper(&mut self, type_name: &str, clone_helper: CloneHelper) {
+        self.clone_helpers.insert(type_name.to_owned(), clone_helper);
+    }
+
+    pub fn get_clone_helper(&self, type_name: &str) -> Option<&CloneHelper> {
+        self.clone_helpers.get(type_name)
+    }
+
+    /// Return [`Error::DuplicateOption`] if any name or alias of `opt` exists in current set.
+    fn check_duplicate(&self, opt: &dyn Opt) -> Result<()> {
+        for (prefix, name) in opt_names(opt) {
+            if self.get_opt_by_name(&prefix, &name).is_some() {
+                return Err(Error::DuplicateOption(format!("{}{}", prefix, name)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Return a [`Commit`] of the prebuilt `ci`, it is useful for generated option tables.
+    pub fn add_opt_cfg(&mut self, ci: CreateInfo) -> Result<Commit<'_>> {
+        Ok(Commit::new(self, ci))
+    }
+
+    /// Move the options of `other` into current set, the identifier of them will be reassigned.
+    /// The prefixs and utils of `other` not exist in current set will be added.
+    /// Return [`Error::DuplicateOption`] if any name or alias of `other` exists in current set.
+    pub fn merge(&mut self, other: DefaultSet) -> Result<()> {
+        for opt in other.opts.iter() {
+            self.check_duplicate(opt.as_ref())?;
+        }
+        for prefix in other.support_prefixs {
+            if ! self.support_prefixs.contains(&prefix) {
+                self.app_prefix(prefix);
+            }
+        }
+        for (type_name, utils) in other.utils {
+            if ! self.utils.contains_key(&type_name) {
+                self.utils.insert(type_name, utils);
+            }
+        }
+        for opt in other.opts {
+            let env = other.envs.get(&opt.id()).cloned();
+            let id = self.add_opt_raw(opt)?;
+
+            if let Some(env) = env {
+                self.set_env(id, &env);
+            }
+        }
+        Ok(())
+    }
+
+    /// Remove all the options match the [`FilterInfo`], return the number of removed options.
+    /// The identifier of left options will be reassigned in order, so get them again after removing.
+    pub fn remove_matching(&mut self, fi: &FilterInfo) -> usize {
+        let len = self.opts.len();
+        let opts = std::mem::take(&mut self.opts);
+        let mut raw_tokens = std::mem::take(&mut self.raw_tokens);
+        let mut envs = std::mem::take(&mut self.envs);
+
+        for opt in opts.into_iter().filter(|opt| ! fi.match_opt(opt.as_ref())) {
+            let old_id = opt.id();
+            let id = self.add_opt_raw(opt).unwrap();
+
+            if let Some(tokens) = raw_tokens.remove(&old_id) {
+                self.raw_tokens.insert(id, tokens);
+            }
+            if let Some(env) = envs.remove(&old_id) {
+                self.envs.insert(id, env);
+            }
+        }
+        len - self.opts.len()
+    }
+
+    /// Create a new set has same options and prefixs, the value of options are default.
+    /// The options are recreated by the utils of current set, see [`CreateInfo::from_opt`].
+    /// The new set only has the builtin utils, add the custom utils before use it.
+    /// Return Err if any option can not be recreated, the identifier of options will be changed otherwise.
+    pub fn clone_config(&self) -> Result<DefaultSet> {
+        let mut ret = DefaultSet::new();
+
+        ret.initialize_utils()?;
+        ret.set_prefix(self.support_prefixs.clone());
+        ret.set_allow_abbrev(self.allow_abbrev);
+        ret.set_enable_no_prefix(self.enable_no_prefix);
+        ret.set_record_raw(self.record_raw);
+        for opt in self.opts.iter() {
+            let utils = self.get_utils(opt.type_name()).ok_or_else(|| Error::InvalidOptionType(opt.type_name().to_owned()))?;
+            let new_opt = utils.create(opt.id(), &CreateInfo::from_opt(opt.as_ref()))?;
+            let id = ret.add_opt_raw(new_opt)?;
+
+            if let Some(env) = self.get_env(opt.id()) {
+                ret.set_env(id, env);
+            }
+        }
+        Ok(ret)
+    }
+
+    /// Compare the value of options which has same prefix and name in `other`,
+    /// return the difference of them.
+    /// The option only exists in `other` is reported with a null `old` value.
+    /// The option has `Any` value is skipped, the value of it can not be compared.
+    pub fn diff(&self, other: &DefaultSet) -> Vec<OptionDiff> {
+        let mut ret = vec![];
+
+        for opt in self.opts.iter() {
+            if let Some(other_opt) = other.get_opt_by_name(opt.prefix(), opt.name()) {
+                if opt.value().is_any() || other_opt.value().is_any() {
+                    continue;
+                }
+                if opt.value() != other_opt.value() {
+                    ret.push(OptionDiff {
+                        name: format!("{}{}", opt.prefix(), opt.name()),
+                        old: opt.value().clone(),
+                        new: other_opt.value().clone(),
+                    });
+                }
+            }
+        }
+        for other_opt in other.opts.iter() {
+            if other_opt.value().is_any() {
+                continue;
+            }
+            if self.get_opt_by_name(other_opt.prefix(), other_opt.name()).is_none() {
+                ret.push(OptionDiff {
+                    name: format!("{}{}", other_opt.prefix(), other_opt.name()),
+                    old: OptValue::null(),
+                    new: other_opt.value().clone(),
+                });
+            }
+        }
+        ret
+    }
+
+    /// Reset the value of option at `index` to default,
+    /// the value is cloned through the clone helper if it has one.
+    fn reset_one(&mut self, index: usize) -> bool {
+        match self.opts.get_mut(index) {
+            Some(opt) => {
+                match self.clone_helpers.get(opt.type_name()) {
+                    Some(clone_helper) => {
+                        let value = opt.default_value().clone_or(&Some(clone_helper.clone()));
+
+                        opt.replace_value(value);
+                    }
+                    None => {
+                        opt.reset_value();
+                    }
+                }
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// The value difference of an option, see [`DefaultSet::diff`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct OptionDiff {
+    pub name: String,
+
+    pub old: OptValue,
+
+    pub new: OptValue,
 }
 
 impl Default for DefaultSet {
@@ -176,6 +441,9 @@ impl Subscriber for DefaultSet {
 impl Set for DefaultSet {
     fn add_utils(&mut self, utils: Box<dyn Utils>) -> Result<bool> {
         if ! self.utils.contains_key(utils.type_name()) {
+            if let Some(clone_helper) = utils.clone_helper() {
+                self.set_clone_helper(utils.type_name(), clone_helper);
+            }
             self.utils.insert(utils.type_name().to_owned(), utils);
             Ok(true)
         }
@@ -192,6 +460,7 @@ impl Set for DefaultSet {
     fn rem_utils(&mut self, type_name: &str) -> Result<bool> {
         if self.utils.contains_key(type_name) {
             self.utils.remove(type_name);
+            self.clone_helpers.remove(type_name);
             Ok(true)
         }
         else {
@@ -221,8 +490,27 @@ impl Set for DefaultSet {
 
         match self.get_utils(ci.get_type_name()) {
             Some(util) => {
-                let opt = util.create(id, &ci)?;
-
+                let mut opt = util.create(id, &ci)?;
+
+                // an option with index, such as `directory=int@1`, is a typed positional
+                if ! ci.get_index().is_null() && ! (opt.is_style(Style::Pos) || opt.is_style(Style::Cmd) || opt.is_style(Style::Main)) {
+                    let mut pos = PosNonOpt::new(
+                        id,
+                        ci.get_name().to_owned(),
+                        ci.is_optional(),
+                        ci.get_index().clone(),
+                        ci.get_help_info().clone(),
+                    );
+
+                    pos.set_value_opt(opt);
+                    opt = Box::new(pos);
+                }
+                if ! opt.prefix().is_empty() {
+                    self.check_duplicate(opt.as_ref())?;
+                }
+                if let Some(env) = ci.get_env() {
+                    self.set_env(id, env);
+                }
                 self.opts.push(opt);
                 Ok(id)
             }
@@ -268,6 +556,24 @@ impl Set for DefaultSet {
         }
     }
 
+    fn get_opt_by_name(&self, prefix: &str, name: &str) -> Option<& dyn Opt> {
+        for opt in self.opts.iter() {
+            if (opt.match_prefix(prefix) && opt.match_name(name)) || opt.match_alias(prefix, name) {
+                return Some(opt.as_ref());
+            }
+        }
+        None
+    }
+
+    fn get_opt_mut_by_name(&mut self, prefix: &str, name: &str) -> Option<&mut dyn Opt> {
+        for opt in self.opts.iter_mut() {
+            if (opt.match_prefix(prefix) && opt.match_name(name)) || opt.match_alias(prefix, name) {
+                return Some(opt.as_mut());
+            }
+        }
+        None
+    }
+
     fn len(&self) -> usize {
         self.opts.len()
     }
@@ -347,6 +653,13 @@ impl Set for DefaultSet {
         self.set_prefix(current_prefixs);
     }
 
+    fn rem_prefix(&mut self, prefix: &str) -> bool {
+        let len = self.support_prefixs.len();
+
+        self.support_prefixs.retain(|v| v != prefix);
+        len != self.support_prefixs.len()
+    }
+
     fn check(&self) -> Result<bool> {
         for opt in &self.opts {
             opt.check()?;
@@ -354,9 +667,65 @@ impl Set for DefaultSet {
         Ok(true)
     }
 
+    fn set_allow_abbrev(&mut self, allow_abbrev: bool) {
+        self.allow_abbrev = allow_abbrev;
+    }
+
+    fn is_allow_abbrev(&self) -> bool {
+        self.allow_abbrev
+    }
+
+    fn set_enable_no_prefix(&mut self, enable_no_prefix: bool) {
+        self.enable_no_prefix = enable_no_prefix;
+    }
+
+    fn is_enable_no_prefix(&self) -> bool {
+        self.enable_no_prefix
+    }
+
+    fn set_record_raw(&mut self, record_raw: bool) {
+        self.record_raw = record_raw;
+    }
+
+    fn is_record_raw(&self) -> bool {
+        self.record_raw
+    }
+
+    fn raw_tokens(&self, id: Identifier) -> &[String] {
+        match self.raw_tokens.get(&id) {
+            Some(tokens) => tokens.as_slice(),
+            None => &[],
+        }
+    }
+
+    fn app_raw_tokens(&mut self, id: Identifier, tokens: &[String]) {
+        if self.record_raw {
+            self.raw_tokens.entry(id).or_insert(vec![]).extend_from_slice(tokens);
+        }
+    }
+
+    fn set_env(&mut self, id: Identifier, env: &str) {
+        self.envs.insert(id, env.to_owned());
+    }
+
+    fn get_env(&self, id: Identifier) -> Option<&str> {
+        self.envs.get(&id).map(|env| env.as_str())
+    }
+
     fn reset(&mut self) {
-        for opt in self.opts.iter_mut() {
-            opt.reset_value();
+        for index in 0 .. self.opts.len() {
+            self.reset_one(index);
+        }
+        self.raw_tokens.clear();
+    }
+
+    fn reset_value_of(&mut self, id: Identifier) -> bool {
+        if self.reset_one(id.get() as usize) {
+            self.raw_tokens.remove(&id);
+            true
+        }
+        else {
+            false
         }
     }
 }
@@ -375,6 +744,16 @@ impl IndexMut<Identifier> for DefaultSet {
     }
 }
 
+/// Return the prefix and name of `opt`, and all its alias.
+fn opt_names(opt: &dyn Opt) -> Vec<(String, String)> {
+    let mut names = vec![(opt.prefix().to_owned(), opt.name().to_owned())];
+
+    if let Some(alias) = opt.alias() {
+        names.extend(alias.iter().cloned());
+    }
+    names
+}
+
 #[derive(Debug)]
 pub struct Commit<'a> {
     ref_set: &'a mut dyn Set,
@@ -418,6 +797,10 @@ impl<'a> Commit<'a> {
         self.create_info.add_alias(prefix, name);
     }
 
+    pub fn add_alias_with_hint(&mut self, prefix: &str, name: &str, hint: &str) {
+        self.create_info.add_alias_with_hint(prefix, name, hint);
+    }
+
     pub fn rem_alias(&mut self, prefix: &str, name: &str) {
         self.create_info.rem_alias(prefix, name);
     }
@@ -438,6 +821,51 @@ impl<'a> Commit<'a> {
         self.create_info.set_help(help);
     }
 
+    /// Set the section of option in help, the option without section is listed under `Options`.
+    pub fn set_section(&mut self, section: &str) {
+        self.create_info.set_section(section);
+    }
+
+    /// Set the validator of option value, only the `int`, `uint` and `flt` option support it.
+    pub fn set_validator(&mut self, validator: Box<ValidatorFn>) {
+        self.create_info.set_validator(ValueValidator::new(validator));
+    }
+
+    /// Skip the value already exists, only the `array` option support it.
+    pub fn set_unique(&mut self, unique: bool) {
+        self.create_info.set_unique(unique);
+    }
+
+    /// Split the value by `delimiter`, the escaped delimiter such as `\,` is treated literally, only the `array` and `map` option support it.
+    pub fn set_delimiter(&mut self, delimiter: char) {
+        self.create_info.set_delimiter(delimiter);
+    }
+
+    /// Set the type of element, such as `uint`, only the `array` option support it.
+    pub fn set_element_type(&mut self, element_type: &str) {
+        self.create_info.set_element_type(element_type);
+    }
+
+    /// Accept the `NaN` and infinity value, only the `flt` option support it.
+    pub fn set_allow_non_finite(&mut self, allow_non_finite: bool) {
+        self.create_info.set_allow_non_finite(allow_non_finite);
+    }
+
+    /// Set the environment variable used as value if the option not set in command line.
+    pub fn set_env(&mut self, env: &str) {
+        self.create_info.set_env(env);
+    }
+
+    /// Read the value from stdin if the value is exactly `-`, only the `str` option support it.
+    pub fn set_stdin_on_dash(&mut self, stdin_on_dash: bool) {
+        self.create_info.set_stdin_on_dash(stdin_on_dash);
+    }
+
+    /// Set the reader used instead of stdin when the value is `-`, only the `str` option support it.
+    pub fn set_reader(&mut self, reader: Box<dyn Fn() -> std::io::Result<String>>) {
+        self.create_info.set_reader(ValueReader::new(reader));
+    }
+
     pub fn commit(&mut self) -> Result<Identifier> {
         self.ref_set.add_opt_ci(&self.create_info)
     }
@@ -683,4 +1111,426 @@ mod tests {
         assert!(set.filter("name").unwrap().find().is_some());
         assert!(set.filter("iname").unwrap().find().is_some());
     }
+
+    #[test]
+    fn make_sure_initialize_utils_work() {
+        let mut set = DefaultSet::new();
+
+        set.initialize_prefixs();
+        assert!(matches!(set.add_opt("--count=int").unwrap().commit(), Err(Error::InvalidOptionType(name)) if name == "int"));
+        assert!(set.initialize_utils().unwrap());
+        for opt in ["--count=int", "--ratio=flt", "--name=str", "--debug=bool", "--tags=array"].iter() {
+            assert!(set.add_opt(opt).unwrap().commit().is_ok());
+        }
+        assert_eq!(set.len(), 5);
+        assert!(matches!(set.initialize_utils(), Err(Error::DuplicateOptionType(name)) if name == "int"));
+    }
+
+    #[test]
+    fn make_sure_get_opt_by_name_work() {
+        let mut set = DefaultSet::new();
+
+        set.initialize_utils().unwrap();
+        set.initialize_prefixs();
+
+        if let Ok(mut commit) = set.add_opt("-d=bool") {
+            commit.add_alias("--", "debug");
+            assert!(commit.commit().is_ok());
+        }
+        if let Ok(mut commit) = set.add_opt("--count=int") {
+            assert!(commit.commit().is_ok());
+        }
+
+        assert_eq!(set.get_opt_by_name("-", "d").unwrap().name(), "d");
+        assert_eq!(set.get_opt_by_name("--", "debug").unwrap().name(), "d");
+        assert_eq!(set.get_opt_by_name("--", "count").unwrap().name(), "count");
+        assert!(set.get_opt_by_name("-", "debug").is_none());
+        assert!(set.get_opt_by_name("--", "d").is_none());
+
+        if let Some(opt) = set.get_opt_mut_by_name("--", "debug") {
+            opt.set_value(OptValue::from_bool(true));
+        }
+        assert_eq!(set.get_opt_by_name("-", "d").unwrap().value().as_bool(), Some(&true));
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_diff_work() {
+        use crate::parser::{Parser, ForwardParser};
+        use crate::arg::{ArgIterator, IndexIterator};
+        use crate::id::DefaultIdGen;
+
+        let mut parsers = vec![];
+
+        for args in [
+            ["--debug", "--count", "1", "--name", "foo"],
+            ["--debug", "--count", "2", "--array", "foo"],
+        ].iter() {
+            let mut set = DefaultSet::new();
+            let mut parser = ForwardParser::new(DefaultIdGen::default());
+
+            set.initialize_utils().unwrap();
+            set.initialize_prefixs();
+
+            set.add_opt("--debug=bool").unwrap().commit().unwrap();
+            set.add_opt("--count=int").unwrap().commit().unwrap();
+            set.add_opt("--name=str").unwrap().commit().unwrap();
+            set.add_opt("--array=array").unwrap().commit().unwrap();
+            set.add_opt("--other=str").unwrap().commit().unwrap();
+
+            let mut ai = ArgIterator::new();
+
+            ai.set_args(&mut args.iter().map(|&v|String::from(v)));
+            set.subscribe_from(&mut parser);
+            parser.publish_to(set);
+            parser.parse(&mut ai).unwrap();
+            parsers.push(parser);
+        }
+
+        let set0 = parsers[0].set().as_ref().unwrap();
+        let set1 = parsers[1].set().as_ref().unwrap();
+        let diff = set0.diff(set1);
+
+        assert_eq!(diff, vec![
+            OptionDiff { name: String::from("--count"), old: OptValue::from_int(1), new: OptValue::from_int(2) },
+            OptionDiff { name: String::from("--name"), old: OptValue::from_str("foo"), new: OptValue::null() },
+            OptionDiff { name: String::from("--array"), old: OptValue::null(), new: OptValue::from_vec(vec![String::from("foo")]) },
+        ]);
+        assert!(set0.diff(set0).is_empty());
+
+        let mut set2 = set0.clone_config().unwrap();
+        let mut set3 = set0.clone_config().unwrap();
+        let id = set3.add_opt("--extra=str").unwrap().commit().unwrap();
+
+        set3.get_opt_mut(id).unwrap().set_value(OptValue::from_str("bar"));
+        for set in [&mut set2, &mut set3] {
+            set.get_opt_mut_by_name("--", "other").unwrap().set_value(OptValue::from_any(Box::new(42i64)));
+        }
+        assert_eq!(set2.diff(&set3), vec![
+            OptionDiff { name: String::from("--extra"), old: OptValue::null(), new: OptValue::from_str("bar") },
+        ]);
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_add_opt_cfg_work() {
+        use crate::parser::{Parser, ForwardParser};
+        use crate::arg::{ArgIterator, IndexIterator};
+        use crate::id::DefaultIdGen;
+
+        let mut ai = ArgIterator::new();
+        let mut parser = ForwardParser::new(DefaultIdGen::default());
+        let mut set = DefaultSet::new();
+        let mut ci = CreateInfo::default();
+
+        set.initialize_utils().unwrap();
+        set.initialize_prefixs();
+
+        ci.set_type_name("int");
+        ci.set_name("count");
+        ci.set_prefix("--");
+        ci.add_alias("-", "c");
+        ci.add_alias("/", "cnt");
+        ci.set_deafult_value(OptValue::from_int(42));
+
+        let id = set.add_opt_cfg(ci).unwrap().commit().unwrap();
+
+        assert!(set.add_opt_cfg(CreateInfo::default()).unwrap().commit().is_err());
+        assert_eq!(set.get_opt(id).unwrap().value().as_int(), Some(&42));
+        assert!(set.get_opt(id).unwrap().match_alias("-", "c"));
+        assert!(set.get_opt(id).unwrap().match_alias("/", "cnt"));
+
+        set.subscribe_from(&mut parser);
+        parser.publish_to(set);
+        for (args, value) in [
+            (vec!["foo"], 42),
+            (vec!["-c", "1"], 1),
+            (vec!["/cnt=2"], 2),
+            (vec!["--count", "3"], 3),
+        ].iter() {
+            parser.reset_values();
+            ai.reset();
+            ai.set_args(&mut args.iter().map(|&v|String::from(v)));
+            parser.parse(&mut ai).unwrap();
+            assert_eq!(parser.get_opt(id).unwrap().value().as_int(), Some(value));
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_merge_work() {
+        use crate::parser::{Parser, ForwardParser};
+        use crate::arg::{ArgIterator, IndexIterator};
+        use crate::id::DefaultIdGen;
+
+        let mut set = DefaultSet::new();
+        let mut other = DefaultSet::new();
+
+        set.add_utils(Box::new(BoolUtils::new())).unwrap();
+        set.set_prefix(vec![String::from("-")]);
+        other.initialize_utils().unwrap();
+        other.initialize_prefixs();
+
+        let f = set.add_opt("-f=bool").unwrap().commit().unwrap();
+
+        other.add_opt("-c=int").unwrap().commit().unwrap();
+        set.merge(other).unwrap();
+
+        let c = set.get_opt_by_name("-", "c").unwrap().id();
+
+        assert_eq!(set.len(), 2);
+        assert_eq!(c, IIdentifier::new(1));
+        assert_eq!(set.iter().map(|v| v.name().to_owned()).collect::<Vec<String>>(), vec!["f", "c"]);
+        assert!(set.get_utils("int").is_some());
+        assert!(set.get_prefix().contains(&String::from("--")));
+
+        let mut conflict = DefaultSet::new();
+
+        conflict.initialize_utils().unwrap();
+        conflict.initialize_prefixs();
+        if let Ok(mut commit) = conflict.add_opt("--foo=str") {
+            commit.add_alias("-", "c");
+            commit.commit().unwrap();
+        }
+        assert!(matches!(set.merge(conflict), Err(Error::DuplicateOption(name)) if name == "-c"));
+        assert_eq!(set.len(), 2);
+
+        let mut ai = ArgIterator::new();
+        let mut parser = ForwardParser::new(DefaultIdGen::default());
+
+        ai.set_args(&mut ["-f", "-c", "42"].iter().map(|&v|String::from(v)));
+        set.subscribe_from(&mut parser);
+        parser.publish_to(set);
+        parser.parse(&mut ai).unwrap();
+
+        assert_eq!(parser.get_opt(f).unwrap().value().as_bool(), Some(&true));
+        assert_eq!(parser.get_opt(c).unwrap().value().as_int(), Some(&42));
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_clone_config_work() {
+        use crate::parser::{Parser, ForwardParser};
+        use crate::arg::{ArgIterator, IndexIterator};
+        use crate::id::DefaultIdGen;
+
+        let mut set = DefaultSet::new();
+
+        set.initialize_utils().unwrap();
+        set.initialize_prefixs();
+
+        let mut commit = set.add_opt("-c=int").unwrap();
+
+        commit.set_deafult_value(OptValue::from_int(1));
+
+        let count = commit.commit().unwrap();
+        let name = set.add_opt("--name=str!").unwrap().commit().unwrap();
+        let mut commit = set.add_opt("-d=bool").unwrap();
+
+        commit.add_alias("--", "debug");
+
+        let debug = commit.commit().unwrap();
+
+        set.get_opt_mut(count).unwrap().set_value(OptValue::from_int(8));
+
+        let clone = set.clone_config().unwrap();
+
+        assert_eq!(clone.len(), 3);
+        assert_eq!(clone.get_prefix(), set.get_prefix());
+        assert_eq!(clone[count].value().as_int(), Some(&1));
+        assert!(! clone[name].optional());
+        assert!(clone[debug].match_alias("--", "debug"));
+
+        let mut ai = ArgIterator::new();
+        let mut parser = ForwardParser::new(DefaultIdGen::default());
+
+        ai.set_args(&mut ["-c", "42", "--name", "foo", "--debug"].iter().map(|&v|String::from(v)));
+        clone.subscribe_from(&mut parser);
+        parser.publish_to(clone);
+        parser.parse(&mut ai).unwrap();
+
+        assert_eq!(parser.get_opt(count).unwrap().value().as_int(), Some(&42));
+        assert_eq!(parser.get_opt(name).unwrap().value().as_str(), Some(&String::from("foo")));
+        assert_eq!(parser.get_opt(debug).unwrap().value().as_bool(), Some(&true));
+        assert_eq!(set[count].value().as_int(), Some(&8));
+        assert!(! set[name].has_value());
+        assert_ne!(set[debug].value().as_bool(), Some(&true));
+
+        let mut set = DefaultSet::new();
+        let ci = CreateInfo::parse("--count=int", &vec![String::from("--")]).unwrap();
+
+        set.add_opt_raw(int::IntUtils::new().create(IIdentifier::new(0), &ci).unwrap()).unwrap();
+        assert!(matches!(set.clone_config(), Err(Error::InvalidOptionType(name)) if name == "int"));
+    }
+
+    #[test]
+    fn make_sure_iter_mut_work() {
+        let mut set = DefaultSet::new();
+
+        set.initialize_utils().unwrap();
+        set.initialize_prefixs();
+        set.add_opt("-c=int!").unwrap().commit().unwrap();
+        set.add_opt("--name=str!").unwrap().commit().unwrap();
+        set.add_opt("--debug=bool").unwrap().commit().unwrap();
+
+        assert!(set.check().is_err());
+        for opt in set.iter_mut() {
+            opt.set_optional(true);
+        }
+        assert!(set.iter().all(|opt| opt.optional()));
+        assert!(set.check().is_ok());
+    }
+
+    #[test]
+    #[cfg(not(feature="async"))]
+    fn make_sure_rem_prefix_work() {
+        use crate::parser::{Parser, ForwardParser};
+        use crate::arg::{ArgIterator, IndexIterator};
+        use crate::id::DefaultIdGen;
+
+        for remove in [false, true].iter() {
+            let mut set = DefaultSet::new();
+
+            set.initialize_utils().unwrap();
+            set.initialize_prefixs();
+            assert!(! set.has_prefix("+"));
+            set.app_prefix(String::from("+"));
+            assert!(set.has_prefix("+"));
+
+            let x = set.add_opt("+x=bool").unwrap().commit().unwrap();
+
+            if *remove {
+                assert!(set.rem_prefix("+"));
+                assert!(! set.has_prefix("+"));
+                assert!(! set.rem_prefix("+"));
+                assert!(set.has_prefix("--"));
+            }
+
+            let mut ai = ArgIterator::new();
+            let mut parser = ForwardParser::new(DefaultIdGen::default());
+
+            ai.set_args(&mut ["+x"].iter().map(|&v|String::from(v)));
+            set.subscribe_from(&mut parser);
+            parser.publish_to(set);
+            parser.parse(&mut ai).unwrap();
+
+            // the option using removed prefix can not be matched
+            assert_eq!(parser.get_opt(x).unwrap().value().as_bool(), Some(&! remove));
+            assert_eq!(parser.noa().is_empty(), ! remove);
+        }
+    }
+
+    #[test]
+    fn make_sure_duplicate_option_work() {
+        let mut set = DefaultSet::new();
+
+        set.initialize_utils().unwrap();
+        set.initialize_prefixs();
+
+        let c = set.add_opt("-c=int").unwrap().commit().unwrap();
+
+        assert!(matches!(set.add_opt("-c=bool").unwrap().commit(), Err(Error::DuplicateOption(name)) if name == "-c"));
+        assert!(set.add_opt("--c=bool").unwrap().commit().is_ok());
+
+        if let Ok(mut commit) = set.add_opt("--count=int") {
+            commit.add_alias("-", "n");
+            commit.commit().unwrap();
+        }
+        // conflict between a name and an existing alias
+        assert!(matches!(set.add_opt("-n=str").unwrap().commit(), Err(Error::DuplicateOption(name)) if name == "-n"));
+        if let Ok(mut commit) = set.add_opt("--number=int") {
+            commit.add_alias("-", "c");
+            assert!(matches!(commit.commit(), Err(Error::DuplicateOption(name)) if name == "-c"));
+        }
+        // the non-option has no prefix is not checked
+        set.add_opt("file=pos@1").unwrap().commit().unwrap();
+        set.add_opt("file=pos@2").unwrap().commit().unwrap();
+        assert_eq!(set.len(), 5);
+        assert!(set.find_conflicts().is_empty());
+
+        set[c].add_alias("-", "n");
+        set[c].add_alias("--", "c");
+        assert_eq!(set.find_conflicts(), vec![String::from("--c"), String::from("-n")]);
+    }
+
+    #[test]
+    fn make_sure_reset_value_of_work() {
+        let mut set = DefaultSet::new();
+
+        set.initialize_utils().unwrap();
+        set.initialize_prefixs();
+
+        let mut commit = set.add_opt("--count=int").unwrap();
+
+        commit.set_deafult_value(OptValue::from_int(1));
+        let count = commit.commit().unwrap();
+        let mut commit = set.add_opt("--name=str").unwrap();
+
+        commit.set_deafult_value(OptValue::from_str("foo"));
+        let name = commit.commit().unwrap();
+
+        set[count].set_value(OptValue::from_int(8));
+        set[name].set_value(OptValue::from_str("bar"));
+        assert!(set.reset_value_of(count));
+        assert_eq!(set[count].value().as_int(), Some(&1));
+        assert_eq!(set[name].value().as_str(), Some(&String::from("bar")));
+        assert!(! set.reset_value_of(IIdentifier::new(42)));
+
+        let mut commit = set.add_opt("--include=array").unwrap();
+
+        commit.set_deafult_value(OptValue::from_vec(vec![String::from("a")]));
+        let include = commit.commit().unwrap();
+
+        set.set_clone_helper("array", CloneHelper::new(|v| Box::new(v.downcast_ref::<Vec<String>>().unwrap().clone())));
+        set[include].set_value(OptValue::from_vec(vec![String::from("b")]));
+        assert!(set.reset_value_of(include));
+        assert_eq!(set[include].value().as_vec(), Some(&vec![String::from("a")]));
+    }
+
+    #[test]
+    fn make_sure_remove_matching_work() {
+        let mut set = DefaultSet::new();
+
+        set.initialize_utils().unwrap();
+        set.initialize_prefixs();
+        set.add_opt("--include=array").unwrap().commit().unwrap();
+        set.add_opt("-v=bool").unwrap().commit().unwrap();
+        set.add_opt("--define=array").unwrap().commit().unwrap();
+
+        let mut fi = FilterInfo::new();
+
+        fi.set_type_name("array");
+        assert_eq!(set.remove_matching(&fi), 2);
+        assert_eq!(set.len(), 1);
+        assert!(set.find(&fi).is_none());
+
+        let verbose = set.filter("v").unwrap().find().unwrap().id();
+
+        assert_eq!(set[verbose].name(), "v");
+        assert_eq!(set[verbose].type_name(), "bool");
+        assert_eq!(set.remove_matching(&fi), 0);
+    }
+
+    #[test]
+    fn make_sure_opt_help_work() {
+        let mut set = DefaultSet::new();
+
+        set.initialize_utils().unwrap();
+        set.initialize_prefixs();
+
+        let mut commit = set.add_opt("--count=int").unwrap();
+
+        commit.set_help("the number of items");
+        let count = commit.commit().unwrap();
+        let name = set.add_opt("--name=str").unwrap().commit().unwrap();
+
+        assert_eq!(set[count].help(), "the number of items");
+        assert_eq!(set[name].help(), "");
+        set[name].set_help("the name of item");
+        assert_eq!(set[name].help(), "the name of item");
+
+        let cloned = set.clone_config().unwrap();
+
+        assert_eq!(cloned[count].help(), "the number of items");
+    }
 }
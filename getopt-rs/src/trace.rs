@@ -0,0 +1,250 @@
+use std::fmt::Display;
+use std::collections::HashMap;
+
+use crate::set::Set;
+use crate::opt::{Opt, OptValue, Style};
+use crate::id::Identifier;
+use crate::proc::Proc;
+use crate::parser::matched_argument;
+
+/// The parse result of an option.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OptionTrace {
+    pub id: Identifier,
+
+    /// The option name with prefix, such as `--debug`.
+    pub name: String,
+
+    /// How many times the option matched.
+    pub count: u64,
+
+    /// The generate style matched the option, in matched order.
+    pub styles: Vec<String>,
+
+    /// The final value of option.
+    pub value: OptValue,
+}
+
+impl OptionTrace {
+    pub fn is_matched(&self) -> bool {
+        self.count > 0
+    }
+}
+
+/// ParseTrace hold the parse result of every option, it can display as a table.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParseTrace {
+    options: Vec<OptionTrace>,
+}
+
+impl ParseTrace {
+    pub fn new() -> Self {
+        Self {
+            options: vec![],
+        }
+    }
+
+    /// Create the trace from options of `set` and the matched styles record by parser.
+    pub fn from_set(set: &dyn Set, matched_styles: &HashMap<Identifier, Vec<String>>) -> Self {
+        let mut options = vec![];
+
+        for opt in set.iter() {
+            if opt.is_style(Style::Boolean) || opt.is_style(Style::Argument) || opt.is_style(Style::Multiple) {
+                let styles = matched_styles.get(&opt.id()).cloned().unwrap_or_default();
+
+                options.push(OptionTrace {
+                    id: opt.id(),
+                    name: format!("{}{}", opt.prefix(), opt.name()),
+                    count: styles.len() as u64,
+                    styles,
+                    value: opt.value().clone(),
+                });
+            }
+        }
+        Self {
+            options,
+        }
+    }
+
+    pub fn options(&self) -> &Vec<OptionTrace> {
+        &self.options
+    }
+
+    /// Get the trace of option which has the `name` with prefix, such as `-c`.
+    pub fn get(&self, name: &str) -> Option<&OptionTrace> {
+        self.options.iter().find(|v| v.name == name)
+    }
+}
+
+impl Display for ParseTrace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let width = self.options.iter().map(|v| v.name.len()).max().unwrap_or(0).max(4);
+
+        writeln!(f, "{:<width$}  {:<7}  {:<5}  {:<30}  {}", "name", "matched", "count", "style", "value", width = width)?;
+        for opt in self.options.iter() {
+            writeln!(
+                f,
+                "{:<width$}  {:<7}  {:<5}  {:<30}  {:?}",
+                opt.name,
+                opt.is_matched(),
+                opt.count,
+                opt.styles.join(","),
+                opt.value,
+                width = width
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// The timing metrics of a [`Proc`](crate::proc::Proc) published by parser.
+#[cfg(feature="profile")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcProfile {
+    pub id: Identifier,
+
+    /// How many options checked by the proc.
+    pub checked: usize,
+
+    /// The time spent in processing the proc.
+    pub elapsed: std::time::Duration,
+}
+
+/// ProfileReport hold the timing metrics of every [`Proc`](crate::proc::Proc) published by parser.
+#[cfg(feature="profile")]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProfileReport {
+    procs: Vec<ProcProfile>,
+}
+
+#[cfg(feature="profile")]
+impl ProfileReport {
+    pub fn new() -> Self {
+        Self {
+            procs: vec![],
+        }
+    }
+
+    pub fn procs(&self) -> &Vec<ProcProfile> {
+        &self.procs
+    }
+
+    /// Get the total number of options checked by all the procs.
+    pub fn checked(&self) -> usize {
+        self.procs.iter().map(|v| v.checked).sum()
+    }
+
+    /// Get the total time spent in processing all the procs.
+    pub fn elapsed(&self) -> std::time::Duration {
+        self.procs.iter().map(|v| v.elapsed).sum()
+    }
+
+    pub fn record(&mut self, id: Identifier, checked: usize, elapsed: std::time::Duration) {
+        self.procs.push(ProcProfile { id, checked, elapsed, });
+    }
+}
+
+/// Record the `style` of option matched, only the option style is recorded.
+/// Return true if the `style` recorded.
+pub(crate) fn record_match(matched_styles: &mut HashMap<Identifier, Vec<String>>, style: &Option<String>, opt: &dyn Opt) -> bool {
+    if let Some(style) = style {
+        if opt.is_style(Style::Boolean) || opt.is_style(Style::Argument) || opt.is_style(Style::Multiple) {
+            matched_styles.entry(opt.id()).or_insert(vec![]).push(style.clone());
+            return true;
+        }
+    }
+    false
+}
+
+/// Record the raw `tokens` of options matched,
+/// the argument is recorded only if the option consumed it.
+pub(crate) fn record_raw(set: &mut dyn Set, ids: &[Identifier], tokens: &[String], need_argument: bool) {
+    let len = if need_argument { tokens.len() } else { tokens.len().min(1) };
+
+    for id in ids {
+        set.app_raw_tokens(*id, &tokens[.. len]);
+    }
+}
+
+/// MatchRecorder record the options matched by the [`Proc`] published to parser.
+#[derive(Debug, Default)]
+pub(crate) struct MatchRecorder {
+    /// The generate style of current proc.
+    pub gen_style: Option<String>,
+
+    /// The arguments of current proc.
+    pub gen_tokens: Vec<String>,
+
+    pub matched_styles: HashMap<Identifier, Vec<String>>,
+
+    pub last_matched: Option<Identifier>,
+
+    pub raw_values: HashMap<Identifier, Vec<String>>,
+
+    /// The option which is the last argument but need an argument.
+    pub missing_argument: Option<String>,
+
+    #[cfg(feature="profile")]
+    pub profile: ProfileReport,
+
+    matched_ids: Vec<Identifier>,
+
+    #[cfg(feature="profile")]
+    start: Option<std::time::Instant>,
+
+    #[cfg(feature="profile")]
+    checked: usize,
+}
+
+impl MatchRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start recording the options matched by a new proc.
+    pub fn begin(&mut self) {
+        self.matched_ids.clear();
+        #[cfg(feature="profile")]
+        {
+            self.start = Some(std::time::Instant::now());
+            self.checked = 0;
+        }
+    }
+
+    /// Record the `opt` is checked by `proc`, and it is matched if `matched` is true.
+    pub fn record(&mut self, proc: &dyn Proc, opt: &dyn Opt, matched: bool) {
+        #[cfg(feature="profile")]
+        { self.checked += 1; }
+
+        if matched {
+            if record_match(&mut self.matched_styles, &self.gen_style, opt) {
+                self.matched_ids.push(opt.id());
+            }
+            self.raw_values.entry(opt.id()).or_insert(vec![]).push(matched_argument(proc, opt));
+        }
+    }
+
+    /// Finish recording the options matched by `proc`, the raw tokens are recorded into `set`.
+    pub fn finish(&mut self, set: &mut dyn Set, proc: &dyn Proc) {
+        record_raw(set, &self.matched_ids, &self.gen_tokens, proc.is_need_argument());
+        if let Some(id) = self.matched_ids.last() {
+            self.last_matched = Some(*id);
+        }
+
+        #[cfg(feature="profile")]
+        self.profile.record(proc.id(), self.checked, self.start.map(|v| v.elapsed()).unwrap_or_default());
+    }
+
+    /// Return the options matched by current proc.
+    pub fn matched(&self) -> &[Identifier] {
+        &self.matched_ids
+    }
+
+    /// Clear the matches of previous parse.
+    pub fn clear(&mut self) {
+        self.matched_styles.clear();
+        self.last_matched = None;
+        self.raw_values.clear();
+        self.missing_argument = None;
+    }
+}
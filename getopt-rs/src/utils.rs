@@ -2,10 +2,16 @@
 use std::fmt::Debug;
 
 use crate::callback::CallbackType;
-use crate::opt::{Opt, HelpInfo, NonOptIndex, OptValue};
+use crate::opt::{Opt, HelpInfo, NonOptIndex, OptValue, ValueValidator, ValueReader, CloneHelper};
+use crate::opt::array::ArrayOpt;
+use crate::opt::map::MapOpt;
+use crate::opt::str::StrOpt;
+use crate::opt::flt::FltOpt;
+use crate::nonopt::pos::PosNonOpt;
 use crate::error::{Error, Result};
 use crate::proc::Info;
 use crate::id::Identifier;
+use crate::pattern::{self, ParseResult};
 
 pub trait Utils: Debug {
     fn type_name(&self) -> &str;
@@ -15,6 +21,11 @@ pub trait Utils: Debug {
     fn create(&self, id: Identifier, ci: &CreateInfo) -> Result<Box<dyn Opt>>;
 
     fn gen_info(&self, opt: &dyn Opt) -> Box<dyn Info>;
+
+    /// Return the [`CloneHelper`] if the option type hold an `OptValue::Any` value.
+    fn clone_helper(&self) -> Option<CloneHelper> {
+        None
+    }
 }
 
 #[derive(Debug, Default)]
@@ -38,6 +49,22 @@ pub struct CreateInfo {
     opt_callback_type: CallbackType,
 
     opt_help: HelpInfo,
+
+    opt_validator: Option<ValueValidator>,
+
+    opt_unique: bool,
+
+    opt_delimiter: Option<char>,
+
+    opt_element_type: Option<String>,
+
+    opt_allow_non_finite: bool,
+
+    opt_env: Option<String>,
+
+    opt_stdin_on_dash: bool,
+
+    opt_reader: Option<ValueReader>,
 }
 
 impl CreateInfo {
@@ -63,33 +90,99 @@ impl CreateInfo {
             opt_value: deafult_value,
             opt_callback_type,
             opt_help,
+            opt_validator: None,
+            opt_unique: false,
+            opt_delimiter: None,
+            opt_element_type: None,
+            opt_allow_non_finite: false,
+            opt_env: None,
+            opt_stdin_on_dash: false,
+            opt_reader: None,
         }
     }
 
+    /// Create the information from an exists option, the value of option is not included.
+    pub fn from_opt(opt: &dyn Opt) -> Self {
+        let mut ret = Self::new(
+            opt.type_name(),
+            opt.name(),
+            opt.prefix(),
+            opt.index().clone(),
+            opt.is_deactivate_style(),
+            opt.optional(),
+            opt.default_value().clone(),
+            opt.callback_type(),
+            opt.help_info(),
+        );
+
+        if let Some(alias) = opt.alias() {
+            for (prefix, name) in alias.iter() {
+                ret.add_alias(prefix, name);
+            }
+        }
+        if let Some(array) = opt.as_any().downcast_ref::<ArrayOpt>() {
+            ret.set_unique(array.is_unique());
+            if let Some(delimiter) = array.get_delimiter() {
+                ret.set_delimiter(delimiter);
+            }
+            if let Some(element_type) = array.get_element_type() {
+                ret.set_element_type(element_type);
+            }
+        }
+        if let Some(string) = opt.as_any().downcast_ref::<StrOpt>() {
+            ret.set_stdin_on_dash(string.is_stdin_on_dash());
+            if let Some(reader) = string.get_reader() {
+                ret.set_reader(reader.clone());
+            }
+        }
+        if let Some(flt) = opt.as_any().downcast_ref::<FltOpt>() {
+            ret.set_allow_non_finite(flt.is_allow_non_finite());
+        }
+        if let Some(delimiter) = opt.as_any().downcast_ref::<MapOpt>().and_then(|map| map.get_delimiter()) {
+            ret.set_delimiter(delimiter);
+        }
+        if let Some(value_type) = opt.as_any().downcast_ref::<PosNonOpt>().and_then(|pos| pos.value_type()) {
+            ret.set_type_name(value_type);
+        }
+        ret
+    }
+
     pub fn parse(s: &str, prefixs: &Vec<String>) -> Result<Self> {
         let pr = parse_opt_string(s, prefixs)?;
         let type_name = pr.type_name.ok_or(Error::NullOptionType)?;
         let opt_name = pr.opt_name.ok_or(Error::NullOptionName)?;
+        let opt_value = match pr.default_value.as_ref() {
+            Some(default_value) => parse_default_value(&type_name, default_value)?,
+            None => OptValue::default(),
+        };
         Ok(Self {
             type_name,
             opt_name,
             opt_prefix: pr.opt_prefix.unwrap_or(String::default()),
-            opt_index: pr.opt_index,
+            opt_index: pr.opt_index.into(),
             deactivate: pr.deactivate.unwrap_or(false),
             optional: pr.optional.unwrap_or(true),
             opt_alias: vec![],
-            opt_value: OptValue::default(),
+            opt_value,
             opt_callback_type: CallbackType::default(),
             opt_help: HelpInfo::default(),
+            opt_validator: None,
+            opt_unique: false,
+            opt_delimiter: None,
+            opt_element_type: pr.element_type,
+            opt_allow_non_finite: false,
+            opt_env: None,
+            opt_stdin_on_dash: false,
+            opt_reader: None,
         })
     }
 
     /// Check if the create information is correct
     pub fn check(&self) -> Result<bool> {
-        if self.get_type_name() != "" {
+        if self.get_type_name() == "" {
             Err(Error::NullOptionType)
         }
-        else if self.get_name() != "" {
+        else if self.get_name() == "" {
             Err(Error::NullOptionName)
         }
         else {
@@ -144,6 +237,38 @@ impl CreateInfo {
         &self.opt_help
     }
 
+    pub fn get_validator(&self) -> Option<&ValueValidator> {
+        self.opt_validator.as_ref()
+    }
+
+    pub fn is_unique(&self) -> bool {
+        self.opt_unique
+    }
+
+    pub fn get_delimiter(&self) -> Option<char> {
+        self.opt_delimiter
+    }
+
+    pub fn get_element_type(&self) -> Option<&str> {
+        self.opt_element_type.as_deref()
+    }
+
+    pub fn is_allow_non_finite(&self) -> bool {
+        self.opt_allow_non_finite
+    }
+
+    pub fn get_env(&self) -> Option<&str> {
+        self.opt_env.as_deref()
+    }
+
+    pub fn is_stdin_on_dash(&self) -> bool {
+        self.opt_stdin_on_dash
+    }
+
+    pub fn get_reader(&self) -> Option<&ValueReader> {
+        self.opt_reader.as_ref()
+    }
+
     pub fn set_deactivate_style(&mut self, deactivate: bool) {
         self.deactivate = deactivate;
     }
@@ -184,16 +309,59 @@ impl CreateInfo {
         self.opt_help.help = help.to_owned()
     }
 
+    pub fn set_section(&mut self, section: &str) {
+        self.opt_help.section = section.to_owned()
+    }
+
+    pub fn set_validator(&mut self, validator: ValueValidator) {
+        self.opt_validator = Some(validator);
+    }
+
+    pub fn set_unique(&mut self, unique: bool) {
+        self.opt_unique = unique;
+    }
+
+    pub fn set_delimiter(&mut self, delimiter: char) {
+        self.opt_delimiter = Some(delimiter);
+    }
+
+    pub fn set_element_type(&mut self, element_type: &str) {
+        self.opt_element_type = Some(element_type.to_owned());
+    }
+
+    pub fn set_allow_non_finite(&mut self, allow_non_finite: bool) {
+        self.opt_allow_non_finite = allow_non_finite;
+    }
+
+    pub fn set_env(&mut self, env: &str) {
+        self.opt_env = Some(env.to_owned());
+    }
+
+    pub fn set_stdin_on_dash(&mut self, stdin_on_dash: bool) {
+        self.opt_stdin_on_dash = stdin_on_dash;
+    }
+
+    pub fn set_reader(&mut self, reader: ValueReader) {
+        self.opt_reader = Some(reader);
+    }
+
     pub fn add_alias(&mut self, prefix: &str, name: &str) {
         self.opt_alias.push((prefix.to_owned(), name.to_owned()));
     }
 
+    /// Add the alias with the `hint` displayed in help, such as `--debug[=LEVEL]`.
+    pub fn add_alias_with_hint(&mut self, prefix: &str, name: &str, hint: &str) {
+        self.add_alias(prefix, name);
+        self.opt_help.add_alias_hint(&format!("{}{}", prefix, name), hint);
+    }
+
     pub fn rem_alias(&mut self, prefix: &str, name: &str) {
         for index in 0 .. self.opt_alias.len() {
             let alias = &self.opt_alias[index];
 
             if alias.0 == prefix && alias.1 == name {
                 self.opt_alias.remove(index);
+                self.opt_help.rem_alias_hint(&format!("{}{}", prefix, name));
                 break;
             }
         }
@@ -201,6 +369,7 @@ impl CreateInfo {
 
     pub fn clr_alias(&mut self) {
         self.opt_alias.clear();
+        self.opt_help.alias_hints.clear();
     }
 }
 
@@ -239,7 +408,7 @@ impl FilterInfo {
             type_name: pr.type_name,
             opt_name: pr.opt_name,
             opt_prefix: pr.opt_prefix,
-            opt_index: pr.opt_index,
+            opt_index: pr.opt_index.into(),
         })
     }
 
@@ -382,161 +551,34 @@ impl FilterInfo {
     }
 }
 
-#[derive(Debug)]
-struct ParseResult {
-    type_name: Option<String>,
-
-    opt_name: Option<String>,
-
-    opt_prefix: Option<String>,
-
-    deactivate: Option<bool>,
-
-    optional: Option<bool>,
-
-    opt_index: NonOptIndex,
+/// Parse the `default` of option string into the value of type `type_name`,
+/// the items of `array` are separated by `,`.
+fn parse_default_value(type_name: &str, default: &str) -> Result<OptValue> {
+    match type_name {
+        "int" => OptValue::parse_int(default),
+        "uint" => OptValue::parse_uint(default),
+        "flt" => OptValue::parse_flt(default),
+        "str" => Ok(OptValue::from_str(default)),
+        "bool" => match default {
+            "true" => Ok(OptValue::from_bool(true)),
+            "false" => Ok(OptValue::from_bool(false)),
+            _ => Err(Error::InvaldOptionValue(default.to_owned(), String::from("expect true or false"))),
+        },
+        "array" => Ok(OptValue::from_vec(default.split(',').map(|v| v.to_owned()).collect::<Vec<String>>())),
+        _ => Err(Error::InvaldOptionValue(default.to_owned(), format!("type `{}` not support default value", type_name))),
+    }
 }
 
-/// Parse input string `<prefix>|<name>=<type>[!][/]@<index>`,
-/// such as `-|o=a!`, means the force required option `o`, with a prefix "-", 
-/// and option type is `a`.
-/// `!` means the option is optional or not.
-/// `/` means the option is deactivate style or not.
+/// Parse the option string, see [`pattern::parse_opt_string`].
 fn parse_opt_string(s: &str, prefixs: &Vec<String>) -> Result<ParseResult> {
-    const SPLIT: &str = "=";
-    const DEACTIVATE: &str = "/";
-    const NO_OPTIONAL: &str = "!";
-    const INDEX: &str = "@";
-
-    if s.is_empty(){
-        return Err(Error::InvalidOptionStr(s.to_owned()));
-    }
-
-    let mut splited_index = 0;
-    let mut deactivate = None;
-    let mut optional = None;
-    let mut opt_index = NonOptIndex::Null;
-    let opt_name;
-    let mut type_name = None;
-    let right_info;
-    let left_info;
-    let mut opt_prefix  = None;
-    let mut prefix_index = 0;
-    let without_prefix;
-
-    for prefix in prefixs.iter() {
-        if s.starts_with(prefix) {
-            opt_prefix = Some(prefix.clone());
-            prefix_index = prefix.len();
-            break;
-        }
-    }
-
-    if prefix_index == s.len() {
-        return Err(Error::InvalidOptionStr(s.to_owned()));
-    }
-
-    // do we have a prefix matched
-    if opt_prefix.is_none() {
-        without_prefix = s;
-    }
-    else {
-        without_prefix = s.split_at(prefix_index).1;
-    }
-
-    let splited: Vec<_> = without_prefix.split(SPLIT).collect();
-
-    // for example, s is `-o=a!/@1`, prefix is `-`
-    if splited.len() == 2 {
-        // `o`
-        left_info = splited[0];
-        // `a!/@1`
-        right_info = splited[1];
-    }
-    else {
-        // without type, right_info is `-|o!/@1`
-        right_info = without_prefix;
-        left_info = without_prefix;
-    }
-
-    // if we have a `/`
-    if let Some(index) = right_info.rfind(DEACTIVATE) {
-        deactivate = Some(true);
-        if index != 0 {
-            splited_index = index;
-        }
-    }
-    // if we have a `!`
-    if let Some(index) = right_info.rfind(NO_OPTIONAL) {
-        optional = Some(false);
-        if index != 0 && (index < splited_index || splited_index == 0) {
-            splited_index = index;
-        }
-    }
-    // if we have a `@`
-    if let Some(index) = right_info.rfind(INDEX) {
-        match right_info.split_at(index + 1).1.parse::<i64>() {
-            Ok(v) => {
-                if v > 0 {
-                    opt_index = NonOptIndex::forward(v as u64);
-                }
-                else if v < 0 {
-                    opt_index = NonOptIndex::backward((-v) as  u64);
-                }
-                else {
-                    opt_index = NonOptIndex::anywhere();
-                }
-            }
-            Err(_) => {
-                return Err(Error::InvalidOptionStr(s.to_owned()))
-            }
-        }
-        if index != 0 && (index < splited_index || splited_index == 0) {
-            splited_index = index;
-        }
-    }
-
-    if splited.len() == 2 {
-        let inner_opt_type;
-
-        if splited_index == 0 {
-            // we not have `/`, `!` or `@`, so right_info is option type
-            inner_opt_type = right_info;
-        } else {
-            // left part is option type
-            inner_opt_type = right_info.split_at(splited_index).0;
-        };
-        
-        type_name = Some(inner_opt_type.to_owned());
-        opt_name = Some(left_info.to_owned());
-    }
-    else {
-        if splited_index == 0 {
-            // we not have `/`, `!` or `@`, so right_info is option name part
-            opt_name = Some(right_info.to_owned());
-        }
-        else {
-            // left part is option name part
-            opt_name  = Some(right_info.split_at(splited_index).0.to_owned());         
-        }
-    }
-
-    debug!("Parsing ==> {:?} -> {:?} {:?} {:?}", s, opt_prefix, opt_name, type_name);
-
-    return Ok(ParseResult {
-        type_name,
-        opt_name,
-        opt_prefix,
-        deactivate,
-        optional,
-        opt_index,
-    })
+    pattern::parse_opt_string(s, prefixs).map_err(Error::InvalidOptionStr)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::utils::parse_opt_string;
+    use crate::utils::{parse_opt_string, CreateInfo};
     use crate::opt::NonOptIndex;
+    use crate::error::Error;
 
     #[test]
     fn str_can_parse_to_create_info() {
@@ -684,6 +726,14 @@ mod tests {
             ("option!/@-3", Some(("", "option", "", NonOptIndex::Backward(3), true, false))),
             ("option/!@-3", Some(("", "option", "", NonOptIndex::Backward(3), true, false))),
 
+            ("o=a@2..4", Some(("a", "o", "", NonOptIndex::Range(2, Some(4)), false, true))),
+            ("o=a!@2..", Some(("a", "o", "", NonOptIndex::Range(2, None), false, false))),
+            ("option@3..3", Some(("", "option", "", NonOptIndex::Range(3, Some(3)), false, true))),
+            ("o=a@4..2", None),
+            ("o=a@0..2", None),
+            ("o=a@..2", None),
+            ("o=a@2..b", None),
+
             ("o=a@1!", None),
             ("o=a@1/", None),
             ("o=a@1!/", None),
@@ -722,7 +772,7 @@ mod tests {
                     assert_eq!(ci.opt_prefix.as_ref().unwrap_or(&String::from("")).as_str(), test_ci.2);
                     assert_eq!(ci.opt_name.as_ref().unwrap_or(&String::from("")).as_str(), test_ci.1);
                     assert_eq!(ci.type_name.as_ref().unwrap_or(&String::from("")).as_str(), test_ci.0);
-                    assert_eq!(ci.opt_index, test_ci.3);
+                    assert_eq!(NonOptIndex::from(ci.opt_index.clone()), test_ci.3);
                     assert_eq!(ci.deactivate.as_ref().unwrap_or(&false), &test_ci.4);
                     assert_eq!(ci.optional.as_ref().unwrap_or(&true), &test_ci.5);
                 }
@@ -732,4 +782,80 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn create_info_can_be_checked() {
+        let prefixs = vec![String::from("--")];
+
+        assert!(CreateInfo::parse("--count=int", &prefixs).unwrap().check().unwrap());
+
+        let mut ci = CreateInfo::default();
+
+        ci.set_name("count");
+        assert!(matches!(ci.check(), Err(Error::NullOptionType)));
+
+        let mut ci = CreateInfo::default();
+
+        ci.set_type_name("int");
+        assert!(matches!(ci.check(), Err(Error::NullOptionName)));
+    }
+
+    #[test]
+    fn longest_prefix_can_be_matched() {
+        let prefixs = vec!["-".to_owned(), "/".to_owned(), "--".to_owned()];
+
+        let ret = parse_opt_string("--debug=bool", &prefixs).unwrap();
+
+        assert_eq!(ret.opt_prefix.as_ref().unwrap().as_str(), "--");
+        assert_eq!(ret.opt_name.as_ref().unwrap().as_str(), "debug");
+
+        let ret = parse_opt_string("-d=bool", &prefixs).unwrap();
+
+        assert_eq!(ret.opt_prefix.as_ref().unwrap().as_str(), "-");
+        assert_eq!(ret.opt_name.as_ref().unwrap().as_str(), "d");
+    }
+
+    #[test]
+    fn default_value_can_be_parsed() {
+        use crate::opt::OptValue;
+        use crate::set::{Set, DefaultSet};
+
+        let prefixs = vec!["-".to_owned(), "--".to_owned()];
+
+        let ci = CreateInfo::parse("--count=int{10}", &prefixs).unwrap();
+
+        assert_eq!(ci.get_name(), "count");
+        assert_eq!(ci.get_type_name(), "int");
+        assert_eq!(ci.get_default_value().as_int(), Some(&10));
+
+        let ci = CreateInfo::parse("--name=str!{hello}", &prefixs).unwrap();
+
+        assert_eq!(ci.is_optional(), false);
+        assert_eq!(ci.get_default_value().as_str(), Some(&String::from("hello")));
+
+        let ci = CreateInfo::parse("--tags=array{a,b}", &prefixs).unwrap();
+        let tags = ci.get_default_value().as_vec().unwrap();
+
+        assert_eq!(tags, &vec![String::from("a"), String::from("b")]);
+        // the default can be stored as string
+        assert_eq!(ci.get_default_value().to_storage_string(), Some(String::from("a,b,")));
+
+        let ci = CreateInfo::parse("--debug=bool", &prefixs).unwrap();
+
+        assert!(ci.get_default_value().is_null());
+
+        assert!(matches!(CreateInfo::parse("--count=int{abc}", &prefixs), Err(Error::InvaldOptionValue(v, _)) if v == "abc"));
+        assert!(matches!(CreateInfo::parse("--count=int{10", &prefixs), Err(Error::InvalidOptionStr(_))));
+
+        let mut set = DefaultSet::new();
+
+        set.initialize_utils().unwrap();
+        set.initialize_prefixs();
+
+        let id = set.add_opt("--count=int{10}").unwrap().commit().unwrap();
+
+        assert_eq!(set.get_opt(id).unwrap().default_value(), &OptValue::from_int(10));
+        assert_eq!(set.get_opt(id).unwrap().value().as_int(), Some(&10));
+        assert!(set.add_opt("--ratio=flt{fast}").is_err());
+    }
 }
@@ -0,0 +1,88 @@
+#![cfg(feature="async")]
+
+use std::cell::RefCell;
+use std::future::Future;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+
+use async_trait::async_trait;
+use getopt_rs::prelude::*;
+use getopt_rs::callback::ValueCallback;
+
+struct NoopWaker;
+
+impl Wake for NoopWaker {
+    fn wake(self: Arc<Self>) { }
+}
+
+/// The parser never wait on anything, so poll the future until it ready.
+fn block_on<F: Future>(future: F) -> F::Output {
+    let waker = Waker::from(Arc::new(NoopWaker));
+    let mut cx = Context::from_waker(&waker);
+    let mut future = Box::pin(future);
+
+    loop {
+        if let Poll::Ready(value) = future.as_mut().poll(&mut cx) {
+            return value;
+        }
+    }
+}
+
+#[derive(Debug)]
+struct RecordCallback(Rc<RefCell<Vec<i64>>>);
+
+#[async_trait(?Send)]
+impl ValueCallback for RecordCallback {
+    async fn call(&mut self, opt: &dyn Opt) -> Result<bool> {
+        self.0.borrow_mut().push(*opt.value().as_int().unwrap());
+        Ok(true)
+    }
+}
+
+#[test]
+fn make_sure_async_delay_parser_work() {
+    let mut parser = DelayParser::new(DefaultIdGen::default());
+    let mut set = DefaultSet::new();
+    let record = Rc::new(RefCell::new(vec![]));
+
+    set.initialize_utils().unwrap();
+    set.initialize_prefixs();
+
+    let count = set.add_opt("--count=int").unwrap().commit().unwrap();
+
+    parser.set_callback(count, OptCallback::from_value(Box::new(RecordCallback(record.clone()))));
+    set.subscribe_from(&mut parser);
+    parser.publish_to(set);
+
+    let mut ai = ArgIterator::new();
+
+    ai.set_args(&mut ["--count", "42"].iter().map(|&v|String::from(v)));
+    assert!(block_on(parser.parse(&mut ai)).unwrap().is_some());
+    assert_eq!(parser.value_of_int(count).unwrap(), 42);
+    assert_eq!(record.borrow().as_slice(), &[42]);
+}
+
+#[test]
+fn make_sure_async_forward_parser_work() {
+    let mut parser = ForwardParser::new(DefaultIdGen::default());
+    let mut set = DefaultSet::new();
+    let record = Rc::new(RefCell::new(vec![]));
+
+    set.initialize_utils().unwrap();
+    set.initialize_prefixs();
+
+    let count = set.add_opt("--count=int").unwrap().commit().unwrap();
+
+    parser.set_callback(count, OptCallback::from_value(Box::new(RecordCallback(record.clone()))));
+    set.subscribe_from(&mut parser);
+    parser.publish_to(set);
+
+    let mut ai = ArgIterator::new();
+
+    ai.set_args(&mut ["--count", "42", "a.txt", "--count=7", "--", "--count"].iter().map(|&v|String::from(v)));
+    assert!(block_on(parser.parse(&mut ai)).unwrap().is_some());
+    assert_eq!(parser.value_of_int(count).unwrap(), 7);
+    assert_eq!(record.borrow().as_slice(), &[42, 7]);
+    assert_eq!(parser.noa(), &vec![String::from("a.txt"), String::from("--count")]);
+}
@@ -0,0 +1,33 @@
+//! Build the option string parser in a `no_std` crate, make sure it only depend on `core` and `alloc`.
+#![no_std]
+
+extern crate alloc;
+
+#[macro_use]
+extern crate log;
+
+#[path = "../src/pattern.rs"]
+mod pattern;
+
+use alloc::string::String;
+use alloc::vec;
+
+use pattern::{parse_opt_string, PatternIndex};
+
+#[test]
+fn make_sure_no_std_pattern_work() {
+    let prefixs = vec![String::from("--"), String::from("-")];
+    let pr = parse_opt_string("--name=int!", &prefixs).unwrap();
+
+    assert_eq!(pr.opt_prefix.as_deref(), Some("--"));
+    assert_eq!(pr.opt_name.as_deref(), Some("name"));
+    assert_eq!(pr.type_name.as_deref(), Some("int"));
+    assert_eq!(pr.optional, Some(false));
+    assert_eq!(pr.deactivate, None);
+    assert_eq!(pr.opt_index, PatternIndex::Null);
+
+    let pr = parse_opt_string("file=pos@1..3", &prefixs).unwrap();
+
+    assert_eq!(pr.opt_index, PatternIndex::Range(1, Some(3)));
+    assert_eq!(parse_opt_string("--", &prefixs).unwrap_err(), "--");
+}
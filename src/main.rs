@@ -46,7 +46,7 @@ fn example3() {
                 let ret = (*writer)
                     .iter()
                     .filter(|&v| std::path::Path::new(v.as_str()).is_dir())
-                    .map(|v| v.clone())
+                    .cloned()
                     .collect();
                 *writer = ret;
                 Ok(true)
@@ -64,7 +64,7 @@ fn example3() {
                 let ret = (*writer)
                     .iter()
                     .filter(|&v| std::path::Path::new(v.as_str()).is_file())
-                    .map(|v| v.clone())
+                    .cloned()
                     .collect();
                 *writer = ret;
                 Ok(true)
@@ -82,7 +82,7 @@ fn example3() {
                 let ret = (*writer)
                     .iter()
                     .filter(|&v| std::path::Path::new(v.as_str()).read_link().is_ok())
-                    .map(|v| v.clone())
+                    .cloned()
                     .collect();
                 *writer = ret;
                 Ok(true)
@@ -103,7 +103,7 @@ fn example3() {
                         let metadata = std::fs::metadata(v).unwrap();
                         metadata.len() > *opt.value().as_uint().unwrap()
                     })
-                    .map(|v| v.clone())
+                    .cloned()
                     .collect();
                 *writer = ret;
                 Ok(true)